@@ -1,6 +1,8 @@
 #!/usr/bin/env python3
 """
 Spits out tests to dump into lib.rs for each file in the schema-examples directory.
+
+`cargo xtask conformance` does the same for every type in spec/SpaceTraders.json.
 """
 
 
@@ -12,7 +14,7 @@ schema_dir = root / "schema-examples"
 target_file = root / "src" / "tests" / "schema.rs"
 
 
-start = r"""use crate::api::schemas;
+start = r"""use crate::api::schema;
 
 macro_rules! json_deserialize_test {
     ($name:ident, $struct_type:ty, $file_path:expr) => {
@@ -48,7 +50,7 @@ def main():
         print(
             "json_deserialize_test!(test_",
             snake_name,
-            "_schema, schemas::",
+            "_schema, schema::",
             title_name,
             ', "schema-examples/',
             path.name,
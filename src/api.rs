@@ -1,20 +1,239 @@
-use serde::de;
+use crate::cache::{Cache, CacheKind};
+use crate::history::PriceHistory;
+use crate::recording::Recorder;
+use crate::state::AgentState;
+use serde::{de, Serialize};
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
 
+pub mod endpoints;
 pub mod responses;
 pub mod results;
 pub mod schema;
+pub mod transport;
+
+use transport::{Recorded, ReqwestTransport, Transport};
 
 // generic api response stuff
 
-pub fn decode_api_response<T: de::DeserializeOwned>(
-    s: &str,
-) -> Result<T, Box<dyn std::error::Error>> {
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+pub const BASE_URL: &str = "https://api.spacetraders.io/v2";
+
+pub fn decode_api_response<T: de::DeserializeOwned>(s: &str) -> Result<T, Error> {
     serde_json::from_str::<results::ApiResult<T>>(s)?.to_result()
 }
 
 pub struct SpaceTraderApi {
     token: String,
-    client: reqwest::Client,
+    transport: Box<dyn Transport>,
+    base_url: String,
+    cache: Option<Cache>,
+    price_history: Option<PriceHistory>,
+    state: Arc<AgentState>,
+    force_refresh: bool,
 }
 
-impl SpaceTraderApi {}
+impl SpaceTraderApi {
+    pub fn new(token: String) -> Self {
+        Self {
+            token,
+            transport: Box::new(ReqwestTransport::new()),
+            base_url: BASE_URL.to_owned(),
+            cache: None,
+            price_history: None,
+            state: Arc::new(AgentState::new()),
+            force_refresh: false,
+        }
+    }
+
+    pub fn token(&self) -> &str { &self.token }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        //! points the client somewhere other than the real server, like a mock::MockServer
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+        self
+    }
+
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        //! replaces the transport outright, middleware and all
+        self.transport = Box::new(transport);
+        self
+    }
+
+    pub fn layer<L, F>(mut self, wrap: F) -> Self
+    where
+        L: Transport + 'static,
+        F: FnOnce(Box<dyn Transport>) -> L, {
+        //! wraps the current transport in a middleware, e.g.
+        //! `api.layer(RateLimited::spacetraders).layer(|t| Retrying::new(t, 3))`
+        self.transport = Box::new(wrap(self.transport));
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub async fn with_cache_file(self, path: impl AsRef<Path>) -> Result<Self, Error> {
+        //! opens (or creates) a cache at path for the server's current reset
+        let status = self.status().await?;
+        let cache = Cache::open(path, &status.reset_date)?;
+        Ok(self.with_cache(cache))
+    }
+
+    pub fn cache(&self) -> Option<&Cache> { self.cache.as_ref() }
+
+    pub fn with_price_history(mut self, price_history: PriceHistory) -> Self {
+        //! every market fetched from the server gets recorded into price_history
+        self.price_history = Some(price_history);
+        self
+    }
+
+    pub fn price_history(&self) -> Option<&PriceHistory> { self.price_history.as_ref() }
+
+    pub fn with_agent_state(mut self, state: Arc<AgentState>) -> Self {
+        //! shares state with other clients, so all of them keep it up to date
+        self.state = state;
+        self
+    }
+
+    pub fn agent_state(&self) -> &Arc<AgentState> { &self.state }
+
+    pub fn with_recorder(self, recorder: Recorder) -> Self {
+        //! every request and response from here on gets written to recorder
+        self.layer(|transport| Recorded::new(transport, recorder))
+    }
+
+    pub fn set_force_refresh(&mut self, force_refresh: bool) {
+        //! when set, cached endpoints always hit the server and overwrite
+        //! whatever the cache had stored
+        self.force_refresh = force_refresh;
+    }
+
+    pub async fn status(&self) -> Result<responses::GetStatus, Error> {
+        // the status endpoint is the only one not wrapped in a "data" field
+        let text = self.send(reqwest::Method::GET, "/", None).await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<String, Error> {
+        let request = transport::Request {
+            method,
+            base_url: self.base_url.clone(),
+            path: path.to_owned(),
+            token: self.token.clone(),
+            body: body.cloned(),
+        };
+        let response = self.transport.send(request).await?;
+        // only our own agent's endpoints can say anything about it
+        if response.status < 300 && (path.starts_with("/my/") || path == "/register") {
+            self.state.observe(path, body, &response.body);
+        }
+        Ok(response.body)
+    }
+
+    async fn get<T: de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<results::ApiResult<T>, Error> {
+        let text = self.send(reqwest::Method::GET, path, None).await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    pub(crate) async fn fetch<T: de::DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        self.get(path).await?.to_result()
+    }
+
+    pub(crate) async fn fetch_optional<T: de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Option<T>, Error> {
+        //! for endpoints that answer 204 with no body when there's nothing to show
+        let text = self.send(reqwest::Method::GET, path, None).await?;
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        serde_json::from_str::<results::ApiResult<T>>(&text)?
+            .to_result()
+            .map(Some)
+    }
+
+    pub(crate) async fn post<T: de::DeserializeOwned>(
+        &self,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<T, Error> {
+        let text = self
+            .send(reqwest::Method::POST, path, body.as_ref())
+            .await?;
+        serde_json::from_str::<results::ApiResult<T>>(&text)?.to_result()
+    }
+
+    pub(crate) async fn patch<T: de::DeserializeOwned>(
+        &self,
+        path: &str,
+        body: serde_json::Value,
+    ) -> Result<T, Error> {
+        let text = self.send(reqwest::Method::PATCH, path, Some(&body)).await?;
+        serde_json::from_str::<results::ApiResult<T>>(&text)?.to_result()
+    }
+
+    pub(crate) async fn fetch_all<T: de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Vec<T>, Error> {
+        //! walks every page of a list endpoint
+        let mut items = Vec::new();
+        for page in 1.. {
+            let result = self
+                .get::<Vec<T>>(&format!("{path}?page={page}&limit=20"))
+                .await?;
+            let total = result.meta().map_or(0, |meta| meta.total as usize);
+            let data = result.to_result()?;
+            if data.is_empty() {
+                break;
+            }
+            items.extend(data);
+            if items.len() >= total {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    async fn cached<T, F>(&self, kind: CacheKind, symbol: &str, fetch: F) -> Result<T, Error>
+    where
+        T: Serialize + de::DeserializeOwned,
+        F: Future<Output = Result<T, Error>>, {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return fetch.await,
+        };
+        if !self.force_refresh {
+            if let Some(value) = cache.get(kind, symbol)? {
+                return Ok(value);
+            }
+        }
+        self.refreshed(kind, symbol, fetch).await
+    }
+
+    async fn refreshed<T, F>(&self, kind: CacheKind, symbol: &str, fetch: F) -> Result<T, Error>
+    where
+        T: Serialize + de::DeserializeOwned,
+        F: Future<Output = Result<T, Error>>, {
+        //! fetches without looking in the cache, then stores what came back
+        let value = fetch.await?;
+        if let Some(cache) = &self.cache {
+            cache.put(kind, symbol, &value)?;
+        }
+        Ok(value)
+    }
+}
@@ -0,0 +1,11 @@
+/*
+The endpoints module is the other half of api::responses: it
+knows how to actually reach each endpoint. Every submodule adds
+its methods directly onto SpaceTraderApi.
+*/
+
+mod agents;
+mod contracts;
+mod factions;
+mod fleet;
+mod systems;
@@ -0,0 +1,16 @@
+use crate::api::responses::{agents, Register};
+use crate::api::schema::FactionSymbol;
+use crate::api::{Error, SpaceTraderApi};
+use serde_json::json;
+
+impl SpaceTraderApi {
+    pub async fn my_agent(&self) -> Result<agents::MyAgentDetails, Error> {
+        self.fetch("/my/agent").await
+    }
+
+    pub async fn register(&self, symbol: &str, faction: &FactionSymbol) -> Result<Register, Error> {
+        //! the one endpoint that doesn't need a token; the token comes back in the result
+        let body = json!({ "symbol": symbol, "faction": faction });
+        self.post("/register", Some(body)).await
+    }
+}
@@ -0,0 +1,43 @@
+use crate::api::responses::contracts;
+use crate::api::schema::{ShipSymbol, TradeSymbol};
+use crate::api::{Error, SpaceTraderApi};
+use serde_json::json;
+
+impl SpaceTraderApi {
+    pub async fn list_contracts(&self) -> Result<contracts::ListContracts, Error> {
+        self.fetch_all("/my/contracts").await
+    }
+
+    pub async fn get_contract(&self, contract_id: &str) -> Result<contracts::GetContract, Error> {
+        self.fetch(&format!("/my/contracts/{contract_id}")).await
+    }
+
+    pub async fn accept_contract(
+        &self,
+        contract_id: &str,
+    ) -> Result<contracts::AcceptContract, Error> {
+        self.post(&format!("/my/contracts/{contract_id}/accept"), None)
+            .await
+    }
+
+    pub async fn deliver_contract(
+        &self,
+        contract_id: &str,
+        ship_symbol: &ShipSymbol,
+        trade_symbol: TradeSymbol,
+        units: u32,
+    ) -> Result<contracts::DeliverContract, Error> {
+        let body =
+            json!({ "shipSymbol": ship_symbol, "tradeSymbol": trade_symbol, "units": units });
+        self.post(&format!("/my/contracts/{contract_id}/deliver"), Some(body))
+            .await
+    }
+
+    pub async fn fulfill_contract(
+        &self,
+        contract_id: &str,
+    ) -> Result<contracts::FulfillContract, Error> {
+        self.post(&format!("/my/contracts/{contract_id}/fulfill"), None)
+            .await
+    }
+}
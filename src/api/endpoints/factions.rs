@@ -0,0 +1,16 @@
+use crate::api::responses::factions;
+use crate::api::schema::FactionSymbol;
+use crate::api::{Error, SpaceTraderApi};
+
+impl SpaceTraderApi {
+    pub async fn list_factions(&self) -> Result<factions::ListFactions, Error> {
+        self.fetch_all("/factions").await
+    }
+
+    pub async fn get_faction(
+        &self,
+        faction_symbol: &FactionSymbol,
+    ) -> Result<factions::GetFaction, Error> {
+        self.fetch(&format!("/factions/{faction_symbol}")).await
+    }
+}
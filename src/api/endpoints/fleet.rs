@@ -0,0 +1,221 @@
+use crate::api::responses::fleet;
+use crate::api::schema::{
+    ShipNavFlightMode, ShipSymbol, ShipType, Survey, SystemSymbol, TradeSymbol, WaypointSymbol,
+};
+use crate::api::{Error, SpaceTraderApi};
+use crate::cache::CacheKind;
+use serde_json::json;
+
+impl SpaceTraderApi {
+    pub async fn list_ships(&self) -> Result<fleet::ListShips, Error> {
+        self.fetch_all("/my/ships").await
+    }
+
+    pub async fn purchase_ship(
+        &self,
+        ship_type: ShipType,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<fleet::PurchaseShip, Error> {
+        let body = json!({ "shipType": ship_type, "waypointSymbol": waypoint_symbol });
+        self.post("/my/ships", Some(body)).await
+    }
+
+    pub async fn get_ship(&self, ship_symbol: &ShipSymbol) -> Result<fleet::GetShip, Error> {
+        self.fetch(&format!("/my/ships/{ship_symbol}")).await
+    }
+
+    pub async fn get_ship_cargo(
+        &self,
+        ship_symbol: &ShipSymbol,
+    ) -> Result<fleet::GetShipCargo, Error> {
+        self.fetch(&format!("/my/ships/{ship_symbol}/cargo")).await
+    }
+
+    pub async fn orbit_ship(&self, ship_symbol: &ShipSymbol) -> Result<fleet::OrbitShip, Error> {
+        self.post(&format!("/my/ships/{ship_symbol}/orbit"), None)
+            .await
+    }
+
+    pub async fn ship_refine(
+        &self,
+        ship_symbol: &ShipSymbol,
+        produce: TradeSymbol,
+    ) -> Result<fleet::ShipRefine, Error> {
+        let body = json!({ "produce": produce });
+        self.post(&format!("/my/ships/{ship_symbol}/refine"), Some(body))
+            .await
+    }
+
+    pub async fn create_chart(
+        &self,
+        ship_symbol: &ShipSymbol,
+    ) -> Result<fleet::CreateChart, Error> {
+        let charted: fleet::CreateChart = self
+            .post(&format!("/my/ships/{ship_symbol}/chart"), None)
+            .await?;
+        // the cached copies still say it's uncharted
+        if let Some(cache) = &self.cache {
+            let waypoint = &charted.waypoint;
+            cache.put(CacheKind::Waypoint, waypoint.symbol.as_str(), waypoint)?;
+            cache.invalidate(CacheKind::WaypointList, waypoint.system_symbol.as_str())?;
+        }
+        Ok(charted)
+    }
+
+    pub async fn get_ship_cooldown(
+        &self,
+        ship_symbol: &ShipSymbol,
+    ) -> Result<fleet::GetShipCooldown, Error> {
+        self.fetch_optional(&format!("/my/ships/{ship_symbol}/cooldown"))
+            .await
+    }
+
+    pub async fn dock_ship(&self, ship_symbol: &ShipSymbol) -> Result<fleet::DockShip, Error> {
+        self.post(&format!("/my/ships/{ship_symbol}/dock"), None)
+            .await
+    }
+
+    pub async fn create_survey(
+        &self,
+        ship_symbol: &ShipSymbol,
+    ) -> Result<fleet::CreateSurvey, Error> {
+        self.post(&format!("/my/ships/{ship_symbol}/survey"), None)
+            .await
+    }
+
+    pub async fn extract_resources(
+        &self,
+        ship_symbol: &ShipSymbol,
+        survey: Option<&Survey>,
+    ) -> Result<fleet::ExtractResources, Error> {
+        let body = survey.map(|survey| json!({ "survey": survey }));
+        self.post(&format!("/my/ships/{ship_symbol}/extract"), body)
+            .await
+    }
+
+    pub async fn jettison_cargo(
+        &self,
+        ship_symbol: &ShipSymbol,
+        trade_symbol: TradeSymbol,
+        units: u32,
+    ) -> Result<fleet::JettisonCargo, Error> {
+        let body = json!({ "symbol": trade_symbol, "units": units });
+        self.post(&format!("/my/ships/{ship_symbol}/jettison"), Some(body))
+            .await
+    }
+
+    pub async fn jump_ship(
+        &self,
+        ship_symbol: &ShipSymbol,
+        system_symbol: &SystemSymbol,
+    ) -> Result<fleet::JumpShip, Error> {
+        let body = json!({ "systemSymbol": system_symbol });
+        self.post(&format!("/my/ships/{ship_symbol}/jump"), Some(body))
+            .await
+    }
+
+    pub async fn navigate_ship(
+        &self,
+        ship_symbol: &ShipSymbol,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<fleet::NavigateShip, Error> {
+        let body = json!({ "waypointSymbol": waypoint_symbol });
+        self.post(&format!("/my/ships/{ship_symbol}/navigate"), Some(body))
+            .await
+    }
+
+    pub async fn patch_ship_nav(
+        &self,
+        ship_symbol: &ShipSymbol,
+        flight_mode: ShipNavFlightMode,
+    ) -> Result<fleet::PatchShipNav, Error> {
+        let body = json!({ "flightMode": flight_mode });
+        self.patch(&format!("/my/ships/{ship_symbol}/nav"), body)
+            .await
+    }
+
+    pub async fn get_ship_nav(&self, ship_symbol: &ShipSymbol) -> Result<fleet::GetShipNav, Error> {
+        self.fetch(&format!("/my/ships/{ship_symbol}/nav")).await
+    }
+
+    pub async fn warp_ship(
+        &self,
+        ship_symbol: &ShipSymbol,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<fleet::WarpShip, Error> {
+        let body = json!({ "waypointSymbol": waypoint_symbol });
+        self.post(&format!("/my/ships/{ship_symbol}/warp"), Some(body))
+            .await
+    }
+
+    pub async fn sell_cargo(
+        &self,
+        ship_symbol: &ShipSymbol,
+        trade_symbol: TradeSymbol,
+        units: u32,
+    ) -> Result<fleet::SellCargo, Error> {
+        let body = json!({ "symbol": trade_symbol, "units": units });
+        self.post(&format!("/my/ships/{ship_symbol}/sell"), Some(body))
+            .await
+    }
+
+    pub async fn scan_systems(
+        &self,
+        ship_symbol: &ShipSymbol,
+    ) -> Result<fleet::ScanSystems, Error> {
+        self.post(&format!("/my/ships/{ship_symbol}/scan/systems"), None)
+            .await
+    }
+
+    pub async fn scan_waypoints(
+        &self,
+        ship_symbol: &ShipSymbol,
+    ) -> Result<fleet::ScanWaypoints, Error> {
+        self.post(&format!("/my/ships/{ship_symbol}/scan/waypoints"), None)
+            .await
+    }
+
+    pub async fn scan_ships(&self, ship_symbol: &ShipSymbol) -> Result<fleet::ScanShips, Error> {
+        self.post(&format!("/my/ships/{ship_symbol}/scan/ships"), None)
+            .await
+    }
+
+    pub async fn refuel_ship(&self, ship_symbol: &ShipSymbol) -> Result<fleet::RefuelShip, Error> {
+        self.post(&format!("/my/ships/{ship_symbol}/refuel"), None)
+            .await
+    }
+
+    pub async fn purchase_cargo(
+        &self,
+        ship_symbol: &ShipSymbol,
+        trade_symbol: TradeSymbol,
+        units: u32,
+    ) -> Result<fleet::PurchaseCargo, Error> {
+        let body = json!({ "symbol": trade_symbol, "units": units });
+        self.post(&format!("/my/ships/{ship_symbol}/purchase"), Some(body))
+            .await
+    }
+
+    pub async fn transfer_cargo(
+        &self,
+        ship_symbol: &ShipSymbol,
+        trade_symbol: TradeSymbol,
+        units: u32,
+        to_ship_symbol: &ShipSymbol,
+    ) -> Result<fleet::TransferCargo, Error> {
+        let body =
+            json!({ "tradeSymbol": trade_symbol, "units": units, "shipSymbol": to_ship_symbol });
+        self.post(&format!("/my/ships/{ship_symbol}/transfer"), Some(body))
+            .await
+    }
+
+    pub async fn negotiate_contract(
+        &self,
+        ship_symbol: &ShipSymbol,
+    ) -> Result<fleet::NegotiateContract, Error> {
+        //! asks the faction at the waypoint the ship is docked at for a new
+        //! contract, which still has to be accepted
+        self.post(&format!("/my/ships/{ship_symbol}/negotiate/contract"), None)
+            .await
+    }
+}
@@ -0,0 +1,151 @@
+use crate::api::responses::systems;
+use crate::api::schema::{SystemSymbol, WaypointSymbol};
+use crate::api::{Error, SpaceTraderApi};
+use crate::cache::CacheKind;
+
+impl SpaceTraderApi {
+    pub async fn list_systems(&self) -> Result<systems::ListSystems, Error> {
+        let systems: systems::ListSystems = self
+            .cached(CacheKind::SystemList, "*", self.fetch_all("/systems"))
+            .await?;
+        if let Some(cache) = &self.cache {
+            for system in &systems {
+                cache.put(CacheKind::System, system.symbol.as_str(), system)?;
+            }
+        }
+        Ok(systems)
+    }
+
+    pub async fn get_system(
+        &self,
+        system_symbol: &SystemSymbol,
+    ) -> Result<systems::GetSystem, Error> {
+        let path = format!("/systems/{system_symbol}");
+        self.cached(CacheKind::System, system_symbol.as_str(), self.fetch(&path))
+            .await
+    }
+
+    pub async fn list_waypoints(
+        &self,
+        system_symbol: &SystemSymbol,
+    ) -> Result<systems::ListWaypoints, Error> {
+        let path = format!("/systems/{system_symbol}/waypoints");
+        let waypoints: systems::ListWaypoints = self
+            .cached(
+                CacheKind::WaypointList,
+                system_symbol.as_str(),
+                self.fetch_all(&path),
+            )
+            .await?;
+        if let Some(cache) = &self.cache {
+            for waypoint in &waypoints {
+                cache.put(CacheKind::Waypoint, waypoint.symbol.as_str(), waypoint)?;
+            }
+        }
+        Ok(waypoints)
+    }
+
+    pub async fn get_waypoint(
+        &self,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<systems::GetWaypoint, Error> {
+        let system_symbol = waypoint_symbol.system();
+        let path = format!("/systems/{system_symbol}/waypoints/{waypoint_symbol}");
+        self.cached(
+            CacheKind::Waypoint,
+            waypoint_symbol.as_str(),
+            self.fetch(&path),
+        )
+        .await
+    }
+
+    pub async fn get_market(
+        &self,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<systems::GetMarket, Error> {
+        self.cached(
+            CacheKind::Market,
+            waypoint_symbol.as_str(),
+            self.fetch_market(waypoint_symbol),
+        )
+        .await
+    }
+
+    pub async fn refresh_market(
+        &self,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<systems::GetMarket, Error> {
+        //! like get_market, but always asks the server, for when a ship is
+        //! there and the prices are what's wanted
+        self.refreshed(
+            CacheKind::Market,
+            waypoint_symbol.as_str(),
+            self.fetch_market(waypoint_symbol),
+        )
+        .await
+    }
+
+    async fn fetch_market(
+        &self,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<systems::GetMarket, Error> {
+        let system_symbol = waypoint_symbol.system();
+        let path = format!("/systems/{system_symbol}/waypoints/{waypoint_symbol}/market");
+        let market: systems::GetMarket = self.fetch(&path).await?;
+        if let Some(price_history) = &self.price_history {
+            price_history.record(&market)?;
+        }
+        Ok(market)
+    }
+
+    pub async fn get_shipyard(
+        &self,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<systems::GetShipyard, Error> {
+        self.cached(
+            CacheKind::Shipyard,
+            waypoint_symbol.as_str(),
+            self.fetch_shipyard(waypoint_symbol),
+        )
+        .await
+    }
+
+    pub async fn refresh_shipyard(
+        &self,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<systems::GetShipyard, Error> {
+        self.refreshed(
+            CacheKind::Shipyard,
+            waypoint_symbol.as_str(),
+            self.fetch_shipyard(waypoint_symbol),
+        )
+        .await
+    }
+
+    async fn fetch_shipyard(
+        &self,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<systems::GetShipyard, Error> {
+        let system_symbol = waypoint_symbol.system();
+        let path = format!("/systems/{system_symbol}/waypoints/{waypoint_symbol}/shipyard");
+        let shipyard: systems::GetShipyard = self.fetch(&path).await?;
+        if let Some(price_history) = &self.price_history {
+            price_history.record_shipyard(&shipyard)?;
+        }
+        Ok(shipyard)
+    }
+
+    pub async fn get_jump_gate(
+        &self,
+        waypoint_symbol: &WaypointSymbol,
+    ) -> Result<systems::GetJumpGate, Error> {
+        let system_symbol = waypoint_symbol.system();
+        let path = format!("/systems/{system_symbol}/waypoints/{waypoint_symbol}/jump-gate");
+        self.cached(
+            CacheKind::JumpGate,
+            waypoint_symbol.as_str(),
+            self.fetch(&path),
+        )
+        .await
+    }
+}
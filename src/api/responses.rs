@@ -1,3 +1,6 @@
+// generated by `cargo xtask schema` from spec/SpaceTraders.json.
+// don't edit it by hand; change the spec or xtask/src/schema.rs instead
+
 /*
 The responses module contains information about what the different
 endpoints return. It does not contain any information about how to
@@ -7,20 +10,35 @@ connect to those endpoints.
 use crate::api::schema;
 use serde::{Deserialize, Serialize};
 
+pub mod agents;
 pub mod contracts;
 pub mod factions;
 pub mod fleet;
 pub mod systems;
 
-pub mod agents {
-    pub type MyAgentDetails = crate::api::schema::Agent;
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetStatus {
+    pub status: String,
+    pub version: String,
+    pub reset_date: String,
+    pub description: String,
+    pub stats: Stats,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub agents: i32,
+    pub ships: i32,
+    pub systems: i32,
+    pub waypoints: i32,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Register {
-    token: String,
-    agent: schema::Agent,
-    contract: schema::Contract,
-    faction: schema::Faction,
-    ship: schema::Ship,
+    pub agent: schema::Agent,
+    pub contract: schema::Contract,
+    pub faction: schema::Faction,
+    pub ship: schema::Ship,
+    pub token: String,
 }
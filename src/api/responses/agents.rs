@@ -0,0 +1,6 @@
+// generated by `cargo xtask schema` from spec/SpaceTraders.json.
+// don't edit it by hand; change the spec or xtask/src/schema.rs instead
+
+use crate::api::schema;
+
+pub type MyAgentDetails = schema::Agent;
@@ -1,23 +1,27 @@
+// generated by `cargo xtask schema` from spec/SpaceTraders.json.
+// don't edit it by hand; change the spec or xtask/src/schema.rs instead
+
 use crate::api::schema;
 use serde::{Deserialize, Serialize};
 
 pub type ListContracts = Vec<schema::Contract>;
+
 pub type GetContract = schema::Contract;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct AcceptContract {
-    agent: schema::Agent,
-    contract: schema::Contract,
+    pub agent: schema::Agent,
+    pub contract: schema::Contract,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct DeliverContract {
-    contract: schema::Contract,
-    cargo: schema::ShipCargo
+    pub contract: schema::Contract,
+    pub cargo: schema::ShipCargo,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct FulfillContract {
-    agent: schema::Agent,
-    contract: schema::Contract,
+    pub agent: schema::Agent,
+    pub contract: schema::Contract,
 }
@@ -1,4 +1,8 @@
+// generated by `cargo xtask schema` from spec/SpaceTraders.json.
+// don't edit it by hand; change the spec or xtask/src/schema.rs instead
+
 use crate::api::schema;
 
 pub type ListFactions = Vec<schema::Faction>;
+
 pub type GetFaction = schema::Faction;
@@ -1,120 +1,139 @@
+// generated by `cargo xtask schema` from spec/SpaceTraders.json.
+// don't edit it by hand; change the spec or xtask/src/schema.rs instead
+
 use crate::api::schema;
 use serde::{Deserialize, Serialize};
 
 pub type ListShips = Vec<schema::Ship>;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct PurchaseShip {
-    agent: schema::Agent,
-    ship: schema::Ship,
-    transaction: schema::MarketTransaction,
+    pub agent: schema::Agent,
+    pub ship: schema::Ship,
+    pub transaction: schema::ShipyardTransaction,
 }
 
 pub type GetShip = schema::Ship;
+
 pub type GetShipCargo = schema::ShipCargo;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct OrbitShip {
-    nav: schema::ShipNav,
+    pub nav: schema::ShipNav,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ShipRefine {
-    cargo: schema::ShipCargo,
-    cooldown: schema::Cooldown,
-    produced: schema::Produce,
-    consumed: schema::Produce,
+    pub cargo: schema::ShipCargo,
+    pub cooldown: schema::Cooldown,
+    pub produced: Vec<Produce>,
+    pub consumed: Vec<Produce>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Produce {
+    pub trade_symbol: schema::TradeSymbol,
+    pub units: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CreateChart {
-    chart: schema::Chart,
-    waypoint: schema::Waypoint,
+    pub chart: schema::Chart,
+    pub waypoint: schema::Waypoint,
 }
 
 pub type GetShipCooldown = Option<schema::Cooldown>;
-#[derive(Serialize, Deserialize, Debug)]
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct DockShip {
-    nav: schema::ShipNav,
+    pub nav: schema::ShipNav,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CreateSurvey {
-    cooldown: schema::Cooldown,
-    surveys: schema::Survey,
+    pub cooldown: schema::Cooldown,
+    pub surveys: Vec<schema::Survey>,
 }
-#[derive(Serialize, Deserialize, Debug)]
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ExtractResources {
-    cooldown: schema::Cooldown,
-    extraction: schema::Extraction,
-    cargo: schema::ShipCargo,
+    pub cooldown: schema::Cooldown,
+    pub extraction: schema::Extraction,
+    pub cargo: schema::ShipCargo,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct JettisonCargo {
-    cargo: schema::ShipCargo,
+    pub cargo: schema::ShipCargo,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct JumpShip {
-    cooldown: schema::Cooldown,
-    nav: schema::ShipNav,
+    pub cooldown: schema::Cooldown,
+    pub nav: schema::ShipNav,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct NavigateShip {
-    fuel: schema::ShipFuel,
-    nav: schema::ShipNav,
+    pub fuel: schema::ShipFuel,
+    pub nav: schema::ShipNav,
 }
 
 pub type PatchShipNav = schema::ShipNav;
+
 pub type GetShipNav = schema::ShipNav;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct WarpShip {
-    fuel: schema::ShipFuel,
-    nav: schema::ShipNav,
+    pub fuel: schema::ShipFuel,
+    pub nav: schema::ShipNav,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct SellCargo {
-    agent: schema::Agent,
-    cargo: schema::ShipCargo,
-    transaction: schema::MarketTransaction,
+    pub agent: schema::Agent,
+    pub cargo: schema::ShipCargo,
+    pub transaction: schema::MarketTransaction,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ScanSystems {
-    cooldown: schema::Cooldown,
-    systems: Vec<schema::ScannedSystem>,
+    pub cooldown: schema::Cooldown,
+    pub systems: Vec<schema::ScannedSystem>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ScanWaypoints {
-    cooldown: schema::Cooldown,
-    waypoints: Vec<schema::ScannedWaypoint>,
+    pub cooldown: schema::Cooldown,
+    pub waypoints: Vec<schema::ScannedWaypoint>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ScanShips {
-    cooldown: schema::Cooldown,
-    ships: Vec<schema::ScannedShip>,
+    pub cooldown: schema::Cooldown,
+    pub ships: Vec<schema::ScannedShip>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct RefuelShip {
-    agent: schema::Agent,
-    fuel: schema::ShipFuel,
+    pub agent: schema::Agent,
+    pub fuel: schema::ShipFuel,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct PurchaseCargo {
-    agent: schema::Agent,
-    cargo: schema::ShipCargo,
-    transaction: schema::MarketTransaction,
+    pub agent: schema::Agent,
+    pub cargo: schema::ShipCargo,
+    pub transaction: schema::MarketTransaction,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct TransferCargo {
-    cargo: schema::ShipCargo,
+    pub cargo: schema::ShipCargo,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NegotiateContract {
+    pub contract: schema::Contract,
 }
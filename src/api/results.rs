@@ -11,10 +11,10 @@ For what that "data" field might actually resemble, refer
 to api::responses, and in turn api::schemas.
 */
 
-use crate::api::schema;
+use crate::api::{schema, Error};
 use serde::{Deserialize, Serialize};
 
-fn get_error_name(code: i32) -> &'static str {
+pub fn get_error_name(code: i32) -> &'static str {
     match code {
         // General error codes
         4000 => "cooldownConflictError",
@@ -70,6 +70,7 @@ fn get_error_name(code: i32) -> &'static str {
         4238 => "shipInvalidRefineryTypeError",
         4239 => "shipMissingRefineryError",
         4240 => "shipMissingSurveyorError",
+        4244 => "shipNotDockedError",
         // Contract error codes
         4500 => "acceptContractNotAuthorizedError",
         4501 => "acceptContractConflictError",
@@ -81,6 +82,7 @@ fn get_error_name(code: i32) -> &'static str {
         4508 => "shipDeliverTermsError",
         4509 => "shipDeliverFulfilledError",
         4510 => "shipDeliverInvalidLocationError",
+        4511 => "existingContractError",
 
         // Market error codes
         4600 => "marketTradeInsufficientCreditsError",
@@ -89,6 +91,9 @@ fn get_error_name(code: i32) -> &'static str {
         4603 => "marketNotFoundError",
         4604 => "marketTradeUnitLimitError",
 
+        // Rate limiting, which reuses the HTTP status code
+        429 => "rateLimitError",
+
         // Custom error codes
         6000 => "badReplyError",
         _ => "unknownError",
@@ -97,21 +102,39 @@ fn get_error_name(code: i32) -> &'static str {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ApiError {
-    message: String,
-    code: i32,
-    data: Option<schema::Symbolic<Vec<String>>>,
+    pub message: String,
+    pub code: i32,
+    /// varies by error; most list the offending symbols under "symbol"
+    pub data: Option<serde_json::Value>,
+}
+
+impl ApiError {
+    pub fn name(&self) -> &'static str { get_error_name(self.code) }
+}
+
+pub fn get_error_code(error: &Error) -> Option<i32> {
+    //! the server's error code, if error came from the server at all
+    error.downcast_ref::<ApiError>().map(|error| error.code)
 }
 
 impl std::fmt::Display for ApiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.data {
             Some(data) => {
+                let info = match data.get("symbol").and_then(|symbol| symbol.as_array()) {
+                    Some(symbols) => symbols
+                        .iter()
+                        .filter_map(|symbol| symbol.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    None => data.to_string(),
+                };
                 write!(
                     f,
                     "SpaceTraders API error (code={}): {}\nAdditional info: {}",
                     get_error_name(self.code),
                     self.message,
-                    data.symbol.join(" ")
+                    info
                 )
             }
             None => {
@@ -135,7 +158,9 @@ pub struct ApiResult<T> {
 }
 
 impl<T> ApiResult<T> {
-    pub fn to_result(self) -> Result<T, Box<dyn std::error::Error>> {
+    pub fn meta(&self) -> Option<&schema::Meta> { self.meta.as_ref() }
+
+    pub fn to_result(self) -> Result<T, Error> {
         if let Some(error) = self.error {
             Err(Box::new(error))
         } else if let Some(data) = self.data {
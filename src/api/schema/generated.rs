@@ -0,0 +1,2213 @@
+// generated by `cargo xtask schema` from spec/SpaceTraders.json.
+// don't edit it by hand; change the spec or xtask/src/schema.rs instead
+
+use super::{date_time, optional_date_time, ParseEnumError, SupplyLevel, Symbolic, TypedSymbolic};
+use super::{FactionSymbol, SectorSymbol, ShipSymbol, SystemSymbol, WaypointSymbol};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Agent {
+    pub account_id: String,
+    pub symbol: String,
+    pub headquarters: WaypointSymbol,
+    /// The number of credits the agent has available. Credits can be negative if funds have been overdrawn.
+    pub credits: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Chart {
+    pub waypoint_symbol: Option<WaypointSymbol>,
+    pub submitted_by: Option<String>,
+    #[serde(default, with = "optional_date_time")]
+    pub submitted_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectedSystem {
+    pub symbol: SystemSymbol,
+    pub sector_symbol: SectorSymbol,
+    #[serde(rename = "type")]
+    pub kind: SystemType,
+    pub faction_symbol: Option<FactionSymbol>,
+    pub x: i32,
+    pub y: i32,
+    pub distance: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Contract {
+    pub id: String,
+    pub faction_symbol: FactionSymbol,
+    #[serde(rename = "type")]
+    pub kind: ContractType,
+    pub terms: ContractTerms,
+    pub accepted: bool,
+    pub fulfilled: bool,
+    #[serde(with = "date_time")]
+    pub expiration: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ContractType {
+    Procurement,
+    Transport,
+    Shuttle,
+}
+
+impl ContractType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContractType::Procurement => "PROCUREMENT",
+            ContractType::Transport => "TRANSPORT",
+            ContractType::Shuttle => "SHUTTLE",
+        }
+    }
+}
+
+impl fmt::Display for ContractType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for ContractType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "PROCUREMENT" => Ok(ContractType::Procurement),
+            "TRANSPORT" => Ok(ContractType::Transport),
+            "SHUTTLE" => Ok(ContractType::Shuttle),
+            _ => Err(ParseEnumError::new("ContractType", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractDeliverGood {
+    pub trade_symbol: TradeSymbol,
+    pub destination_symbol: WaypointSymbol,
+    pub units_required: i32,
+    pub units_fulfilled: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractPayment {
+    pub on_accepted: i32,
+    pub on_fulfilled: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ContractTerms {
+    #[serde(with = "date_time")]
+    pub deadline: DateTime<Utc>,
+    pub payment: ContractPayment,
+    #[serde(default)]
+    pub deliver: Vec<ContractDeliverGood>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Cooldown {
+    pub ship_symbol: ShipSymbol,
+    pub total_seconds: u32,
+    pub remaining_seconds: u32,
+    #[serde(default, with = "optional_date_time")]
+    pub expiration: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Extraction {
+    pub ship_symbol: ShipSymbol,
+    #[serde(rename = "yield")]
+    pub yield_amount: ExtractionYield,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExtractionYield {
+    pub symbol: TradeSymbol,
+    pub units: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Faction {
+    pub symbol: FactionSymbol,
+    pub name: String,
+    pub description: String,
+    pub headquarters: WaypointSymbol,
+    pub traits: Vec<FactionTrait>,
+    pub is_recruiting: Option<bool>,
+}
+
+pub type FactionTrait = TypedSymbolic<FactionTraitSymbols>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum FactionTraitSymbols {
+    Bureaucratic,
+    Secretive,
+    Capitalistic,
+    Industrious,
+    Peaceful,
+    Distrustful,
+    Welcoming,
+    Smugglers,
+    Scavengers,
+    Rebellious,
+    Exiles,
+    Pirates,
+    Raiders,
+    Clan,
+    Guild,
+    Dominion,
+    Fringe,
+    Forsaken,
+    Isolated,
+    Localized,
+    Established,
+    Notable,
+    Dominant,
+    Inescapable,
+    Innovative,
+    Bold,
+    Visionary,
+    Curious,
+    Daring,
+    Exploratory,
+    Resourceful,
+    Flexible,
+    Cooperative,
+    United,
+    Strategic,
+    Intelligent,
+    ResearchFocused,
+    Collaborative,
+    Progressive,
+    Militaristic,
+    TechnologicallyAdvanced,
+    Aggressive,
+    Imperialistic,
+    TreasureHunters,
+    Dexterous,
+    Unpredictable,
+    Brutal,
+    Fleeting,
+    Adaptable,
+    SelfSufficient,
+    Defensive,
+    Proud,
+    Diverse,
+    Independent,
+    SelfInterested,
+    Fragmented,
+    Commercial,
+    FreeMarkets,
+    Entrepreneurial,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl FactionTraitSymbols {
+    pub fn as_str(&self) -> &str {
+        match self {
+            FactionTraitSymbols::Bureaucratic => "BUREAUCRATIC",
+            FactionTraitSymbols::Secretive => "SECRETIVE",
+            FactionTraitSymbols::Capitalistic => "CAPITALISTIC",
+            FactionTraitSymbols::Industrious => "INDUSTRIOUS",
+            FactionTraitSymbols::Peaceful => "PEACEFUL",
+            FactionTraitSymbols::Distrustful => "DISTRUSTFUL",
+            FactionTraitSymbols::Welcoming => "WELCOMING",
+            FactionTraitSymbols::Smugglers => "SMUGGLERS",
+            FactionTraitSymbols::Scavengers => "SCAVENGERS",
+            FactionTraitSymbols::Rebellious => "REBELLIOUS",
+            FactionTraitSymbols::Exiles => "EXILES",
+            FactionTraitSymbols::Pirates => "PIRATES",
+            FactionTraitSymbols::Raiders => "RAIDERS",
+            FactionTraitSymbols::Clan => "CLAN",
+            FactionTraitSymbols::Guild => "GUILD",
+            FactionTraitSymbols::Dominion => "DOMINION",
+            FactionTraitSymbols::Fringe => "FRINGE",
+            FactionTraitSymbols::Forsaken => "FORSAKEN",
+            FactionTraitSymbols::Isolated => "ISOLATED",
+            FactionTraitSymbols::Localized => "LOCALIZED",
+            FactionTraitSymbols::Established => "ESTABLISHED",
+            FactionTraitSymbols::Notable => "NOTABLE",
+            FactionTraitSymbols::Dominant => "DOMINANT",
+            FactionTraitSymbols::Inescapable => "INESCAPABLE",
+            FactionTraitSymbols::Innovative => "INNOVATIVE",
+            FactionTraitSymbols::Bold => "BOLD",
+            FactionTraitSymbols::Visionary => "VISIONARY",
+            FactionTraitSymbols::Curious => "CURIOUS",
+            FactionTraitSymbols::Daring => "DARING",
+            FactionTraitSymbols::Exploratory => "EXPLORATORY",
+            FactionTraitSymbols::Resourceful => "RESOURCEFUL",
+            FactionTraitSymbols::Flexible => "FLEXIBLE",
+            FactionTraitSymbols::Cooperative => "COOPERATIVE",
+            FactionTraitSymbols::United => "UNITED",
+            FactionTraitSymbols::Strategic => "STRATEGIC",
+            FactionTraitSymbols::Intelligent => "INTELLIGENT",
+            FactionTraitSymbols::ResearchFocused => "RESEARCH_FOCUSED",
+            FactionTraitSymbols::Collaborative => "COLLABORATIVE",
+            FactionTraitSymbols::Progressive => "PROGRESSIVE",
+            FactionTraitSymbols::Militaristic => "MILITARISTIC",
+            FactionTraitSymbols::TechnologicallyAdvanced => "TECHNOLOGICALLY_ADVANCED",
+            FactionTraitSymbols::Aggressive => "AGGRESSIVE",
+            FactionTraitSymbols::Imperialistic => "IMPERIALISTIC",
+            FactionTraitSymbols::TreasureHunters => "TREASURE_HUNTERS",
+            FactionTraitSymbols::Dexterous => "DEXTEROUS",
+            FactionTraitSymbols::Unpredictable => "UNPREDICTABLE",
+            FactionTraitSymbols::Brutal => "BRUTAL",
+            FactionTraitSymbols::Fleeting => "FLEETING",
+            FactionTraitSymbols::Adaptable => "ADAPTABLE",
+            FactionTraitSymbols::SelfSufficient => "SELF_SUFFICIENT",
+            FactionTraitSymbols::Defensive => "DEFENSIVE",
+            FactionTraitSymbols::Proud => "PROUD",
+            FactionTraitSymbols::Diverse => "DIVERSE",
+            FactionTraitSymbols::Independent => "INDEPENDENT",
+            FactionTraitSymbols::SelfInterested => "SELF_INTERESTED",
+            FactionTraitSymbols::Fragmented => "FRAGMENTED",
+            FactionTraitSymbols::Commercial => "COMMERCIAL",
+            FactionTraitSymbols::FreeMarkets => "FREE_MARKETS",
+            FactionTraitSymbols::Entrepreneurial => "ENTREPRENEURIAL",
+            FactionTraitSymbols::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for FactionTraitSymbols {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for FactionTraitSymbols {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "BUREAUCRATIC" => FactionTraitSymbols::Bureaucratic,
+            "SECRETIVE" => FactionTraitSymbols::Secretive,
+            "CAPITALISTIC" => FactionTraitSymbols::Capitalistic,
+            "INDUSTRIOUS" => FactionTraitSymbols::Industrious,
+            "PEACEFUL" => FactionTraitSymbols::Peaceful,
+            "DISTRUSTFUL" => FactionTraitSymbols::Distrustful,
+            "WELCOMING" => FactionTraitSymbols::Welcoming,
+            "SMUGGLERS" => FactionTraitSymbols::Smugglers,
+            "SCAVENGERS" => FactionTraitSymbols::Scavengers,
+            "REBELLIOUS" => FactionTraitSymbols::Rebellious,
+            "EXILES" => FactionTraitSymbols::Exiles,
+            "PIRATES" => FactionTraitSymbols::Pirates,
+            "RAIDERS" => FactionTraitSymbols::Raiders,
+            "CLAN" => FactionTraitSymbols::Clan,
+            "GUILD" => FactionTraitSymbols::Guild,
+            "DOMINION" => FactionTraitSymbols::Dominion,
+            "FRINGE" => FactionTraitSymbols::Fringe,
+            "FORSAKEN" => FactionTraitSymbols::Forsaken,
+            "ISOLATED" => FactionTraitSymbols::Isolated,
+            "LOCALIZED" => FactionTraitSymbols::Localized,
+            "ESTABLISHED" => FactionTraitSymbols::Established,
+            "NOTABLE" => FactionTraitSymbols::Notable,
+            "DOMINANT" => FactionTraitSymbols::Dominant,
+            "INESCAPABLE" => FactionTraitSymbols::Inescapable,
+            "INNOVATIVE" => FactionTraitSymbols::Innovative,
+            "BOLD" => FactionTraitSymbols::Bold,
+            "VISIONARY" => FactionTraitSymbols::Visionary,
+            "CURIOUS" => FactionTraitSymbols::Curious,
+            "DARING" => FactionTraitSymbols::Daring,
+            "EXPLORATORY" => FactionTraitSymbols::Exploratory,
+            "RESOURCEFUL" => FactionTraitSymbols::Resourceful,
+            "FLEXIBLE" => FactionTraitSymbols::Flexible,
+            "COOPERATIVE" => FactionTraitSymbols::Cooperative,
+            "UNITED" => FactionTraitSymbols::United,
+            "STRATEGIC" => FactionTraitSymbols::Strategic,
+            "INTELLIGENT" => FactionTraitSymbols::Intelligent,
+            "RESEARCH_FOCUSED" => FactionTraitSymbols::ResearchFocused,
+            "COLLABORATIVE" => FactionTraitSymbols::Collaborative,
+            "PROGRESSIVE" => FactionTraitSymbols::Progressive,
+            "MILITARISTIC" => FactionTraitSymbols::Militaristic,
+            "TECHNOLOGICALLY_ADVANCED" => FactionTraitSymbols::TechnologicallyAdvanced,
+            "AGGRESSIVE" => FactionTraitSymbols::Aggressive,
+            "IMPERIALISTIC" => FactionTraitSymbols::Imperialistic,
+            "TREASURE_HUNTERS" => FactionTraitSymbols::TreasureHunters,
+            "DEXTEROUS" => FactionTraitSymbols::Dexterous,
+            "UNPREDICTABLE" => FactionTraitSymbols::Unpredictable,
+            "BRUTAL" => FactionTraitSymbols::Brutal,
+            "FLEETING" => FactionTraitSymbols::Fleeting,
+            "ADAPTABLE" => FactionTraitSymbols::Adaptable,
+            "SELF_SUFFICIENT" => FactionTraitSymbols::SelfSufficient,
+            "DEFENSIVE" => FactionTraitSymbols::Defensive,
+            "PROUD" => FactionTraitSymbols::Proud,
+            "DIVERSE" => FactionTraitSymbols::Diverse,
+            "INDEPENDENT" => FactionTraitSymbols::Independent,
+            "SELF_INTERESTED" => FactionTraitSymbols::SelfInterested,
+            "FRAGMENTED" => FactionTraitSymbols::Fragmented,
+            "COMMERCIAL" => FactionTraitSymbols::Commercial,
+            "FREE_MARKETS" => FactionTraitSymbols::FreeMarkets,
+            "ENTREPRENEURIAL" => FactionTraitSymbols::Entrepreneurial,
+            _ => FactionTraitSymbols::Unknown(name),
+        }
+    }
+}
+
+impl From<FactionTraitSymbols> for String {
+    fn from(value: FactionTraitSymbols) -> Self {
+        match value {
+            FactionTraitSymbols::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for FactionTraitSymbols {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JumpGate {
+    pub jump_range: f64,
+    pub faction_symbol: Option<FactionSymbol>,
+    pub connected_systems: Vec<ConnectedSystem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Market {
+    pub symbol: WaypointSymbol,
+    pub exports: Vec<TradeGood>,
+    pub imports: Vec<TradeGood>,
+    pub exchange: Vec<TradeGood>,
+    #[serde(default)]
+    pub transactions: Vec<MarketTransaction>,
+    #[serde(default)]
+    pub trade_goods: Vec<MarketTradeGood>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketTradeGood {
+    pub symbol: TradeSymbol,
+    pub trade_volume: u32,
+    pub supply: SupplyLevel,
+    pub purchase_price: u32,
+    pub sell_price: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketTransaction {
+    pub waypoint_symbol: WaypointSymbol,
+    pub ship_symbol: ShipSymbol,
+    pub trade_symbol: TradeSymbol,
+    #[serde(rename = "type")]
+    pub kind: MarketTransactionTypes,
+    pub units: u32,
+    pub price_per_unit: u32,
+    pub total_price: u32,
+    #[serde(with = "date_time")]
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MarketTransactionTypes {
+    Purchase,
+    Sell,
+}
+
+impl MarketTransactionTypes {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketTransactionTypes::Purchase => "PURCHASE",
+            MarketTransactionTypes::Sell => "SELL",
+        }
+    }
+}
+
+impl fmt::Display for MarketTransactionTypes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for MarketTransactionTypes {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "PURCHASE" => Ok(MarketTransactionTypes::Purchase),
+            "SELL" => Ok(MarketTransactionTypes::Sell),
+            _ => Err(ParseEnumError::new("MarketTransactionTypes", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Meta {
+    pub total: u32,
+    pub page: u32,
+    pub limit: u8,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScannedShip {
+    pub symbol: ShipSymbol,
+    pub registration: ShipRegistration,
+    pub nav: ShipNav,
+    pub frame: Option<Symbolic>,
+    pub reactor: Option<Symbolic>,
+    pub engine: Symbolic,
+    #[serde(default)]
+    pub mounts: Vec<Symbolic>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScannedSystem {
+    pub symbol: SystemSymbol,
+    pub sector_symbol: SectorSymbol,
+    #[serde(rename = "type")]
+    pub kind: SystemType,
+    pub x: i32,
+    pub y: i32,
+    pub distance: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScannedWaypoint {
+    pub symbol: WaypointSymbol,
+    #[serde(rename = "type")]
+    pub kind: WaypointType,
+    pub system_symbol: SystemSymbol,
+    pub x: i32,
+    pub y: i32,
+    pub orbitals: Vec<WaypointOrbital>,
+    pub faction: Option<WaypointFaction>,
+    pub traits: Vec<WaypointTrait>,
+    pub chart: Option<Chart>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Ship {
+    pub symbol: ShipSymbol,
+    pub registration: ShipRegistration,
+    pub nav: ShipNav,
+    pub crew: ShipCrew,
+    pub frame: ShipFrame,
+    pub reactor: ShipReactor,
+    pub engine: ShipEngine,
+    pub modules: Vec<ShipModule>,
+    pub mounts: Vec<ShipMount>,
+    pub cargo: ShipCargo,
+    pub fuel: ShipFuel,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipCargo {
+    pub capacity: u32,
+    pub units: u32,
+    pub inventory: Vec<ShipCargoItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipCargoItem {
+    pub symbol: TradeSymbol,
+    pub name: String,
+    pub description: String,
+    pub units: u32,
+}
+
+pub type ShipCondition = u8;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipCrew {
+    pub current: i32,
+    pub required: i32,
+    pub capacity: i32,
+    pub rotation: ShipCrewRotation,
+    pub morale: u8,
+    pub wages: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ShipCrewRotation {
+    Strict,
+    Relaxed,
+}
+
+impl ShipCrewRotation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShipCrewRotation::Strict => "STRICT",
+            ShipCrewRotation::Relaxed => "RELAXED",
+        }
+    }
+}
+
+impl fmt::Display for ShipCrewRotation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for ShipCrewRotation {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "STRICT" => Ok(ShipCrewRotation::Strict),
+            "RELAXED" => Ok(ShipCrewRotation::Relaxed),
+            _ => Err(ParseEnumError::new("ShipCrewRotation", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipEngine {
+    pub symbol: ShipEngineSymbols,
+    pub name: String,
+    pub description: String,
+    pub condition: Option<ShipCondition>,
+    pub speed: u32,
+    pub requirements: ShipRequirements,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum ShipEngineSymbols {
+    EngineImpulseDriveI,
+    EngineIonDriveI,
+    EngineIonDriveIi,
+    EngineHyperDriveI,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl ShipEngineSymbols {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ShipEngineSymbols::EngineImpulseDriveI => "ENGINE_IMPULSE_DRIVE_I",
+            ShipEngineSymbols::EngineIonDriveI => "ENGINE_ION_DRIVE_I",
+            ShipEngineSymbols::EngineIonDriveIi => "ENGINE_ION_DRIVE_II",
+            ShipEngineSymbols::EngineHyperDriveI => "ENGINE_HYPER_DRIVE_I",
+            ShipEngineSymbols::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for ShipEngineSymbols {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for ShipEngineSymbols {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "ENGINE_IMPULSE_DRIVE_I" => ShipEngineSymbols::EngineImpulseDriveI,
+            "ENGINE_ION_DRIVE_I" => ShipEngineSymbols::EngineIonDriveI,
+            "ENGINE_ION_DRIVE_II" => ShipEngineSymbols::EngineIonDriveIi,
+            "ENGINE_HYPER_DRIVE_I" => ShipEngineSymbols::EngineHyperDriveI,
+            _ => ShipEngineSymbols::Unknown(name),
+        }
+    }
+}
+
+impl From<ShipEngineSymbols> for String {
+    fn from(value: ShipEngineSymbols) -> Self {
+        match value {
+            ShipEngineSymbols::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for ShipEngineSymbols {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipFrame {
+    pub symbol: ShipFrameSymbols,
+    pub name: String,
+    pub description: String,
+    pub condition: Option<ShipCondition>,
+    pub module_slots: u32,
+    pub mounting_points: u32,
+    pub fuel_capacity: u32,
+    pub requirements: ShipRequirements,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum ShipFrameSymbols {
+    FrameProbe,
+    FrameDrone,
+    FrameInterceptor,
+    FrameRacer,
+    FrameFighter,
+    FrameFrigate,
+    FrameShuttle,
+    FrameExplorer,
+    FrameMiner,
+    FrameLightFreighter,
+    FrameHeavyFreighter,
+    FrameTransport,
+    FrameDestroyer,
+    FrameCruiser,
+    FrameCarrier,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl ShipFrameSymbols {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ShipFrameSymbols::FrameProbe => "FRAME_PROBE",
+            ShipFrameSymbols::FrameDrone => "FRAME_DRONE",
+            ShipFrameSymbols::FrameInterceptor => "FRAME_INTERCEPTOR",
+            ShipFrameSymbols::FrameRacer => "FRAME_RACER",
+            ShipFrameSymbols::FrameFighter => "FRAME_FIGHTER",
+            ShipFrameSymbols::FrameFrigate => "FRAME_FRIGATE",
+            ShipFrameSymbols::FrameShuttle => "FRAME_SHUTTLE",
+            ShipFrameSymbols::FrameExplorer => "FRAME_EXPLORER",
+            ShipFrameSymbols::FrameMiner => "FRAME_MINER",
+            ShipFrameSymbols::FrameLightFreighter => "FRAME_LIGHT_FREIGHTER",
+            ShipFrameSymbols::FrameHeavyFreighter => "FRAME_HEAVY_FREIGHTER",
+            ShipFrameSymbols::FrameTransport => "FRAME_TRANSPORT",
+            ShipFrameSymbols::FrameDestroyer => "FRAME_DESTROYER",
+            ShipFrameSymbols::FrameCruiser => "FRAME_CRUISER",
+            ShipFrameSymbols::FrameCarrier => "FRAME_CARRIER",
+            ShipFrameSymbols::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for ShipFrameSymbols {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for ShipFrameSymbols {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "FRAME_PROBE" => ShipFrameSymbols::FrameProbe,
+            "FRAME_DRONE" => ShipFrameSymbols::FrameDrone,
+            "FRAME_INTERCEPTOR" => ShipFrameSymbols::FrameInterceptor,
+            "FRAME_RACER" => ShipFrameSymbols::FrameRacer,
+            "FRAME_FIGHTER" => ShipFrameSymbols::FrameFighter,
+            "FRAME_FRIGATE" => ShipFrameSymbols::FrameFrigate,
+            "FRAME_SHUTTLE" => ShipFrameSymbols::FrameShuttle,
+            "FRAME_EXPLORER" => ShipFrameSymbols::FrameExplorer,
+            "FRAME_MINER" => ShipFrameSymbols::FrameMiner,
+            "FRAME_LIGHT_FREIGHTER" => ShipFrameSymbols::FrameLightFreighter,
+            "FRAME_HEAVY_FREIGHTER" => ShipFrameSymbols::FrameHeavyFreighter,
+            "FRAME_TRANSPORT" => ShipFrameSymbols::FrameTransport,
+            "FRAME_DESTROYER" => ShipFrameSymbols::FrameDestroyer,
+            "FRAME_CRUISER" => ShipFrameSymbols::FrameCruiser,
+            "FRAME_CARRIER" => ShipFrameSymbols::FrameCarrier,
+            _ => ShipFrameSymbols::Unknown(name),
+        }
+    }
+}
+
+impl From<ShipFrameSymbols> for String {
+    fn from(value: ShipFrameSymbols) -> Self {
+        match value {
+            ShipFrameSymbols::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for ShipFrameSymbols {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipFuel {
+    pub current: u32,
+    pub capacity: u32,
+    pub consumed: Option<ShipFuelConsumption>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipFuelConsumption {
+    pub amount: u32,
+    #[serde(with = "date_time")]
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipModule {
+    pub symbol: ShipModuleSymbols,
+    pub capacity: Option<u32>,
+    pub range: Option<u32>,
+    pub name: String,
+    pub description: Option<String>,
+    pub requirements: ShipRequirements,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum ShipModuleSymbols {
+    ModuleMineralProcessorI,
+    ModuleCargoHoldI,
+    ModuleCrewQuartersI,
+    ModuleEnvoyQuartersI,
+    ModulePassengerCabinI,
+    ModuleMicroRefineryI,
+    ModuleOreRefineryI,
+    ModuleFuelRefineryI,
+    ModuleScienceLabI,
+    ModuleJumpDriveI,
+    ModuleJumpDriveIi,
+    ModuleJumpDriveIii,
+    ModuleWarpDriveI,
+    ModuleWarpDriveIi,
+    ModuleWarpDriveIii,
+    ModuleShieldGeneratorI,
+    ModuleShieldGeneratorIi,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl ShipModuleSymbols {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ShipModuleSymbols::ModuleMineralProcessorI => "MODULE_MINERAL_PROCESSOR_I",
+            ShipModuleSymbols::ModuleCargoHoldI => "MODULE_CARGO_HOLD_I",
+            ShipModuleSymbols::ModuleCrewQuartersI => "MODULE_CREW_QUARTERS_I",
+            ShipModuleSymbols::ModuleEnvoyQuartersI => "MODULE_ENVOY_QUARTERS_I",
+            ShipModuleSymbols::ModulePassengerCabinI => "MODULE_PASSENGER_CABIN_I",
+            ShipModuleSymbols::ModuleMicroRefineryI => "MODULE_MICRO_REFINERY_I",
+            ShipModuleSymbols::ModuleOreRefineryI => "MODULE_ORE_REFINERY_I",
+            ShipModuleSymbols::ModuleFuelRefineryI => "MODULE_FUEL_REFINERY_I",
+            ShipModuleSymbols::ModuleScienceLabI => "MODULE_SCIENCE_LAB_I",
+            ShipModuleSymbols::ModuleJumpDriveI => "MODULE_JUMP_DRIVE_I",
+            ShipModuleSymbols::ModuleJumpDriveIi => "MODULE_JUMP_DRIVE_II",
+            ShipModuleSymbols::ModuleJumpDriveIii => "MODULE_JUMP_DRIVE_III",
+            ShipModuleSymbols::ModuleWarpDriveI => "MODULE_WARP_DRIVE_I",
+            ShipModuleSymbols::ModuleWarpDriveIi => "MODULE_WARP_DRIVE_II",
+            ShipModuleSymbols::ModuleWarpDriveIii => "MODULE_WARP_DRIVE_III",
+            ShipModuleSymbols::ModuleShieldGeneratorI => "MODULE_SHIELD_GENERATOR_I",
+            ShipModuleSymbols::ModuleShieldGeneratorIi => "MODULE_SHIELD_GENERATOR_II",
+            ShipModuleSymbols::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for ShipModuleSymbols {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for ShipModuleSymbols {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "MODULE_MINERAL_PROCESSOR_I" => ShipModuleSymbols::ModuleMineralProcessorI,
+            "MODULE_CARGO_HOLD_I" => ShipModuleSymbols::ModuleCargoHoldI,
+            "MODULE_CREW_QUARTERS_I" => ShipModuleSymbols::ModuleCrewQuartersI,
+            "MODULE_ENVOY_QUARTERS_I" => ShipModuleSymbols::ModuleEnvoyQuartersI,
+            "MODULE_PASSENGER_CABIN_I" => ShipModuleSymbols::ModulePassengerCabinI,
+            "MODULE_MICRO_REFINERY_I" => ShipModuleSymbols::ModuleMicroRefineryI,
+            "MODULE_ORE_REFINERY_I" => ShipModuleSymbols::ModuleOreRefineryI,
+            "MODULE_FUEL_REFINERY_I" => ShipModuleSymbols::ModuleFuelRefineryI,
+            "MODULE_SCIENCE_LAB_I" => ShipModuleSymbols::ModuleScienceLabI,
+            "MODULE_JUMP_DRIVE_I" => ShipModuleSymbols::ModuleJumpDriveI,
+            "MODULE_JUMP_DRIVE_II" => ShipModuleSymbols::ModuleJumpDriveIi,
+            "MODULE_JUMP_DRIVE_III" => ShipModuleSymbols::ModuleJumpDriveIii,
+            "MODULE_WARP_DRIVE_I" => ShipModuleSymbols::ModuleWarpDriveI,
+            "MODULE_WARP_DRIVE_II" => ShipModuleSymbols::ModuleWarpDriveIi,
+            "MODULE_WARP_DRIVE_III" => ShipModuleSymbols::ModuleWarpDriveIii,
+            "MODULE_SHIELD_GENERATOR_I" => ShipModuleSymbols::ModuleShieldGeneratorI,
+            "MODULE_SHIELD_GENERATOR_II" => ShipModuleSymbols::ModuleShieldGeneratorIi,
+            _ => ShipModuleSymbols::Unknown(name),
+        }
+    }
+}
+
+impl From<ShipModuleSymbols> for String {
+    fn from(value: ShipModuleSymbols) -> Self {
+        match value {
+            ShipModuleSymbols::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for ShipModuleSymbols {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipMount {
+    pub symbol: ShipMountSymbols,
+    pub name: String,
+    pub description: Option<String>,
+    pub strength: Option<u32>,
+    #[serde(default)]
+    pub deposits: Vec<Deposits>,
+    pub requirements: ShipRequirements,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum ShipMountSymbols {
+    MountGasSiphonI,
+    MountGasSiphonIi,
+    MountGasSiphonIii,
+    MountSurveyorI,
+    MountSurveyorIi,
+    MountSurveyorIii,
+    MountSensorArrayI,
+    MountSensorArrayIi,
+    MountSensorArrayIii,
+    MountMiningLaserI,
+    MountMiningLaserIi,
+    MountMiningLaserIii,
+    MountLaserCannonI,
+    MountMissileLauncherI,
+    MountTurretI,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl ShipMountSymbols {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ShipMountSymbols::MountGasSiphonI => "MOUNT_GAS_SIPHON_I",
+            ShipMountSymbols::MountGasSiphonIi => "MOUNT_GAS_SIPHON_II",
+            ShipMountSymbols::MountGasSiphonIii => "MOUNT_GAS_SIPHON_III",
+            ShipMountSymbols::MountSurveyorI => "MOUNT_SURVEYOR_I",
+            ShipMountSymbols::MountSurveyorIi => "MOUNT_SURVEYOR_II",
+            ShipMountSymbols::MountSurveyorIii => "MOUNT_SURVEYOR_III",
+            ShipMountSymbols::MountSensorArrayI => "MOUNT_SENSOR_ARRAY_I",
+            ShipMountSymbols::MountSensorArrayIi => "MOUNT_SENSOR_ARRAY_II",
+            ShipMountSymbols::MountSensorArrayIii => "MOUNT_SENSOR_ARRAY_III",
+            ShipMountSymbols::MountMiningLaserI => "MOUNT_MINING_LASER_I",
+            ShipMountSymbols::MountMiningLaserIi => "MOUNT_MINING_LASER_II",
+            ShipMountSymbols::MountMiningLaserIii => "MOUNT_MINING_LASER_III",
+            ShipMountSymbols::MountLaserCannonI => "MOUNT_LASER_CANNON_I",
+            ShipMountSymbols::MountMissileLauncherI => "MOUNT_MISSILE_LAUNCHER_I",
+            ShipMountSymbols::MountTurretI => "MOUNT_TURRET_I",
+            ShipMountSymbols::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for ShipMountSymbols {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for ShipMountSymbols {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "MOUNT_GAS_SIPHON_I" => ShipMountSymbols::MountGasSiphonI,
+            "MOUNT_GAS_SIPHON_II" => ShipMountSymbols::MountGasSiphonIi,
+            "MOUNT_GAS_SIPHON_III" => ShipMountSymbols::MountGasSiphonIii,
+            "MOUNT_SURVEYOR_I" => ShipMountSymbols::MountSurveyorI,
+            "MOUNT_SURVEYOR_II" => ShipMountSymbols::MountSurveyorIi,
+            "MOUNT_SURVEYOR_III" => ShipMountSymbols::MountSurveyorIii,
+            "MOUNT_SENSOR_ARRAY_I" => ShipMountSymbols::MountSensorArrayI,
+            "MOUNT_SENSOR_ARRAY_II" => ShipMountSymbols::MountSensorArrayIi,
+            "MOUNT_SENSOR_ARRAY_III" => ShipMountSymbols::MountSensorArrayIii,
+            "MOUNT_MINING_LASER_I" => ShipMountSymbols::MountMiningLaserI,
+            "MOUNT_MINING_LASER_II" => ShipMountSymbols::MountMiningLaserIi,
+            "MOUNT_MINING_LASER_III" => ShipMountSymbols::MountMiningLaserIii,
+            "MOUNT_LASER_CANNON_I" => ShipMountSymbols::MountLaserCannonI,
+            "MOUNT_MISSILE_LAUNCHER_I" => ShipMountSymbols::MountMissileLauncherI,
+            "MOUNT_TURRET_I" => ShipMountSymbols::MountTurretI,
+            _ => ShipMountSymbols::Unknown(name),
+        }
+    }
+}
+
+impl From<ShipMountSymbols> for String {
+    fn from(value: ShipMountSymbols) -> Self {
+        match value {
+            ShipMountSymbols::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for ShipMountSymbols {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum Deposits {
+    QuartzSand,
+    SiliconCrystals,
+    PreciousStones,
+    IceWater,
+    AmmoniaIce,
+    IronOre,
+    CopperOre,
+    SilverOre,
+    AluminumOre,
+    GoldOre,
+    PlatinumOre,
+    Diamonds,
+    UraniteOre,
+    MeritiumOre,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl Deposits {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Deposits::QuartzSand => "QUARTZ_SAND",
+            Deposits::SiliconCrystals => "SILICON_CRYSTALS",
+            Deposits::PreciousStones => "PRECIOUS_STONES",
+            Deposits::IceWater => "ICE_WATER",
+            Deposits::AmmoniaIce => "AMMONIA_ICE",
+            Deposits::IronOre => "IRON_ORE",
+            Deposits::CopperOre => "COPPER_ORE",
+            Deposits::SilverOre => "SILVER_ORE",
+            Deposits::AluminumOre => "ALUMINUM_ORE",
+            Deposits::GoldOre => "GOLD_ORE",
+            Deposits::PlatinumOre => "PLATINUM_ORE",
+            Deposits::Diamonds => "DIAMONDS",
+            Deposits::UraniteOre => "URANITE_ORE",
+            Deposits::MeritiumOre => "MERITIUM_ORE",
+            Deposits::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for Deposits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for Deposits {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "QUARTZ_SAND" => Deposits::QuartzSand,
+            "SILICON_CRYSTALS" => Deposits::SiliconCrystals,
+            "PRECIOUS_STONES" => Deposits::PreciousStones,
+            "ICE_WATER" => Deposits::IceWater,
+            "AMMONIA_ICE" => Deposits::AmmoniaIce,
+            "IRON_ORE" => Deposits::IronOre,
+            "COPPER_ORE" => Deposits::CopperOre,
+            "SILVER_ORE" => Deposits::SilverOre,
+            "ALUMINUM_ORE" => Deposits::AluminumOre,
+            "GOLD_ORE" => Deposits::GoldOre,
+            "PLATINUM_ORE" => Deposits::PlatinumOre,
+            "DIAMONDS" => Deposits::Diamonds,
+            "URANITE_ORE" => Deposits::UraniteOre,
+            "MERITIUM_ORE" => Deposits::MeritiumOre,
+            _ => Deposits::Unknown(name),
+        }
+    }
+}
+
+impl From<Deposits> for String {
+    fn from(value: Deposits) -> Self {
+        match value {
+            Deposits::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for Deposits {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipNav {
+    pub system_symbol: SystemSymbol,
+    pub waypoint_symbol: WaypointSymbol,
+    pub route: ShipNavRoute,
+    pub status: ShipNavStatus,
+    pub flight_mode: ShipNavFlightMode,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ShipNavFlightMode {
+    Drift,
+    Stealth,
+    Cruise,
+    Burn,
+}
+
+impl ShipNavFlightMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShipNavFlightMode::Drift => "DRIFT",
+            ShipNavFlightMode::Stealth => "STEALTH",
+            ShipNavFlightMode::Cruise => "CRUISE",
+            ShipNavFlightMode::Burn => "BURN",
+        }
+    }
+}
+
+impl fmt::Display for ShipNavFlightMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for ShipNavFlightMode {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "DRIFT" => Ok(ShipNavFlightMode::Drift),
+            "STEALTH" => Ok(ShipNavFlightMode::Stealth),
+            "CRUISE" => Ok(ShipNavFlightMode::Cruise),
+            "BURN" => Ok(ShipNavFlightMode::Burn),
+            _ => Err(ParseEnumError::new("ShipNavFlightMode", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipNavRoute {
+    pub destination: ShipNavRouteWaypoint,
+    pub departure: ShipNavRouteWaypoint,
+    #[serde(with = "date_time")]
+    pub departure_time: DateTime<Utc>,
+    #[serde(with = "date_time")]
+    pub arrival: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipNavRouteWaypoint {
+    pub symbol: WaypointSymbol,
+    #[serde(rename = "type")]
+    pub kind: WaypointType,
+    pub system_symbol: SystemSymbol,
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ShipNavStatus {
+    InTransit,
+    InOrbit,
+    Docked,
+}
+
+impl ShipNavStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShipNavStatus::InTransit => "IN_TRANSIT",
+            ShipNavStatus::InOrbit => "IN_ORBIT",
+            ShipNavStatus::Docked => "DOCKED",
+        }
+    }
+}
+
+impl fmt::Display for ShipNavStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for ShipNavStatus {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "IN_TRANSIT" => Ok(ShipNavStatus::InTransit),
+            "IN_ORBIT" => Ok(ShipNavStatus::InOrbit),
+            "DOCKED" => Ok(ShipNavStatus::Docked),
+            _ => Err(ParseEnumError::new("ShipNavStatus", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipReactor {
+    pub symbol: ShipReactorSymbols,
+    pub name: String,
+    pub description: String,
+    pub condition: Option<ShipCondition>,
+    pub power_output: u32,
+    pub requirements: ShipRequirements,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum ShipReactorSymbols {
+    ReactorSolarI,
+    ReactorFusionI,
+    ReactorFissionI,
+    ReactorChemicalI,
+    ReactorAntimatterI,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl ShipReactorSymbols {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ShipReactorSymbols::ReactorSolarI => "REACTOR_SOLAR_I",
+            ShipReactorSymbols::ReactorFusionI => "REACTOR_FUSION_I",
+            ShipReactorSymbols::ReactorFissionI => "REACTOR_FISSION_I",
+            ShipReactorSymbols::ReactorChemicalI => "REACTOR_CHEMICAL_I",
+            ShipReactorSymbols::ReactorAntimatterI => "REACTOR_ANTIMATTER_I",
+            ShipReactorSymbols::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for ShipReactorSymbols {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for ShipReactorSymbols {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "REACTOR_SOLAR_I" => ShipReactorSymbols::ReactorSolarI,
+            "REACTOR_FUSION_I" => ShipReactorSymbols::ReactorFusionI,
+            "REACTOR_FISSION_I" => ShipReactorSymbols::ReactorFissionI,
+            "REACTOR_CHEMICAL_I" => ShipReactorSymbols::ReactorChemicalI,
+            "REACTOR_ANTIMATTER_I" => ShipReactorSymbols::ReactorAntimatterI,
+            _ => ShipReactorSymbols::Unknown(name),
+        }
+    }
+}
+
+impl From<ShipReactorSymbols> for String {
+    fn from(value: ShipReactorSymbols) -> Self {
+        match value {
+            ShipReactorSymbols::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for ShipReactorSymbols {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipRegistration {
+    pub name: String,
+    pub faction_symbol: FactionSymbol,
+    pub role: ShipRole,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipRequirements {
+    pub power: Option<i32>,
+    pub crew: Option<i32>,
+    pub slots: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ShipRole {
+    Fabricator,
+    Harvester,
+    Hauler,
+    Interceptor,
+    Excavator,
+    Transport,
+    Repair,
+    Surveyor,
+    Command,
+    Carrier,
+    Patrol,
+    Satellite,
+    Explorer,
+    Refinery,
+}
+
+impl ShipRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShipRole::Fabricator => "FABRICATOR",
+            ShipRole::Harvester => "HARVESTER",
+            ShipRole::Hauler => "HAULER",
+            ShipRole::Interceptor => "INTERCEPTOR",
+            ShipRole::Excavator => "EXCAVATOR",
+            ShipRole::Transport => "TRANSPORT",
+            ShipRole::Repair => "REPAIR",
+            ShipRole::Surveyor => "SURVEYOR",
+            ShipRole::Command => "COMMAND",
+            ShipRole::Carrier => "CARRIER",
+            ShipRole::Patrol => "PATROL",
+            ShipRole::Satellite => "SATELLITE",
+            ShipRole::Explorer => "EXPLORER",
+            ShipRole::Refinery => "REFINERY",
+        }
+    }
+}
+
+impl fmt::Display for ShipRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for ShipRole {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "FABRICATOR" => Ok(ShipRole::Fabricator),
+            "HARVESTER" => Ok(ShipRole::Harvester),
+            "HAULER" => Ok(ShipRole::Hauler),
+            "INTERCEPTOR" => Ok(ShipRole::Interceptor),
+            "EXCAVATOR" => Ok(ShipRole::Excavator),
+            "TRANSPORT" => Ok(ShipRole::Transport),
+            "REPAIR" => Ok(ShipRole::Repair),
+            "SURVEYOR" => Ok(ShipRole::Surveyor),
+            "COMMAND" => Ok(ShipRole::Command),
+            "CARRIER" => Ok(ShipRole::Carrier),
+            "PATROL" => Ok(ShipRole::Patrol),
+            "SATELLITE" => Ok(ShipRole::Satellite),
+            "EXPLORER" => Ok(ShipRole::Explorer),
+            "REFINERY" => Ok(ShipRole::Refinery),
+            _ => Err(ParseEnumError::new("ShipRole", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum ShipType {
+    ShipProbe,
+    ShipMiningDrone,
+    ShipInterceptor,
+    ShipLightHauler,
+    ShipCommandFrigate,
+    ShipExplorer,
+    ShipHeavyFreighter,
+    ShipLightShuttle,
+    ShipOreHound,
+    ShipRefiningFreighter,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl ShipType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ShipType::ShipProbe => "SHIP_PROBE",
+            ShipType::ShipMiningDrone => "SHIP_MINING_DRONE",
+            ShipType::ShipInterceptor => "SHIP_INTERCEPTOR",
+            ShipType::ShipLightHauler => "SHIP_LIGHT_HAULER",
+            ShipType::ShipCommandFrigate => "SHIP_COMMAND_FRIGATE",
+            ShipType::ShipExplorer => "SHIP_EXPLORER",
+            ShipType::ShipHeavyFreighter => "SHIP_HEAVY_FREIGHTER",
+            ShipType::ShipLightShuttle => "SHIP_LIGHT_SHUTTLE",
+            ShipType::ShipOreHound => "SHIP_ORE_HOUND",
+            ShipType::ShipRefiningFreighter => "SHIP_REFINING_FREIGHTER",
+            ShipType::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for ShipType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for ShipType {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "SHIP_PROBE" => ShipType::ShipProbe,
+            "SHIP_MINING_DRONE" => ShipType::ShipMiningDrone,
+            "SHIP_INTERCEPTOR" => ShipType::ShipInterceptor,
+            "SHIP_LIGHT_HAULER" => ShipType::ShipLightHauler,
+            "SHIP_COMMAND_FRIGATE" => ShipType::ShipCommandFrigate,
+            "SHIP_EXPLORER" => ShipType::ShipExplorer,
+            "SHIP_HEAVY_FREIGHTER" => ShipType::ShipHeavyFreighter,
+            "SHIP_LIGHT_SHUTTLE" => ShipType::ShipLightShuttle,
+            "SHIP_ORE_HOUND" => ShipType::ShipOreHound,
+            "SHIP_REFINING_FREIGHTER" => ShipType::ShipRefiningFreighter,
+            _ => ShipType::Unknown(name),
+        }
+    }
+}
+
+impl From<ShipType> for String {
+    fn from(value: ShipType) -> Self {
+        match value {
+            ShipType::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for ShipType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Shipyard {
+    pub symbol: WaypointSymbol,
+    pub ship_types: Vec<ShipTypeObject>,
+    #[serde(default)]
+    pub transactions: Vec<ShipyardTransaction>,
+    #[serde(default)]
+    pub ships: Vec<ShipyardShip>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShipTypeObject {
+    #[serde(rename = "type")]
+    pub kind: ShipType,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipyardShip {
+    #[serde(rename = "type")]
+    pub kind: Option<ShipType>,
+    pub name: String,
+    pub description: String,
+    pub purchase_price: i32,
+    pub frame: ShipFrame,
+    pub reactor: ShipReactor,
+    pub engine: ShipEngine,
+    pub modules: Vec<ShipModule>,
+    pub mounts: Vec<ShipMount>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipyardTransaction {
+    pub waypoint_symbol: WaypointSymbol,
+    pub ship_symbol: String,
+    pub price: u32,
+    pub agent_symbol: String,
+    #[serde(with = "date_time")]
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Survey {
+    pub signature: String,
+    pub symbol: WaypointSymbol,
+    pub deposits: Vec<SurveyDeposit>,
+    #[serde(with = "date_time")]
+    pub expiration: DateTime<Utc>,
+    pub size: SurveySize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SurveySize {
+    Small,
+    Moderate,
+    Large,
+}
+
+impl SurveySize {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SurveySize::Small => "SMALL",
+            SurveySize::Moderate => "MODERATE",
+            SurveySize::Large => "LARGE",
+        }
+    }
+}
+
+impl fmt::Display for SurveySize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for SurveySize {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "SMALL" => Ok(SurveySize::Small),
+            "MODERATE" => Ok(SurveySize::Moderate),
+            "LARGE" => Ok(SurveySize::Large),
+            _ => Err(ParseEnumError::new("SurveySize", s)),
+        }
+    }
+}
+
+pub type SurveyDeposit = Symbolic;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct System {
+    pub symbol: SystemSymbol,
+    pub sector_symbol: SectorSymbol,
+    #[serde(rename = "type")]
+    pub kind: SystemType,
+    pub x: i32,
+    pub y: i32,
+    pub waypoints: Vec<SystemWaypoint>,
+    pub factions: Vec<SystemFaction>,
+}
+
+pub type SystemFaction = Symbolic<FactionSymbol>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SystemType {
+    NeutronStar,
+    RedStar,
+    OrangeStar,
+    BlueStar,
+    YoungStar,
+    WhiteDwarf,
+    BlackHole,
+    Hypergiant,
+    Nebula,
+    Unstable,
+}
+
+impl SystemType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SystemType::NeutronStar => "NEUTRON_STAR",
+            SystemType::RedStar => "RED_STAR",
+            SystemType::OrangeStar => "ORANGE_STAR",
+            SystemType::BlueStar => "BLUE_STAR",
+            SystemType::YoungStar => "YOUNG_STAR",
+            SystemType::WhiteDwarf => "WHITE_DWARF",
+            SystemType::BlackHole => "BLACK_HOLE",
+            SystemType::Hypergiant => "HYPERGIANT",
+            SystemType::Nebula => "NEBULA",
+            SystemType::Unstable => "UNSTABLE",
+        }
+    }
+}
+
+impl fmt::Display for SystemType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for SystemType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NEUTRON_STAR" => Ok(SystemType::NeutronStar),
+            "RED_STAR" => Ok(SystemType::RedStar),
+            "ORANGE_STAR" => Ok(SystemType::OrangeStar),
+            "BLUE_STAR" => Ok(SystemType::BlueStar),
+            "YOUNG_STAR" => Ok(SystemType::YoungStar),
+            "WHITE_DWARF" => Ok(SystemType::WhiteDwarf),
+            "BLACK_HOLE" => Ok(SystemType::BlackHole),
+            "HYPERGIANT" => Ok(SystemType::Hypergiant),
+            "NEBULA" => Ok(SystemType::Nebula),
+            "UNSTABLE" => Ok(SystemType::Unstable),
+            _ => Err(ParseEnumError::new("SystemType", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SystemWaypoint {
+    pub symbol: WaypointSymbol,
+    #[serde(rename = "type")]
+    pub kind: WaypointType,
+    pub x: i32,
+    pub y: i32,
+}
+
+pub type TradeGood = TypedSymbolic<TradeSymbol>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum TradeSymbol {
+    PreciousStones,
+    QuartzSand,
+    SiliconCrystals,
+    AmmoniaIce,
+    LiquidHydrogen,
+    LiquidNitrogen,
+    IceWater,
+    ExoticMatter,
+    AdvancedCircuitry,
+    GravitonEmitters,
+    Iron,
+    IronOre,
+    Copper,
+    CopperOre,
+    Aluminum,
+    AluminumOre,
+    Silver,
+    SilverOre,
+    Gold,
+    GoldOre,
+    Platinum,
+    PlatinumOre,
+    Diamonds,
+    Uranite,
+    UraniteOre,
+    Meritium,
+    MeritiumOre,
+    Hydrocarbon,
+    Antimatter,
+    Fertilizers,
+    Fabrics,
+    Food,
+    Jewelry,
+    Machinery,
+    Firearms,
+    AssaultRifles,
+    MilitaryEquipment,
+    Explosives,
+    LabInstruments,
+    Ammunition,
+    Electronics,
+    ShipPlating,
+    Equipment,
+    Fuel,
+    Medicine,
+    Drugs,
+    Clothing,
+    Microprocessors,
+    Plastics,
+    Polynucleotides,
+    Biocomposites,
+    Nanobots,
+    AiMainframes,
+    QuantumDrives,
+    RoboticDrones,
+    CyberImplants,
+    GeneTherapeutics,
+    NeuralChips,
+    MoodRegulators,
+    ViralAgents,
+    MicroFusionGenerators,
+    Supergrains,
+    LaserRifles,
+    Holographics,
+    ShipSalvage,
+    RelicTech,
+    NovelLifeforms,
+    BotanicalSpecimens,
+    CulturalArtifacts,
+    ReactorSolarI,
+    ReactorFusionI,
+    ReactorFissionI,
+    ReactorChemicalI,
+    ReactorAntimatterI,
+    EngineImpulseDriveI,
+    EngineIonDriveI,
+    EngineIonDriveIi,
+    EngineHyperDriveI,
+    ModuleMineralProcessorI,
+    ModuleCargoHoldI,
+    ModuleCrewQuartersI,
+    ModuleEnvoyQuartersI,
+    ModulePassengerCabinI,
+    ModuleMicroRefineryI,
+    ModuleOreRefineryI,
+    ModuleFuelRefineryI,
+    ModuleScienceLabI,
+    ModuleJumpDriveI,
+    ModuleJumpDriveIi,
+    ModuleJumpDriveIii,
+    ModuleWarpDriveI,
+    ModuleWarpDriveIi,
+    ModuleWarpDriveIii,
+    ModuleShieldGeneratorI,
+    ModuleShieldGeneratorIi,
+    MountGasSiphonI,
+    MountGasSiphonIi,
+    MountGasSiphonIii,
+    MountSurveyorI,
+    MountSurveyorIi,
+    MountSurveyorIii,
+    MountSensorArrayI,
+    MountSensorArrayIi,
+    MountSensorArrayIii,
+    MountMiningLaserI,
+    MountMiningLaserIi,
+    MountMiningLaserIii,
+    MountLaserCannonI,
+    MountMissileLauncherI,
+    MountTurretI,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl TradeSymbol {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TradeSymbol::PreciousStones => "PRECIOUS_STONES",
+            TradeSymbol::QuartzSand => "QUARTZ_SAND",
+            TradeSymbol::SiliconCrystals => "SILICON_CRYSTALS",
+            TradeSymbol::AmmoniaIce => "AMMONIA_ICE",
+            TradeSymbol::LiquidHydrogen => "LIQUID_HYDROGEN",
+            TradeSymbol::LiquidNitrogen => "LIQUID_NITROGEN",
+            TradeSymbol::IceWater => "ICE_WATER",
+            TradeSymbol::ExoticMatter => "EXOTIC_MATTER",
+            TradeSymbol::AdvancedCircuitry => "ADVANCED_CIRCUITRY",
+            TradeSymbol::GravitonEmitters => "GRAVITON_EMITTERS",
+            TradeSymbol::Iron => "IRON",
+            TradeSymbol::IronOre => "IRON_ORE",
+            TradeSymbol::Copper => "COPPER",
+            TradeSymbol::CopperOre => "COPPER_ORE",
+            TradeSymbol::Aluminum => "ALUMINUM",
+            TradeSymbol::AluminumOre => "ALUMINUM_ORE",
+            TradeSymbol::Silver => "SILVER",
+            TradeSymbol::SilverOre => "SILVER_ORE",
+            TradeSymbol::Gold => "GOLD",
+            TradeSymbol::GoldOre => "GOLD_ORE",
+            TradeSymbol::Platinum => "PLATINUM",
+            TradeSymbol::PlatinumOre => "PLATINUM_ORE",
+            TradeSymbol::Diamonds => "DIAMONDS",
+            TradeSymbol::Uranite => "URANITE",
+            TradeSymbol::UraniteOre => "URANITE_ORE",
+            TradeSymbol::Meritium => "MERITIUM",
+            TradeSymbol::MeritiumOre => "MERITIUM_ORE",
+            TradeSymbol::Hydrocarbon => "HYDROCARBON",
+            TradeSymbol::Antimatter => "ANTIMATTER",
+            TradeSymbol::Fertilizers => "FERTILIZERS",
+            TradeSymbol::Fabrics => "FABRICS",
+            TradeSymbol::Food => "FOOD",
+            TradeSymbol::Jewelry => "JEWELRY",
+            TradeSymbol::Machinery => "MACHINERY",
+            TradeSymbol::Firearms => "FIREARMS",
+            TradeSymbol::AssaultRifles => "ASSAULT_RIFLES",
+            TradeSymbol::MilitaryEquipment => "MILITARY_EQUIPMENT",
+            TradeSymbol::Explosives => "EXPLOSIVES",
+            TradeSymbol::LabInstruments => "LAB_INSTRUMENTS",
+            TradeSymbol::Ammunition => "AMMUNITION",
+            TradeSymbol::Electronics => "ELECTRONICS",
+            TradeSymbol::ShipPlating => "SHIP_PLATING",
+            TradeSymbol::Equipment => "EQUIPMENT",
+            TradeSymbol::Fuel => "FUEL",
+            TradeSymbol::Medicine => "MEDICINE",
+            TradeSymbol::Drugs => "DRUGS",
+            TradeSymbol::Clothing => "CLOTHING",
+            TradeSymbol::Microprocessors => "MICROPROCESSORS",
+            TradeSymbol::Plastics => "PLASTICS",
+            TradeSymbol::Polynucleotides => "POLYNUCLEOTIDES",
+            TradeSymbol::Biocomposites => "BIOCOMPOSITES",
+            TradeSymbol::Nanobots => "NANOBOTS",
+            TradeSymbol::AiMainframes => "AI_MAINFRAMES",
+            TradeSymbol::QuantumDrives => "QUANTUM_DRIVES",
+            TradeSymbol::RoboticDrones => "ROBOTIC_DRONES",
+            TradeSymbol::CyberImplants => "CYBER_IMPLANTS",
+            TradeSymbol::GeneTherapeutics => "GENE_THERAPEUTICS",
+            TradeSymbol::NeuralChips => "NEURAL_CHIPS",
+            TradeSymbol::MoodRegulators => "MOOD_REGULATORS",
+            TradeSymbol::ViralAgents => "VIRAL_AGENTS",
+            TradeSymbol::MicroFusionGenerators => "MICRO_FUSION_GENERATORS",
+            TradeSymbol::Supergrains => "SUPERGRAINS",
+            TradeSymbol::LaserRifles => "LASER_RIFLES",
+            TradeSymbol::Holographics => "HOLOGRAPHICS",
+            TradeSymbol::ShipSalvage => "SHIP_SALVAGE",
+            TradeSymbol::RelicTech => "RELIC_TECH",
+            TradeSymbol::NovelLifeforms => "NOVEL_LIFEFORMS",
+            TradeSymbol::BotanicalSpecimens => "BOTANICAL_SPECIMENS",
+            TradeSymbol::CulturalArtifacts => "CULTURAL_ARTIFACTS",
+            TradeSymbol::ReactorSolarI => "REACTOR_SOLAR_I",
+            TradeSymbol::ReactorFusionI => "REACTOR_FUSION_I",
+            TradeSymbol::ReactorFissionI => "REACTOR_FISSION_I",
+            TradeSymbol::ReactorChemicalI => "REACTOR_CHEMICAL_I",
+            TradeSymbol::ReactorAntimatterI => "REACTOR_ANTIMATTER_I",
+            TradeSymbol::EngineImpulseDriveI => "ENGINE_IMPULSE_DRIVE_I",
+            TradeSymbol::EngineIonDriveI => "ENGINE_ION_DRIVE_I",
+            TradeSymbol::EngineIonDriveIi => "ENGINE_ION_DRIVE_II",
+            TradeSymbol::EngineHyperDriveI => "ENGINE_HYPER_DRIVE_I",
+            TradeSymbol::ModuleMineralProcessorI => "MODULE_MINERAL_PROCESSOR_I",
+            TradeSymbol::ModuleCargoHoldI => "MODULE_CARGO_HOLD_I",
+            TradeSymbol::ModuleCrewQuartersI => "MODULE_CREW_QUARTERS_I",
+            TradeSymbol::ModuleEnvoyQuartersI => "MODULE_ENVOY_QUARTERS_I",
+            TradeSymbol::ModulePassengerCabinI => "MODULE_PASSENGER_CABIN_I",
+            TradeSymbol::ModuleMicroRefineryI => "MODULE_MICRO_REFINERY_I",
+            TradeSymbol::ModuleOreRefineryI => "MODULE_ORE_REFINERY_I",
+            TradeSymbol::ModuleFuelRefineryI => "MODULE_FUEL_REFINERY_I",
+            TradeSymbol::ModuleScienceLabI => "MODULE_SCIENCE_LAB_I",
+            TradeSymbol::ModuleJumpDriveI => "MODULE_JUMP_DRIVE_I",
+            TradeSymbol::ModuleJumpDriveIi => "MODULE_JUMP_DRIVE_II",
+            TradeSymbol::ModuleJumpDriveIii => "MODULE_JUMP_DRIVE_III",
+            TradeSymbol::ModuleWarpDriveI => "MODULE_WARP_DRIVE_I",
+            TradeSymbol::ModuleWarpDriveIi => "MODULE_WARP_DRIVE_II",
+            TradeSymbol::ModuleWarpDriveIii => "MODULE_WARP_DRIVE_III",
+            TradeSymbol::ModuleShieldGeneratorI => "MODULE_SHIELD_GENERATOR_I",
+            TradeSymbol::ModuleShieldGeneratorIi => "MODULE_SHIELD_GENERATOR_II",
+            TradeSymbol::MountGasSiphonI => "MOUNT_GAS_SIPHON_I",
+            TradeSymbol::MountGasSiphonIi => "MOUNT_GAS_SIPHON_II",
+            TradeSymbol::MountGasSiphonIii => "MOUNT_GAS_SIPHON_III",
+            TradeSymbol::MountSurveyorI => "MOUNT_SURVEYOR_I",
+            TradeSymbol::MountSurveyorIi => "MOUNT_SURVEYOR_II",
+            TradeSymbol::MountSurveyorIii => "MOUNT_SURVEYOR_III",
+            TradeSymbol::MountSensorArrayI => "MOUNT_SENSOR_ARRAY_I",
+            TradeSymbol::MountSensorArrayIi => "MOUNT_SENSOR_ARRAY_II",
+            TradeSymbol::MountSensorArrayIii => "MOUNT_SENSOR_ARRAY_III",
+            TradeSymbol::MountMiningLaserI => "MOUNT_MINING_LASER_I",
+            TradeSymbol::MountMiningLaserIi => "MOUNT_MINING_LASER_II",
+            TradeSymbol::MountMiningLaserIii => "MOUNT_MINING_LASER_III",
+            TradeSymbol::MountLaserCannonI => "MOUNT_LASER_CANNON_I",
+            TradeSymbol::MountMissileLauncherI => "MOUNT_MISSILE_LAUNCHER_I",
+            TradeSymbol::MountTurretI => "MOUNT_TURRET_I",
+            TradeSymbol::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for TradeSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for TradeSymbol {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "PRECIOUS_STONES" => TradeSymbol::PreciousStones,
+            "QUARTZ_SAND" => TradeSymbol::QuartzSand,
+            "SILICON_CRYSTALS" => TradeSymbol::SiliconCrystals,
+            "AMMONIA_ICE" => TradeSymbol::AmmoniaIce,
+            "LIQUID_HYDROGEN" => TradeSymbol::LiquidHydrogen,
+            "LIQUID_NITROGEN" => TradeSymbol::LiquidNitrogen,
+            "ICE_WATER" => TradeSymbol::IceWater,
+            "EXOTIC_MATTER" => TradeSymbol::ExoticMatter,
+            "ADVANCED_CIRCUITRY" => TradeSymbol::AdvancedCircuitry,
+            "GRAVITON_EMITTERS" => TradeSymbol::GravitonEmitters,
+            "IRON" => TradeSymbol::Iron,
+            "IRON_ORE" => TradeSymbol::IronOre,
+            "COPPER" => TradeSymbol::Copper,
+            "COPPER_ORE" => TradeSymbol::CopperOre,
+            "ALUMINUM" => TradeSymbol::Aluminum,
+            "ALUMINUM_ORE" => TradeSymbol::AluminumOre,
+            "SILVER" => TradeSymbol::Silver,
+            "SILVER_ORE" => TradeSymbol::SilverOre,
+            "GOLD" => TradeSymbol::Gold,
+            "GOLD_ORE" => TradeSymbol::GoldOre,
+            "PLATINUM" => TradeSymbol::Platinum,
+            "PLATINUM_ORE" => TradeSymbol::PlatinumOre,
+            "DIAMONDS" => TradeSymbol::Diamonds,
+            "URANITE" => TradeSymbol::Uranite,
+            "URANITE_ORE" => TradeSymbol::UraniteOre,
+            "MERITIUM" => TradeSymbol::Meritium,
+            "MERITIUM_ORE" => TradeSymbol::MeritiumOre,
+            "HYDROCARBON" => TradeSymbol::Hydrocarbon,
+            "ANTIMATTER" => TradeSymbol::Antimatter,
+            "FERTILIZERS" => TradeSymbol::Fertilizers,
+            "FABRICS" => TradeSymbol::Fabrics,
+            "FOOD" => TradeSymbol::Food,
+            "JEWELRY" => TradeSymbol::Jewelry,
+            "MACHINERY" => TradeSymbol::Machinery,
+            "FIREARMS" => TradeSymbol::Firearms,
+            "ASSAULT_RIFLES" => TradeSymbol::AssaultRifles,
+            "MILITARY_EQUIPMENT" => TradeSymbol::MilitaryEquipment,
+            "EXPLOSIVES" => TradeSymbol::Explosives,
+            "LAB_INSTRUMENTS" => TradeSymbol::LabInstruments,
+            "AMMUNITION" => TradeSymbol::Ammunition,
+            "ELECTRONICS" => TradeSymbol::Electronics,
+            "SHIP_PLATING" => TradeSymbol::ShipPlating,
+            "EQUIPMENT" => TradeSymbol::Equipment,
+            "FUEL" => TradeSymbol::Fuel,
+            "MEDICINE" => TradeSymbol::Medicine,
+            "DRUGS" => TradeSymbol::Drugs,
+            "CLOTHING" => TradeSymbol::Clothing,
+            "MICROPROCESSORS" => TradeSymbol::Microprocessors,
+            "PLASTICS" => TradeSymbol::Plastics,
+            "POLYNUCLEOTIDES" => TradeSymbol::Polynucleotides,
+            "BIOCOMPOSITES" => TradeSymbol::Biocomposites,
+            "NANOBOTS" => TradeSymbol::Nanobots,
+            "AI_MAINFRAMES" => TradeSymbol::AiMainframes,
+            "QUANTUM_DRIVES" => TradeSymbol::QuantumDrives,
+            "ROBOTIC_DRONES" => TradeSymbol::RoboticDrones,
+            "CYBER_IMPLANTS" => TradeSymbol::CyberImplants,
+            "GENE_THERAPEUTICS" => TradeSymbol::GeneTherapeutics,
+            "NEURAL_CHIPS" => TradeSymbol::NeuralChips,
+            "MOOD_REGULATORS" => TradeSymbol::MoodRegulators,
+            "VIRAL_AGENTS" => TradeSymbol::ViralAgents,
+            "MICRO_FUSION_GENERATORS" => TradeSymbol::MicroFusionGenerators,
+            "SUPERGRAINS" => TradeSymbol::Supergrains,
+            "LASER_RIFLES" => TradeSymbol::LaserRifles,
+            "HOLOGRAPHICS" => TradeSymbol::Holographics,
+            "SHIP_SALVAGE" => TradeSymbol::ShipSalvage,
+            "RELIC_TECH" => TradeSymbol::RelicTech,
+            "NOVEL_LIFEFORMS" => TradeSymbol::NovelLifeforms,
+            "BOTANICAL_SPECIMENS" => TradeSymbol::BotanicalSpecimens,
+            "CULTURAL_ARTIFACTS" => TradeSymbol::CulturalArtifacts,
+            "REACTOR_SOLAR_I" => TradeSymbol::ReactorSolarI,
+            "REACTOR_FUSION_I" => TradeSymbol::ReactorFusionI,
+            "REACTOR_FISSION_I" => TradeSymbol::ReactorFissionI,
+            "REACTOR_CHEMICAL_I" => TradeSymbol::ReactorChemicalI,
+            "REACTOR_ANTIMATTER_I" => TradeSymbol::ReactorAntimatterI,
+            "ENGINE_IMPULSE_DRIVE_I" => TradeSymbol::EngineImpulseDriveI,
+            "ENGINE_ION_DRIVE_I" => TradeSymbol::EngineIonDriveI,
+            "ENGINE_ION_DRIVE_II" => TradeSymbol::EngineIonDriveIi,
+            "ENGINE_HYPER_DRIVE_I" => TradeSymbol::EngineHyperDriveI,
+            "MODULE_MINERAL_PROCESSOR_I" => TradeSymbol::ModuleMineralProcessorI,
+            "MODULE_CARGO_HOLD_I" => TradeSymbol::ModuleCargoHoldI,
+            "MODULE_CREW_QUARTERS_I" => TradeSymbol::ModuleCrewQuartersI,
+            "MODULE_ENVOY_QUARTERS_I" => TradeSymbol::ModuleEnvoyQuartersI,
+            "MODULE_PASSENGER_CABIN_I" => TradeSymbol::ModulePassengerCabinI,
+            "MODULE_MICRO_REFINERY_I" => TradeSymbol::ModuleMicroRefineryI,
+            "MODULE_ORE_REFINERY_I" => TradeSymbol::ModuleOreRefineryI,
+            "MODULE_FUEL_REFINERY_I" => TradeSymbol::ModuleFuelRefineryI,
+            "MODULE_SCIENCE_LAB_I" => TradeSymbol::ModuleScienceLabI,
+            "MODULE_JUMP_DRIVE_I" => TradeSymbol::ModuleJumpDriveI,
+            "MODULE_JUMP_DRIVE_II" => TradeSymbol::ModuleJumpDriveIi,
+            "MODULE_JUMP_DRIVE_III" => TradeSymbol::ModuleJumpDriveIii,
+            "MODULE_WARP_DRIVE_I" => TradeSymbol::ModuleWarpDriveI,
+            "MODULE_WARP_DRIVE_II" => TradeSymbol::ModuleWarpDriveIi,
+            "MODULE_WARP_DRIVE_III" => TradeSymbol::ModuleWarpDriveIii,
+            "MODULE_SHIELD_GENERATOR_I" => TradeSymbol::ModuleShieldGeneratorI,
+            "MODULE_SHIELD_GENERATOR_II" => TradeSymbol::ModuleShieldGeneratorIi,
+            "MOUNT_GAS_SIPHON_I" => TradeSymbol::MountGasSiphonI,
+            "MOUNT_GAS_SIPHON_II" => TradeSymbol::MountGasSiphonIi,
+            "MOUNT_GAS_SIPHON_III" => TradeSymbol::MountGasSiphonIii,
+            "MOUNT_SURVEYOR_I" => TradeSymbol::MountSurveyorI,
+            "MOUNT_SURVEYOR_II" => TradeSymbol::MountSurveyorIi,
+            "MOUNT_SURVEYOR_III" => TradeSymbol::MountSurveyorIii,
+            "MOUNT_SENSOR_ARRAY_I" => TradeSymbol::MountSensorArrayI,
+            "MOUNT_SENSOR_ARRAY_II" => TradeSymbol::MountSensorArrayIi,
+            "MOUNT_SENSOR_ARRAY_III" => TradeSymbol::MountSensorArrayIii,
+            "MOUNT_MINING_LASER_I" => TradeSymbol::MountMiningLaserI,
+            "MOUNT_MINING_LASER_II" => TradeSymbol::MountMiningLaserIi,
+            "MOUNT_MINING_LASER_III" => TradeSymbol::MountMiningLaserIii,
+            "MOUNT_LASER_CANNON_I" => TradeSymbol::MountLaserCannonI,
+            "MOUNT_MISSILE_LAUNCHER_I" => TradeSymbol::MountMissileLauncherI,
+            "MOUNT_TURRET_I" => TradeSymbol::MountTurretI,
+            _ => TradeSymbol::Unknown(name),
+        }
+    }
+}
+
+impl From<TradeSymbol> for String {
+    fn from(value: TradeSymbol) -> Self {
+        match value {
+            TradeSymbol::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for TradeSymbol {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Waypoint {
+    pub symbol: WaypointSymbol,
+    #[serde(rename = "type")]
+    pub kind: WaypointType,
+    pub system_symbol: SystemSymbol,
+    pub x: i32,
+    pub y: i32,
+    pub orbitals: Vec<WaypointOrbital>,
+    pub faction: Option<WaypointFaction>,
+    pub traits: Vec<WaypointTrait>,
+    pub chart: Option<Chart>,
+}
+
+pub type WaypointFaction = Symbolic<FactionSymbol>;
+
+pub type WaypointOrbital = Symbolic<WaypointSymbol>;
+
+pub type WaypointTrait = TypedSymbolic<WaypointTraitSymbol>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum WaypointTraitSymbol {
+    Uncharted,
+    Marketplace,
+    Shipyard,
+    Outpost,
+    ScatteredSettlements,
+    SprawlingCities,
+    MegaStructures,
+    Overcrowded,
+    HighTech,
+    Corrupt,
+    Bureaucratic,
+    TradingHub,
+    Industrial,
+    BlackMarket,
+    ResearchFacility,
+    MilitaryBase,
+    SurveillanceOutpost,
+    ExplorationOutpost,
+    MineralDeposits,
+    CommonMetalDeposits,
+    PreciousMetalDeposits,
+    RareMetalDeposits,
+    MethanePools,
+    IceCrystals,
+    ExplosiveGases,
+    StrongMagnetosphere,
+    VibrantAuroras,
+    SaltFlats,
+    Canyons,
+    PerpetualDaylight,
+    PerpetualOvercast,
+    DrySeabeds,
+    MagmaSeas,
+    Supervolcanoes,
+    AshClouds,
+    VastRuins,
+    MutatedFlora,
+    Terraformed,
+    ExtremeTemperatures,
+    ExtremePressure,
+    DiverseLife,
+    ScarceLife,
+    Fossils,
+    WeakGravity,
+    StrongGravity,
+    CrushingGravity,
+    ToxicAtmosphere,
+    CorrosiveAtmosphere,
+    BreathableAtmosphere,
+    Jovian,
+    Rocky,
+    Volcanic,
+    Frozen,
+    Swamp,
+    Barren,
+    Temperate,
+    Jungle,
+    Ocean,
+    Stripped,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl WaypointTraitSymbol {
+    pub fn as_str(&self) -> &str {
+        match self {
+            WaypointTraitSymbol::Uncharted => "UNCHARTED",
+            WaypointTraitSymbol::Marketplace => "MARKETPLACE",
+            WaypointTraitSymbol::Shipyard => "SHIPYARD",
+            WaypointTraitSymbol::Outpost => "OUTPOST",
+            WaypointTraitSymbol::ScatteredSettlements => "SCATTERED_SETTLEMENTS",
+            WaypointTraitSymbol::SprawlingCities => "SPRAWLING_CITIES",
+            WaypointTraitSymbol::MegaStructures => "MEGA_STRUCTURES",
+            WaypointTraitSymbol::Overcrowded => "OVERCROWDED",
+            WaypointTraitSymbol::HighTech => "HIGH_TECH",
+            WaypointTraitSymbol::Corrupt => "CORRUPT",
+            WaypointTraitSymbol::Bureaucratic => "BUREAUCRATIC",
+            WaypointTraitSymbol::TradingHub => "TRADING_HUB",
+            WaypointTraitSymbol::Industrial => "INDUSTRIAL",
+            WaypointTraitSymbol::BlackMarket => "BLACK_MARKET",
+            WaypointTraitSymbol::ResearchFacility => "RESEARCH_FACILITY",
+            WaypointTraitSymbol::MilitaryBase => "MILITARY_BASE",
+            WaypointTraitSymbol::SurveillanceOutpost => "SURVEILLANCE_OUTPOST",
+            WaypointTraitSymbol::ExplorationOutpost => "EXPLORATION_OUTPOST",
+            WaypointTraitSymbol::MineralDeposits => "MINERAL_DEPOSITS",
+            WaypointTraitSymbol::CommonMetalDeposits => "COMMON_METAL_DEPOSITS",
+            WaypointTraitSymbol::PreciousMetalDeposits => "PRECIOUS_METAL_DEPOSITS",
+            WaypointTraitSymbol::RareMetalDeposits => "RARE_METAL_DEPOSITS",
+            WaypointTraitSymbol::MethanePools => "METHANE_POOLS",
+            WaypointTraitSymbol::IceCrystals => "ICE_CRYSTALS",
+            WaypointTraitSymbol::ExplosiveGases => "EXPLOSIVE_GASES",
+            WaypointTraitSymbol::StrongMagnetosphere => "STRONG_MAGNETOSPHERE",
+            WaypointTraitSymbol::VibrantAuroras => "VIBRANT_AURORAS",
+            WaypointTraitSymbol::SaltFlats => "SALT_FLATS",
+            WaypointTraitSymbol::Canyons => "CANYONS",
+            WaypointTraitSymbol::PerpetualDaylight => "PERPETUAL_DAYLIGHT",
+            WaypointTraitSymbol::PerpetualOvercast => "PERPETUAL_OVERCAST",
+            WaypointTraitSymbol::DrySeabeds => "DRY_SEABEDS",
+            WaypointTraitSymbol::MagmaSeas => "MAGMA_SEAS",
+            WaypointTraitSymbol::Supervolcanoes => "SUPERVOLCANOES",
+            WaypointTraitSymbol::AshClouds => "ASH_CLOUDS",
+            WaypointTraitSymbol::VastRuins => "VAST_RUINS",
+            WaypointTraitSymbol::MutatedFlora => "MUTATED_FLORA",
+            WaypointTraitSymbol::Terraformed => "TERRAFORMED",
+            WaypointTraitSymbol::ExtremeTemperatures => "EXTREME_TEMPERATURES",
+            WaypointTraitSymbol::ExtremePressure => "EXTREME_PRESSURE",
+            WaypointTraitSymbol::DiverseLife => "DIVERSE_LIFE",
+            WaypointTraitSymbol::ScarceLife => "SCARCE_LIFE",
+            WaypointTraitSymbol::Fossils => "FOSSILS",
+            WaypointTraitSymbol::WeakGravity => "WEAK_GRAVITY",
+            WaypointTraitSymbol::StrongGravity => "STRONG_GRAVITY",
+            WaypointTraitSymbol::CrushingGravity => "CRUSHING_GRAVITY",
+            WaypointTraitSymbol::ToxicAtmosphere => "TOXIC_ATMOSPHERE",
+            WaypointTraitSymbol::CorrosiveAtmosphere => "CORROSIVE_ATMOSPHERE",
+            WaypointTraitSymbol::BreathableAtmosphere => "BREATHABLE_ATMOSPHERE",
+            WaypointTraitSymbol::Jovian => "JOVIAN",
+            WaypointTraitSymbol::Rocky => "ROCKY",
+            WaypointTraitSymbol::Volcanic => "VOLCANIC",
+            WaypointTraitSymbol::Frozen => "FROZEN",
+            WaypointTraitSymbol::Swamp => "SWAMP",
+            WaypointTraitSymbol::Barren => "BARREN",
+            WaypointTraitSymbol::Temperate => "TEMPERATE",
+            WaypointTraitSymbol::Jungle => "JUNGLE",
+            WaypointTraitSymbol::Ocean => "OCEAN",
+            WaypointTraitSymbol::Stripped => "STRIPPED",
+            WaypointTraitSymbol::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for WaypointTraitSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for WaypointTraitSymbol {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "UNCHARTED" => WaypointTraitSymbol::Uncharted,
+            "MARKETPLACE" => WaypointTraitSymbol::Marketplace,
+            "SHIPYARD" => WaypointTraitSymbol::Shipyard,
+            "OUTPOST" => WaypointTraitSymbol::Outpost,
+            "SCATTERED_SETTLEMENTS" => WaypointTraitSymbol::ScatteredSettlements,
+            "SPRAWLING_CITIES" => WaypointTraitSymbol::SprawlingCities,
+            "MEGA_STRUCTURES" => WaypointTraitSymbol::MegaStructures,
+            "OVERCROWDED" => WaypointTraitSymbol::Overcrowded,
+            "HIGH_TECH" => WaypointTraitSymbol::HighTech,
+            "CORRUPT" => WaypointTraitSymbol::Corrupt,
+            "BUREAUCRATIC" => WaypointTraitSymbol::Bureaucratic,
+            "TRADING_HUB" => WaypointTraitSymbol::TradingHub,
+            "INDUSTRIAL" => WaypointTraitSymbol::Industrial,
+            "BLACK_MARKET" => WaypointTraitSymbol::BlackMarket,
+            "RESEARCH_FACILITY" => WaypointTraitSymbol::ResearchFacility,
+            "MILITARY_BASE" => WaypointTraitSymbol::MilitaryBase,
+            "SURVEILLANCE_OUTPOST" => WaypointTraitSymbol::SurveillanceOutpost,
+            "EXPLORATION_OUTPOST" => WaypointTraitSymbol::ExplorationOutpost,
+            "MINERAL_DEPOSITS" => WaypointTraitSymbol::MineralDeposits,
+            "COMMON_METAL_DEPOSITS" => WaypointTraitSymbol::CommonMetalDeposits,
+            "PRECIOUS_METAL_DEPOSITS" => WaypointTraitSymbol::PreciousMetalDeposits,
+            "RARE_METAL_DEPOSITS" => WaypointTraitSymbol::RareMetalDeposits,
+            "METHANE_POOLS" => WaypointTraitSymbol::MethanePools,
+            "ICE_CRYSTALS" => WaypointTraitSymbol::IceCrystals,
+            "EXPLOSIVE_GASES" => WaypointTraitSymbol::ExplosiveGases,
+            "STRONG_MAGNETOSPHERE" => WaypointTraitSymbol::StrongMagnetosphere,
+            "VIBRANT_AURORAS" => WaypointTraitSymbol::VibrantAuroras,
+            "SALT_FLATS" => WaypointTraitSymbol::SaltFlats,
+            "CANYONS" => WaypointTraitSymbol::Canyons,
+            "PERPETUAL_DAYLIGHT" => WaypointTraitSymbol::PerpetualDaylight,
+            "PERPETUAL_OVERCAST" => WaypointTraitSymbol::PerpetualOvercast,
+            "DRY_SEABEDS" => WaypointTraitSymbol::DrySeabeds,
+            "MAGMA_SEAS" => WaypointTraitSymbol::MagmaSeas,
+            "SUPERVOLCANOES" => WaypointTraitSymbol::Supervolcanoes,
+            "ASH_CLOUDS" => WaypointTraitSymbol::AshClouds,
+            "VAST_RUINS" => WaypointTraitSymbol::VastRuins,
+            "MUTATED_FLORA" => WaypointTraitSymbol::MutatedFlora,
+            "TERRAFORMED" => WaypointTraitSymbol::Terraformed,
+            "EXTREME_TEMPERATURES" => WaypointTraitSymbol::ExtremeTemperatures,
+            "EXTREME_PRESSURE" => WaypointTraitSymbol::ExtremePressure,
+            "DIVERSE_LIFE" => WaypointTraitSymbol::DiverseLife,
+            "SCARCE_LIFE" => WaypointTraitSymbol::ScarceLife,
+            "FOSSILS" => WaypointTraitSymbol::Fossils,
+            "WEAK_GRAVITY" => WaypointTraitSymbol::WeakGravity,
+            "STRONG_GRAVITY" => WaypointTraitSymbol::StrongGravity,
+            "CRUSHING_GRAVITY" => WaypointTraitSymbol::CrushingGravity,
+            "TOXIC_ATMOSPHERE" => WaypointTraitSymbol::ToxicAtmosphere,
+            "CORROSIVE_ATMOSPHERE" => WaypointTraitSymbol::CorrosiveAtmosphere,
+            "BREATHABLE_ATMOSPHERE" => WaypointTraitSymbol::BreathableAtmosphere,
+            "JOVIAN" => WaypointTraitSymbol::Jovian,
+            "ROCKY" => WaypointTraitSymbol::Rocky,
+            "VOLCANIC" => WaypointTraitSymbol::Volcanic,
+            "FROZEN" => WaypointTraitSymbol::Frozen,
+            "SWAMP" => WaypointTraitSymbol::Swamp,
+            "BARREN" => WaypointTraitSymbol::Barren,
+            "TEMPERATE" => WaypointTraitSymbol::Temperate,
+            "JUNGLE" => WaypointTraitSymbol::Jungle,
+            "OCEAN" => WaypointTraitSymbol::Ocean,
+            "STRIPPED" => WaypointTraitSymbol::Stripped,
+            _ => WaypointTraitSymbol::Unknown(name),
+        }
+    }
+}
+
+impl From<WaypointTraitSymbol> for String {
+    fn from(value: WaypointTraitSymbol) -> Self {
+        match value {
+            WaypointTraitSymbol::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for WaypointTraitSymbol {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(from = "String", into = "String")]
+pub enum WaypointType {
+    Planet,
+    GasGiant,
+    Moon,
+    OrbitalStation,
+    JumpGate,
+    AsteroidField,
+    Nebula,
+    DebrisField,
+    GravityWell,
+    /// one the spec doesn't list (yet), by its name
+    Unknown(String),
+}
+
+impl WaypointType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            WaypointType::Planet => "PLANET",
+            WaypointType::GasGiant => "GAS_GIANT",
+            WaypointType::Moon => "MOON",
+            WaypointType::OrbitalStation => "ORBITAL_STATION",
+            WaypointType::JumpGate => "JUMP_GATE",
+            WaypointType::AsteroidField => "ASTEROID_FIELD",
+            WaypointType::Nebula => "NEBULA",
+            WaypointType::DebrisField => "DEBRIS_FIELD",
+            WaypointType::GravityWell => "GRAVITY_WELL",
+            WaypointType::Unknown(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for WaypointType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl From<String> for WaypointType {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "PLANET" => WaypointType::Planet,
+            "GAS_GIANT" => WaypointType::GasGiant,
+            "MOON" => WaypointType::Moon,
+            "ORBITAL_STATION" => WaypointType::OrbitalStation,
+            "JUMP_GATE" => WaypointType::JumpGate,
+            "ASTEROID_FIELD" => WaypointType::AsteroidField,
+            "NEBULA" => WaypointType::Nebula,
+            "DEBRIS_FIELD" => WaypointType::DebrisField,
+            "GRAVITY_WELL" => WaypointType::GravityWell,
+            _ => WaypointType::Unknown(name),
+        }
+    }
+}
+
+impl From<WaypointType> for String {
+    fn from(value: WaypointType) -> Self {
+        match value {
+            WaypointType::Unknown(name) => name,
+            value => value.as_str().to_owned(),
+        }
+    }
+}
+
+impl FromStr for WaypointType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(s.to_owned().into()) }
+}
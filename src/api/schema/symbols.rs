@@ -0,0 +1,151 @@
+/*
+Symbols name everything in the game, and most of them nest: waypoint
+X1-DF55-20250Z is in system X1-DF55, which is in sector X1. Each kind
+gets its own type so a ship can't be sent somewhere by its own name,
+and so a waypoint can tell you its system without another request.
+
+They serialize as the plain string. Decoding doesn't check the format,
+since whatever the server calls something is its name; parse() does,
+for symbols that come from anywhere else.
+*/
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+/// what parse() says about a string that isn't that kind of symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSymbolError {
+    pub kind: &'static str,
+    pub value: String,
+}
+
+impl fmt::Display for ParseSymbolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} isn't a {}", self.value, self.kind)
+    }
+}
+
+impl std::error::Error for ParseSymbolError {}
+
+fn is_part(part: &str) -> bool {
+    !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn has_parts(symbol: &str, count: usize) -> bool {
+    //! "X1-DF55" has 2
+    symbol.split('-').count() == count && symbol.split('-').all(is_part)
+}
+
+macro_rules! symbol {
+    ($(#[$doc:meta])* $name:ident, $valid:expr) => {
+        $(#[$doc])*
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn as_str(&self) -> &str { &self.0 }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(&self.0) }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseSymbolError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let valid: fn(&str) -> bool = $valid;
+                match valid(s) {
+                    true => Ok(Self(s.to_owned())),
+                    false => Err(ParseSymbolError {
+                        kind: stringify!($name),
+                        value: s.to_owned(),
+                    }),
+                }
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str { &self.0 }
+        }
+
+        // so maps keyed by symbol can be looked up with a &str
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str { &self.0 }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool { self.0 == other }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool { self.0 == *other }
+        }
+
+        impl From<$name> for String {
+            fn from(symbol: $name) -> String { symbol.0 }
+        }
+    };
+}
+
+symbol!(
+    /// e.g. "X1"
+    SectorSymbol,
+    |s| has_parts(s, 1)
+);
+
+symbol!(
+    /// e.g. "X1-DF55"
+    SystemSymbol,
+    |s| has_parts(s, 2)
+);
+
+symbol!(
+    /// e.g. "X1-DF55-20250Z"
+    WaypointSymbol,
+    |s| has_parts(s, 3)
+);
+
+symbol!(
+    /// the agent's symbol and a number, e.g. "MY_AGENT-1"
+    ShipSymbol,
+    |s| match s.rsplit_once('-') {
+        Some((agent, number)) => {
+            !agent.is_empty()
+                && agent
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                && is_part(number)
+        }
+        None => false,
+    }
+);
+
+symbol!(
+    /// e.g. "COSMIC"
+    FactionSymbol,
+    |s| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+);
+
+impl SystemSymbol {
+    pub fn sector(&self) -> SectorSymbol {
+        let sector = self.0.split('-').next().unwrap_or_default();
+        SectorSymbol(sector.to_owned())
+    }
+}
+
+impl WaypointSymbol {
+    pub fn system(&self) -> SystemSymbol {
+        // anything the server sends that isn't shaped like a waypoint is its own system
+        let system = self
+            .0
+            .rsplit_once('-')
+            .map_or(&*self.0, |(system, _)| system);
+        SystemSymbol(system.to_owned())
+    }
+
+    pub fn sector(&self) -> SectorSymbol { self.system().sector() }
+}
@@ -0,0 +1,320 @@
+/*
+Transports are what actually get a request to the server and a
+response back. SpaceTraderApi builds a Request, hands it to its
+transport, and decodes whatever comes back, so the endpoint code
+doesn't care whether that's reqwest, something in memory, or a stack
+of middleware wrapped around either.
+
+Middleware are transports that wrap another one: Logged, RateLimited,
+Recorded and Retrying. Stack them in the order you want them to run,
+outermost first, e.g. Retrying -> RateLimited -> Reqwest means every
+retry waits its turn in the rate limit.
+*/
+
+use crate::api::Error;
+use crate::recording::{Recorder, Recording};
+use chrono::Utc;
+use reqwest::Method;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: Method,
+    pub base_url: String,
+    /// relative to base_url, query included, e.g. "/my/ships?page=1&limit=20"
+    pub path: String,
+    pub token: String,
+    pub body: Option<Value>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Response {
+    pub status: u16,
+    /// names are lowercase
+    pub headers: BTreeMap<String, String>,
+    /// empty for a 204
+    pub body: String,
+}
+
+pub trait Transport: Send + Sync {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, Error>>;
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, Error>> {
+        (**self).send(request)
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, Error>> {
+        (**self).send(request)
+    }
+}
+
+/// the real thing
+#[derive(Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn with_client(client: reqwest::Client) -> Self { Self { client } }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, Error>> {
+        Box::pin(async move {
+            let url = format!("{}{}", request.base_url, request.path);
+            let mut builder = self.client.request(request.method, url);
+            if !request.token.is_empty() {
+                builder = builder.bearer_auth(&request.token);
+            }
+            if let Some(body) = &request.body {
+                builder = builder.json(body);
+            }
+            let response = builder.send().await?;
+            let status = response.status().as_u16();
+            let headers = response
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_owned()))
+                })
+                .collect();
+            let body = response.text().await?;
+            Ok(Response {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+/// answers requests with a function instead of a server, for tests
+#[derive(Clone)]
+pub struct InMemoryTransport {
+    handler: Arc<dyn Fn(&Request) -> Response + Send + Sync>,
+}
+
+impl InMemoryTransport {
+    pub fn new(handler: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Self {
+        Self {
+            handler: Arc::new(handler),
+        }
+    }
+}
+
+impl Transport for InMemoryTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, Error>> {
+        let response = (self.handler)(&request);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+/// reports every request and how it went, one line each
+pub struct Logged<T> {
+    inner: T,
+    log: Box<dyn Fn(&str) + Send + Sync>,
+}
+
+impl<T: Transport> Logged<T> {
+    pub fn to_stderr(inner: T) -> Self {
+        //! for when there's nothing better to hand the lines to
+        Self::with_logger(inner, |line| eprintln!("{line}"))
+    }
+
+    pub fn with_logger(inner: T, log: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        Self {
+            inner,
+            log: Box::new(log),
+        }
+    }
+}
+
+impl<T: Transport> Transport for Logged<T> {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, Error>> {
+        Box::pin(async move {
+            let line = format!("{} {}", request.method, request.path);
+            let timer = Instant::now();
+            let result = self.inner.send(request).await;
+            let elapsed = timer.elapsed().as_millis();
+            match &result {
+                Ok(response) => {
+                    (self.log)(&format!("{line} -> {} in {elapsed}ms", response.status))
+                }
+                Err(e) => (self.log)(&format!("{line} -> failed in {elapsed}ms: {e}")),
+            }
+            result
+        })
+    }
+}
+
+/// a token bucket, so requests go out no faster than the server allows
+/// instead of being bounced with a 429
+pub struct RateLimited<T> {
+    inner: T,
+    per_second: f64,
+    burst: f64,
+    bucket: Mutex<(f64, Instant)>,
+}
+
+impl<T: Transport> RateLimited<T> {
+    pub fn new(inner: T, per_second: f64, burst: u32) -> Self {
+        Self {
+            inner,
+            per_second,
+            burst: burst as f64,
+            bucket: Mutex::new((burst as f64, Instant::now())),
+        }
+    }
+
+    pub fn spacetraders(inner: T) -> Self {
+        //! the limits the game server enforces per account: 2 a second,
+        //! with bursts of up to 10
+        Self::new(inner, 2.0, 10)
+    }
+
+    async fn wait_turn(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().await;
+            let now = Instant::now();
+            let refilled = now.duration_since(bucket.1).as_secs_f64() * self.per_second;
+            bucket.0 = (bucket.0 + refilled).min(self.burst);
+            bucket.1 = now;
+            // going negative reserves a later slot, so waiters keep their order
+            bucket.0 -= 1.0;
+            match bucket.0 {
+                tokens if tokens >= 0.0 => Duration::ZERO,
+                tokens => Duration::from_secs_f64(-tokens / self.per_second),
+            }
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+impl<T: Transport> Transport for RateLimited<T> {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, Error>> {
+        Box::pin(async move {
+            self.wait_turn().await;
+            self.inner.send(request).await
+        })
+    }
+}
+
+/// writes every exchange to a Recorder. the token is left out
+pub struct Recorded<T> {
+    inner: T,
+    recorder: Recorder,
+}
+
+impl<T: Transport> Recorded<T> {
+    pub fn new(inner: T, recorder: Recorder) -> Self { Self { inner, recorder } }
+}
+
+impl<T: Transport> Transport for Recorded<T> {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, Error>> {
+        Box::pin(async move {
+            let method = request.method.to_string();
+            let path = request.path.clone();
+            let request_body = request.body.clone();
+            let started_at = Utc::now();
+            let timer = std::time::Instant::now();
+            let response = self.inner.send(request).await?;
+            self.recorder.record(&Recording {
+                method,
+                path,
+                request_body,
+                status: response.status,
+                headers: response.headers.clone(),
+                body: response.body.clone(),
+                started_at,
+                duration_ms: timer.elapsed().as_millis() as u64,
+            })?;
+            Ok(response)
+        })
+    }
+}
+
+/// tries again after rate limits, server errors and failed connections,
+/// backing off a little more each time. only GETs are tried again after
+/// a server error or failed connection, since anything else may have
+/// gone through with just the response lost; a 429 was never acted on,
+/// so those are always tried again
+pub struct Retrying<T> {
+    inner: T,
+    attempts: u32,
+    backoff: Duration,
+    retry_writes: bool,
+}
+
+/// the longest a Retry-After is taken at its word
+const MAX_RETRY_AFTER: f64 = 60.0 * 60.0;
+
+impl<T: Transport> Retrying<T> {
+    pub fn new(inner: T, attempts: u32) -> Self {
+        Self {
+            inner,
+            attempts: attempts.max(1),
+            backoff: Duration::from_millis(500),
+            retry_writes: false,
+        }
+    }
+
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        //! how long to wait before the first retry. it doubles after that
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn with_retry_writes(mut self, retry_writes: bool) -> Self {
+        //! tries POSTs, PATCHes and so on again after server errors and
+        //! failed connections too, for servers where that's known to be safe
+        self.retry_writes = retry_writes;
+        self
+    }
+}
+
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds: f64 = response.headers.get("retry-after")?.parse().ok()?;
+    // anything that isn't a number (NaN) falls back on the backoff
+    Duration::try_from_secs_f64(seconds.clamp(0.0, MAX_RETRY_AFTER)).ok()
+}
+
+impl<T: Transport> Transport for Retrying<T> {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, Error>> {
+        Box::pin(async move {
+            let mut backoff = self.backoff;
+            let mut attempt = 1;
+            let retryable = self.retry_writes || request.method == Method::GET;
+            loop {
+                let last = attempt >= self.attempts;
+                let wait = match self.inner.send(request.clone()).await {
+                    Ok(response) if response.status == 429 && !last => {
+                        retry_after(&response).unwrap_or(backoff)
+                    }
+                    Ok(response) if response.status >= 500 && retryable && !last => backoff,
+                    Err(_) if retryable && !last => backoff,
+                    result => return result,
+                };
+                tokio::time::sleep(wait).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+        })
+    }
+}
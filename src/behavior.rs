@@ -0,0 +1,426 @@
+/*
+The behavior module scripts what a ship does as a handful of hooks
+rather than one long async function.
+
+A ShipBehavior is called on_start when it's handed a ship (once the
+ship isn't in transit), on_arrival when a trip or jump it asked for
+is over, and on_cooldown_ready once the ship can extract or survey
+again. Each hook does whatever can be done on the spot and answers
+with a Step: where to fly or jump next, to wait out the cooldown, to
+sleep for a while, or that it's done. Errors go to on_error, which
+can pick a Step to carry on with, or hand the error back so the
+fleet restarts the behavior after its backoff. Every ShipBehavior is
+a fleet::Behavior, so it can be given straight to a Fleet.
+
+Hooks should decide from the ship as it is now rather than from what
+they remember doing, since a run can be stopped and started over at
+any point. The built-in behaviors cover the usual roles, and
+Builtin::for_ship() picks one from a ship's registered role, or from
+its frame when the role doesn't say.
+*/
+
+use crate::api::schema::{
+    Market, Ship, ShipFrameSymbols, ShipRole, Survey, SystemSymbol, TradeSymbol, Waypoint,
+    WaypointSymbol, WaypointTraitSymbol,
+};
+use crate::api::transport::BoxFuture;
+use crate::api::Error;
+use crate::fleet::{Behavior, ShipContext};
+use crate::galaxy::Galaxy;
+use crate::navigation;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod contracts;
+mod explorer;
+mod hauler;
+mod miner;
+mod scout;
+mod surveyor;
+
+pub use contracts::ContractRunner;
+pub use explorer::Explorer;
+pub use hauler::Hauler;
+pub use miner::{Miner, MiningCycle};
+pub use scout::MarketScout;
+pub use surveyor::Surveyor;
+
+/// how long the built-ins sleep when there's nothing for them to do
+const IDLE: Duration = Duration::from_secs(10 * 60);
+/// surveys of one waypoint kept in a SurveyPool before the oldest go
+const SURVEYS_PER_WAYPOINT: usize = 20;
+
+/// what a ship should do once a hook is done with it
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+    /// fly to a waypoint in the same system, then on_arrival
+    NavigateTo(WaypointSymbol),
+    /// jump to another system through the gate the ship is at, then on_arrival
+    JumpTo(SystemSymbol),
+    /// wait until the ship's cooldown is over, then on_cooldown_ready
+    Cooldown,
+    /// sleep, then on_start all over again
+    Wait(Duration),
+    /// stop, and leave the ship idle until it's reassigned
+    Done,
+}
+
+pub trait ShipBehavior: Send + Sync {
+    fn on_start<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>>;
+
+    fn on_arrival<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>> {
+        //! by default, works out what to do from wherever the ship is now
+        self.on_start(ship)
+    }
+
+    fn on_cooldown_ready<'a>(
+        &'a self,
+        ship: &'a ShipContext,
+    ) -> BoxFuture<'a, Result<Step, Error>> {
+        self.on_start(ship)
+    }
+
+    fn on_error<'a>(
+        &'a self,
+        _ship: &'a ShipContext,
+        error: Error,
+    ) -> BoxFuture<'a, Result<Step, Error>> {
+        //! by default, gives up and leaves it to the fleet to start over
+        Box::pin(async move { Err(error) })
+    }
+}
+
+impl<B: ShipBehavior> Behavior for B {
+    fn run<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(drive(self, ship))
+    }
+}
+
+async fn drive<B: ShipBehavior>(behavior: &B, ship: &ShipContext) -> Result<(), Error> {
+    // so on_start always finds the ship somewhere
+    let mut next = match ship.wait_for_arrival().await {
+        Ok(()) => behavior.on_start(ship).await,
+        Err(error) => Err(error),
+    };
+    loop {
+        let step = match next {
+            Ok(step) => step,
+            Err(error) => behavior.on_error(ship, error).await?,
+        };
+        next = match step {
+            Step::NavigateTo(waypoint) => match ship.navigate(&waypoint).await {
+                Ok(()) => behavior.on_arrival(ship).await,
+                Err(error) => Err(error),
+            },
+            Step::JumpTo(system) => match ship.jump(&system).await {
+                Ok(()) => behavior.on_arrival(ship).await,
+                Err(error) => Err(error),
+            },
+            Step::Cooldown => match ship.wait_for_cooldown().await {
+                Ok(()) => behavior.on_cooldown_ready(ship).await,
+                Err(error) => Err(error),
+            },
+            Step::Wait(duration) => {
+                tokio::time::sleep(duration).await;
+                behavior.on_start(ship).await
+            }
+            Step::Done => return Ok(()),
+        };
+    }
+}
+
+/// does nothing, for ships none of the built-ins know what to do with
+pub struct Idle;
+
+impl ShipBehavior for Idle {
+    fn on_start<'a>(&'a self, _ship: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>> {
+        Box::pin(async { Ok(Step::Done) })
+    }
+}
+
+/// surveys shared between the ships that make them and the ones that mine
+#[derive(Default)]
+pub struct SurveyPool {
+    surveys: Mutex<Vec<Survey>>,
+}
+
+impl SurveyPool {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn add(&self, surveys: impl IntoIterator<Item = Survey>) {
+        //! keeps the newest SURVEYS_PER_WAYPOINT of each waypoint
+        let mut pool = self.surveys.lock().unwrap();
+        for survey in surveys {
+            let waypoint = survey.symbol.clone();
+            pool.push(survey);
+            let count = pool.iter().filter(|s| s.symbol == waypoint).count();
+            if count > SURVEYS_PER_WAYPOINT {
+                let oldest = pool.iter().position(|s| s.symbol == waypoint).unwrap();
+                pool.remove(oldest);
+            }
+        }
+    }
+
+    pub fn best(&self, waypoint: &WaypointSymbol, now: DateTime<Utc>) -> Option<Survey> {
+        //! the largest survey of waypoint that hasn't expired by now, and
+        //! of those the one that lasts longest
+        let pool = self.surveys.lock().unwrap();
+        pool.iter()
+            .filter(|survey| survey.symbol == *waypoint && survey.expiration > now)
+            .max_by_key(|survey| (survey.size, survey.expiration))
+            .cloned()
+    }
+
+    pub fn remove(&self, signature: &str) -> bool {
+        //! for surveys the server says are used up or expired
+        let mut pool = self.surveys.lock().unwrap();
+        let before = pool.len();
+        pool.retain(|survey| survey.signature != signature);
+        pool.len() != before
+    }
+
+    pub fn len(&self) -> usize { self.surveys.lock().unwrap().len() }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+/// the built-in behaviors, for picking one without making it yet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Builtin {
+    Miner,
+    Hauler,
+    Surveyor,
+    MarketScout,
+    Explorer,
+    ContractRunner,
+}
+
+impl Builtin {
+    pub fn for_ship(ship: &Ship) -> Option<Self> {
+        //! by the ship's registered role, or failing that its frame
+        Self::for_role(ship.registration.role)
+            .or_else(|| Self::for_frame(ship.frame.symbol.clone()))
+    }
+
+    pub fn for_role(role: ShipRole) -> Option<Self> {
+        match role {
+            ShipRole::Excavator | ShipRole::Harvester => Some(Builtin::Miner),
+            ShipRole::Hauler | ShipRole::Transport => Some(Builtin::Hauler),
+            ShipRole::Surveyor => Some(Builtin::Surveyor),
+            ShipRole::Satellite => Some(Builtin::MarketScout),
+            ShipRole::Explorer => Some(Builtin::Explorer),
+            ShipRole::Command => Some(Builtin::ContractRunner),
+            _ => None,
+        }
+    }
+
+    pub fn for_frame(frame: ShipFrameSymbols) -> Option<Self> {
+        match frame {
+            ShipFrameSymbols::FrameMiner | ShipFrameSymbols::FrameDrone => Some(Builtin::Miner),
+            ShipFrameSymbols::FrameLightFreighter
+            | ShipFrameSymbols::FrameHeavyFreighter
+            | ShipFrameSymbols::FrameTransport => Some(Builtin::Hauler),
+            ShipFrameSymbols::FrameProbe => Some(Builtin::MarketScout),
+            ShipFrameSymbols::FrameExplorer => Some(Builtin::Explorer),
+            ShipFrameSymbols::FrameFrigate => Some(Builtin::ContractRunner),
+            _ => None,
+        }
+    }
+
+    pub fn behavior(&self, surveys: &Arc<SurveyPool>, galaxy: &Arc<Galaxy>) -> Arc<dyn Behavior> {
+        //! a new one with default settings. miners and surveyors share
+        //! surveys, and explorers record what they find in galaxy
+        match self {
+            Builtin::Miner => Arc::new(Miner::new(surveys.clone())),
+            Builtin::Hauler => Arc::new(Hauler::new()),
+            Builtin::Surveyor => Arc::new(Surveyor::new(surveys.clone())),
+            Builtin::MarketScout => Arc::new(MarketScout::new()),
+            Builtin::Explorer => Arc::new(Explorer::new(galaxy.clone())),
+            Builtin::ContractRunner => {
+                Arc::new(ContractRunner::new().with_surveys(surveys.clone()))
+            }
+        }
+    }
+}
+
+pub fn default_behavior(
+    ship: &Ship,
+    surveys: &Arc<SurveyPool>,
+    galaxy: &Arc<Galaxy>,
+) -> Arc<dyn Behavior> {
+    //! for Fleet::start, e.g.
+    //! `fleet.start(|ship| default_behavior(ship, &surveys, &galaxy))`.
+    //! ships that don't fit any of the built-ins are left Idle
+    match Builtin::for_ship(ship) {
+        Some(builtin) => builtin.behavior(surveys, galaxy),
+        None => Arc::new(Idle),
+    }
+}
+
+// the rest are odds and ends the built-ins share
+
+fn distance(ship: &Ship, waypoint: &Waypoint) -> f64 {
+    let here = &ship.nav.route.destination;
+    navigation::distance((here.x, here.y), (waypoint.x, waypoint.y))
+}
+
+fn by_distance<'a>(
+    ship: &Ship,
+    waypoints: impl IntoIterator<Item = &'a Waypoint>,
+) -> Vec<&'a Waypoint> {
+    let mut waypoints: Vec<&Waypoint> = waypoints.into_iter().collect();
+    waypoints.sort_by(|a, b| distance(ship, a).total_cmp(&distance(ship, b)));
+    waypoints
+}
+
+fn has_trait(waypoint: &Waypoint, symbol: WaypointTraitSymbol) -> bool {
+    waypoint.traits.iter().any(|t| t.symbol == symbol)
+}
+
+fn is_full(ship: &Ship) -> bool {
+    ship.cargo.capacity > 0 && ship.cargo.units >= ship.cargo.capacity
+}
+
+fn units_of(ship: &Ship, symbol: &TradeSymbol) -> u32 {
+    ship.cargo
+        .inventory
+        .iter()
+        .filter(|item| item.symbol == *symbol)
+        .map(|item| item.units)
+        .sum()
+}
+
+async fn waypoints(ship: &ShipContext, here: &Ship) -> Result<Vec<Waypoint>, Error> {
+    ship.api.list_waypoints(&here.nav.system_symbol).await
+}
+
+fn buys(market: &Market, symbol: &TradeSymbol) -> bool {
+    //! by the prices if we can see them, or else the listing
+    match market.trade_goods.is_empty() {
+        false => market.trade_goods.iter().any(|good| good.symbol == *symbol),
+        true => market
+            .imports
+            .iter()
+            .chain(&market.exchange)
+            .any(|good| good.symbol == *symbol),
+    }
+}
+
+fn sells(market: &Market, symbol: &TradeSymbol) -> bool {
+    match market.trade_goods.is_empty() {
+        false => market.trade_goods.iter().any(|good| good.symbol == *symbol),
+        true => market
+            .exports
+            .iter()
+            .chain(&market.exchange)
+            .any(|good| good.symbol == *symbol),
+    }
+}
+
+async fn nearest_buyer<'a>(
+    ship: &ShipContext,
+    here: &Ship,
+    waypoints: &'a [Waypoint],
+    goods: &[TradeSymbol],
+) -> Result<Option<&'a Waypoint>, Error> {
+    //! the closest market that takes any of goods
+    let markets = waypoints
+        .iter()
+        .filter(|waypoint| has_trait(waypoint, WaypointTraitSymbol::Marketplace));
+    for waypoint in by_distance(here, markets) {
+        let market = ship.api.get_market(&waypoint.symbol).await?;
+        if goods.iter().any(|symbol| buys(&market, symbol)) {
+            return Ok(Some(waypoint));
+        }
+    }
+    Ok(None)
+}
+
+async fn sell_here(ship: &ShipContext, keep: &[TradeSymbol]) -> Result<i64, Error> {
+    //! docks and sells everything the market here takes, except what's in
+    //! keep, a trade volume at a time. returns how much it made
+    ship.dock().await?;
+    let here = ship.current().await?;
+    let market = ship.api.refresh_market(&here.nav.waypoint_symbol).await?;
+    let mut earned = 0;
+    for item in &here.cargo.inventory {
+        if keep.contains(&item.symbol) || !buys(&market, &item.symbol) {
+            continue;
+        }
+        let volume = trade_volume(&market, item.symbol.clone()).unwrap_or(item.units);
+        let mut left = item.units;
+        while left > 0 {
+            let units = left.min(volume.max(1));
+            let sold = ship
+                .api
+                .sell_cargo(&ship.symbol, item.symbol.clone(), units)
+                .await?;
+            earned += sold.transaction.total_price as i64;
+            left -= units;
+        }
+    }
+    if earned > 0 {
+        // so the price history sees what selling did to the prices
+        ship.api.refresh_market(&here.nav.waypoint_symbol).await?;
+    }
+    Ok(earned)
+}
+
+async fn buy_here(ship: &ShipContext, symbol: TradeSymbol, units: u32) -> Result<u32, Error> {
+    //! docks and buys up to units of symbol, as many as there's room and
+    //! credits for. returns how many it got, which is none while the
+    //! balance isn't known
+    ship.dock().await?;
+    let here = ship.current().await?;
+    let market = ship.api.refresh_market(&here.nav.waypoint_symbol).await?;
+    let good = match market.trade_goods.iter().find(|good| good.symbol == symbol) {
+        Some(good) => good,
+        None => return Ok(0),
+    };
+    let credits = match ship.api.agent_state().credits() {
+        Some(credits) => credits,
+        None => return Ok(0),
+    };
+    let affordable = (credits / good.purchase_price.max(1) as i64).clamp(0, u32::MAX as i64);
+    let room = here.cargo.capacity.saturating_sub(here.cargo.units);
+    let mut left = units.min(room).min(affordable as u32);
+    let mut bought = 0;
+    while left > 0 {
+        let units = left.min(good.trade_volume.max(1));
+        ship.api
+            .purchase_cargo(&ship.symbol, symbol.clone(), units)
+            .await?;
+        bought += units;
+        left -= units;
+    }
+    if bought > 0 {
+        ship.api.refresh_market(&here.nav.waypoint_symbol).await?;
+    }
+    Ok(bought)
+}
+
+async fn refuel_here(ship: &ShipContext) -> Result<i64, Error> {
+    //! tops up the tank, if it needs it and the market here sells fuel.
+    //! returns what it cost, as far as the agent's credits tell
+    let here = ship.current().await?;
+    if here.fuel.current >= here.fuel.capacity {
+        return Ok(0);
+    }
+    let market = ship.api.get_market(&here.nav.waypoint_symbol).await?;
+    if !sells(&market, &TradeSymbol::Fuel) {
+        return Ok(0);
+    }
+    ship.dock().await?;
+    let before = ship.api.agent_state().credits();
+    let refueled = ship.api.refuel_ship(&ship.symbol).await?;
+    Ok(before.map_or(0, |before| before - refueled.agent.credits))
+}
+
+fn trade_volume(market: &Market, symbol: TradeSymbol) -> Option<u32> {
+    market
+        .trade_goods
+        .iter()
+        .find(|good| good.symbol == symbol)
+        .map(|good| good.trade_volume)
+}
@@ -0,0 +1,287 @@
+/*
+The contract runner works through our procurement contracts one good
+at a time. It gets what's wanted at the cheapest market the
+PriceHistory knows of, or, when no market we know sells it and the
+ship has a mining laser, by mining it at the nearest asteroid field
+and jettisoning everything else. It flies each hold full to the
+destination, delivers it, and fulfills the contract once everything's
+in. Anything in the hold the contract doesn't want is sold, if
+there's a market where the ship is that takes it, or jettisoned, so
+it can't fill up the room the contract needs.
+
+With no contract on the go it looks at what's on offer, and with
+none of those it asks the faction for a new one, waiting if it says
+no. An offer is only accepted if the ContractEvaluator reckons this
+ship can source and deliver it before the deadline, at a profit and
+with the credits we have; otherwise it waits for something better.
+Contracts past their deadline, and offers past their expiration, are
+left alone. The deadline is by the server's clock, which the runner
+takes on trust to be ours unless it's given another.
+*/
+
+use super::{
+    buy_here, by_distance, has_trait, is_full, refuel_here, sell_here, units_of, waypoints,
+    ShipBehavior, Step, SurveyPool, IDLE,
+};
+use crate::api::results::get_error_code;
+use crate::api::schema::{
+    Contract, ContractType, Ship, TradeSymbol, WaypointTraitSymbol, WaypointType,
+};
+use crate::api::transport::BoxFuture;
+use crate::api::Error;
+use crate::capabilities::ShipCapabilities;
+use crate::contracts::{ContractEvaluator, Evaluation};
+use crate::fleet::ShipContext;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+pub struct ContractRunner {
+    surveys: Option<Arc<SurveyPool>>,
+    clock: Box<dyn Fn() -> DateTime<Utc> + Send + Sync>,
+    log: Box<dyn Fn(&Evaluation) + Send + Sync>,
+}
+
+impl Default for ContractRunner {
+    fn default() -> Self { Self::new() }
+}
+
+impl ContractRunner {
+    pub fn new() -> Self {
+        Self {
+            surveys: None,
+            clock: Box::new(Utc::now),
+            log: Box::new(|_| {}),
+        }
+    }
+
+    pub fn with_surveys(mut self, surveys: Arc<SurveyPool>) -> Self {
+        //! for when it mines what a contract wants
+        self.surveys = Some(surveys);
+        self
+    }
+
+    pub fn with_clock(mut self, clock: impl Fn() -> DateTime<Utc> + Send + Sync + 'static) -> Self {
+        //! what the server thinks the time is, for checking deadlines
+        self.clock = Box::new(clock);
+        self
+    }
+
+    pub fn with_logger(mut self, log: impl Fn(&Evaluation) + Send + Sync + 'static) -> Self {
+        //! called with the evaluation of every offer it considers, taken or not
+        self.log = Box::new(log);
+        self
+    }
+
+    async fn contract(&self, ship: &ShipContext) -> Result<Option<Contract>, Error> {
+        //! the accepted contract to work on, accepting or negotiating one if
+        //! there isn't one already
+        let now = (self.clock)();
+        let contracts = ship.api.list_contracts().await?;
+        let open: Vec<_> = contracts
+            .into_iter()
+            .filter(|contract| {
+                matches!(contract.kind, ContractType::Procurement)
+                    && !contract.fulfilled
+                    && contract.terms.deadline > now
+            })
+            .collect();
+        if let Some(contract) = open.iter().find(|contract| contract.accepted) {
+            return Ok(Some(contract.clone()));
+        }
+        let mut offered: Vec<Contract> = open
+            .into_iter()
+            .filter(|contract| contract.expiration > now)
+            .collect();
+        if offered.is_empty() {
+            ship.dock().await?;
+            match ship.api.negotiate_contract(&ship.symbol).await {
+                Ok(negotiated) => offered.push(negotiated.contract),
+                // already has one, or nobody here will offer one
+                Err(error) if matches!(get_error_code(&error), Some(4511 | 4500)) => {
+                    return Ok(None)
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        // only one this ship can source and deliver in time, at a profit
+        let here = ship.current().await?;
+        let system = ship.api.get_system(&here.nav.system_symbol).await?;
+        let fleet = [here];
+        let mut evaluator = ContractEvaluator::new(&system, &fleet);
+        if let Some(history) = ship.api.price_history() {
+            evaluator.add_prices(history.latest_in_system(&system.symbol)?);
+        }
+        if let Some(credits) = ship.api.agent_state().credits() {
+            evaluator.set_credits(credits);
+        }
+        for contract in offered {
+            let evaluation = evaluator.evaluate_at(&contract, now);
+            (self.log)(&evaluation);
+            if evaluation.go {
+                return Ok(Some(ship.api.accept_contract(&contract.id).await?.contract));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn clear_hold(
+        &self,
+        ship: &ShipContext,
+        here: &Ship,
+        keep: &[TradeSymbol],
+    ) -> Result<(), Error> {
+        //! sells what isn't in keep to the market here, if there is one,
+        //! and jettisons whatever it won't take
+        let waypoints = waypoints(ship, here).await?;
+        let market_here = waypoints.iter().any(|waypoint| {
+            waypoint.symbol == here.nav.waypoint_symbol
+                && has_trait(waypoint, WaypointTraitSymbol::Marketplace)
+        });
+        if market_here {
+            sell_here(ship, keep).await?;
+        }
+        let here = ship.current().await?;
+        for item in here.cargo.inventory {
+            if !keep.contains(&item.symbol) {
+                ship.api
+                    .jettison_cargo(&ship.symbol, item.symbol, item.units)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn next(&self, ship: &ShipContext) -> Result<Step, Error> {
+        let mut contract = match self.contract(ship).await? {
+            Some(contract) => contract,
+            None => return Ok(Step::Wait(IDLE)),
+        };
+        loop {
+            let good = contract
+                .terms
+                .deliver
+                .iter()
+                .find(|good| good.units_fulfilled < good.units_required);
+            let good = match good {
+                Some(good) => good.clone(),
+                None => {
+                    ship.api.fulfill_contract(&contract.id).await?;
+                    contract = match self.contract(ship).await? {
+                        Some(contract) => contract,
+                        None => return Ok(Step::Wait(IDLE)),
+                    };
+                    continue;
+                }
+            };
+            let here = ship.current().await?;
+            let wanted = (good.units_required - good.units_fulfilled) as u32;
+            let held = units_of(&here, &good.trade_symbol);
+
+            // a full hold goes in now, and the rest on a later trip
+            if held >= wanted || (held > 0 && is_full(&here)) {
+                if good.destination_symbol != here.nav.waypoint_symbol {
+                    return Ok(Step::NavigateTo(good.destination_symbol.clone()));
+                }
+                ship.dock().await?;
+                let units = held.min(wanted);
+                contract = ship
+                    .api
+                    .deliver_contract(&contract.id, &ship.symbol, good.trade_symbol.clone(), units)
+                    .await?
+                    .contract;
+                continue;
+            }
+
+            let keep: Vec<TradeSymbol> = contract
+                .terms
+                .deliver
+                .iter()
+                .map(|good| good.trade_symbol.clone())
+                .collect();
+            if here
+                .cargo
+                .inventory
+                .iter()
+                .any(|item| !keep.contains(&item.symbol))
+            {
+                self.clear_hold(ship, &here, &keep).await?;
+                continue;
+            }
+
+            let history = ship
+                .api
+                .price_history()
+                .ok_or("the contract runner needs a client with a price history")?;
+            let source = history.best_buy(&here.nav.system_symbol, good.trade_symbol.clone())?;
+            if let Some(source) = source {
+                if source.waypoint_symbol != here.nav.waypoint_symbol {
+                    return Ok(Step::NavigateTo(source.waypoint_symbol));
+                }
+                if buy_here(ship, good.trade_symbol.clone(), wanted - held).await? == 0 {
+                    return Err(format!("couldn't buy any {} here", good.trade_symbol).into());
+                }
+                refuel_here(ship).await?;
+                continue;
+            }
+            if !ShipCapabilities::of(&here).can_mine {
+                return Err(format!(
+                    "no market we know of sells {}, and {} can't mine it",
+                    good.trade_symbol, ship.symbol
+                )
+                .into());
+            }
+
+            let waypoints = waypoints(ship, &here).await?;
+            let fields = waypoints
+                .iter()
+                .filter(|waypoint| matches!(waypoint.kind, WaypointType::AsteroidField));
+            let field = match by_distance(&here, fields).first() {
+                Some(field) => field.symbol.clone(),
+                None => {
+                    return Err(format!("no asteroid field in {}", here.nav.system_symbol).into())
+                }
+            };
+            if field != here.nav.waypoint_symbol {
+                return Ok(Step::NavigateTo(field));
+            }
+            ship.orbit().await?;
+            let now = (self.clock)();
+            let survey = self
+                .surveys
+                .as_ref()
+                .and_then(|pool| pool.best(&field, now));
+            let cargo = match ship
+                .api
+                .extract_resources(&ship.symbol, survey.as_ref())
+                .await
+            {
+                Ok(extracted) => extracted.cargo,
+                Err(error) if get_error_code(&error) == Some(4000) => return Ok(Step::Cooldown),
+                Err(error) if matches!(get_error_code(&error), Some(4221 | 4224)) => {
+                    match (&self.surveys, survey) {
+                        (Some(pool), Some(survey)) => pool.remove(&survey.signature),
+                        _ => return Err(error),
+                    };
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            // keep the hold for what the contract wants
+            for item in cargo.inventory {
+                if item.symbol != good.trade_symbol {
+                    ship.api
+                        .jettison_cargo(&ship.symbol, item.symbol, item.units)
+                        .await?;
+                }
+            }
+            return Ok(Step::Cooldown);
+        }
+    }
+}
+
+impl ShipBehavior for ContractRunner {
+    fn on_start<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>> {
+        Box::pin(self.next(ship))
+    }
+}
@@ -0,0 +1,165 @@
+/*
+The explorer charts every uncharted waypoint it can reach and records
+what it finds in a Galaxy.
+
+In each system it plans a tour of the waypoints still marked
+UNCHARTED, nearest first and then the nearest to that, and charts
+each one as it gets there. The server saying a waypoint's already
+charted is as good as charting it ourselves. Once the system's done
+it looks through the system's jump gates and jumps towards the
+closest system, by number of jumps, that we've never seen or still
+has something uncharted. With nowhere left to go, it's done.
+*/
+
+use super::{by_distance, has_trait, refuel_here, waypoints, ShipBehavior, Step};
+use crate::api::results::get_error_code;
+use crate::api::schema::{
+    Ship, ShipSymbol, Waypoint, WaypointSymbol, WaypointTraitSymbol, WaypointType,
+};
+use crate::api::transport::BoxFuture;
+use crate::api::Error;
+use crate::fleet::ShipContext;
+use crate::galaxy::Galaxy;
+use crate::navigation;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub struct Explorer {
+    galaxy: Arc<Galaxy>,
+    tours: Mutex<HashMap<ShipSymbol, Vec<WaypointSymbol>>>,
+}
+
+impl Explorer {
+    pub fn new(galaxy: Arc<Galaxy>) -> Self {
+        Self {
+            galaxy,
+            tours: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn chart(&self, ship: &ShipContext, waypoint: &Waypoint) -> Result<(), Error> {
+        match ship.api.create_chart(&ship.symbol).await {
+            Ok(charted) => self.galaxy.record_waypoint(&charted.waypoint),
+            // somebody got there first, which does just as well
+            Err(error) if get_error_code(&error) == Some(4230) => {
+                let mut waypoint = waypoint.clone();
+                waypoint
+                    .traits
+                    .retain(|t| t.symbol != WaypointTraitSymbol::Uncharted);
+                self.galaxy.record_waypoint(&waypoint)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    fn next_stop(
+        &self,
+        ship: &ShipContext,
+        here: &Ship,
+        uncharted: &[Waypoint],
+    ) -> Option<WaypointSymbol> {
+        //! where the ship's tour goes next, planning a new one once the last
+        //! is done or the ship's moved on to another system
+        let mut tours = self.tours.lock().unwrap();
+        let tour = tours.entry(ship.symbol.clone()).or_default();
+        tour.retain(|stop| uncharted.iter().any(|waypoint| waypoint.symbol == *stop));
+        if tour.is_empty() {
+            *tour = plan_tour(here, uncharted);
+        }
+        tour.first().cloned()
+    }
+
+    async fn record_jump_gates(
+        &self,
+        ship: &ShipContext,
+        waypoints: &[Waypoint],
+    ) -> Result<(), Error> {
+        let gates = waypoints
+            .iter()
+            .filter(|waypoint| matches!(waypoint.kind, WaypointType::JumpGate));
+        for gate in gates {
+            if self.galaxy.jump_gate(&gate.symbol)?.is_none() {
+                let jump_gate = ship.api.get_jump_gate(&gate.symbol).await?;
+                self.galaxy.record_jump_gate(&gate.symbol, &jump_gate)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn next(&self, ship: &ShipContext) -> Result<Step, Error> {
+        loop {
+            let here = ship.current().await?;
+            let system = &here.nav.system_symbol;
+            let waypoints = waypoints(ship, &here).await?;
+            self.galaxy.record_waypoints(&waypoints)?;
+
+            let uncharted = self.galaxy.uncharted_in_system(system)?;
+            let at = &here.nav.waypoint_symbol;
+            if let Some(waypoint) = uncharted.iter().find(|waypoint| waypoint.symbol == *at) {
+                self.chart(ship, waypoint).await?;
+                continue;
+            }
+            let at_market = waypoints.iter().any(|waypoint| {
+                waypoint.symbol == *at && has_trait(waypoint, WaypointTraitSymbol::Marketplace)
+            });
+            if at_market {
+                refuel_here(ship).await?;
+            }
+            if let Some(stop) = self.next_stop(ship, &here, &uncharted) {
+                return Ok(Step::NavigateTo(stop));
+            }
+
+            // all charted here, so it's on to the next system
+            self.record_jump_gates(ship, &waypoints).await?;
+            let known = self.galaxy.systems()?;
+            let unfinished = self.galaxy.uncharted_systems()?;
+            let route = self.galaxy.jump_route(system, |system| {
+                !known.contains(system) || unfinished.contains(system)
+            })?;
+            let next = match route.and_then(|route| route.into_iter().next()) {
+                Some(next) => next,
+                None => return Ok(Step::Done),
+            };
+            let mut gates = Vec::new();
+            for waypoint in &waypoints {
+                if let Some(gate) = self.galaxy.jump_gate(&waypoint.symbol)? {
+                    if gate.connected_systems.iter().any(|to| to.symbol == next) {
+                        gates.push(waypoint);
+                    }
+                }
+            }
+            let gate = match by_distance(&here, gates).first() {
+                Some(gate) => gate.symbol.clone(),
+                None => return Err(format!("no gate in {system} goes to {next}").into()),
+            };
+            if gate != *at {
+                return Ok(Step::NavigateTo(gate));
+            }
+            return Ok(Step::JumpTo(next));
+        }
+    }
+}
+
+fn plan_tour(here: &Ship, stops: &[Waypoint]) -> Vec<WaypointSymbol> {
+    //! the nearest stop first, then the nearest to that, and so on
+    let from = &here.nav.route.destination;
+    let mut at = (from.x, from.y);
+    let mut left: Vec<&Waypoint> = stops.iter().collect();
+    let mut tour = Vec::new();
+    while !left.is_empty() {
+        let distance = |waypoint: &Waypoint| navigation::distance(at, (waypoint.x, waypoint.y));
+        let nearest = (0..left.len())
+            .min_by(|&a, &b| distance(left[a]).total_cmp(&distance(left[b])))
+            .unwrap();
+        let stop = left.swap_remove(nearest);
+        at = (stop.x, stop.y);
+        tour.push(stop.symbol.clone());
+    }
+    tour
+}
+
+impl ShipBehavior for Explorer {
+    fn on_start<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>> {
+        Box::pin(self.next(ship))
+    }
+}
@@ -0,0 +1,104 @@
+/*
+The hauler trades: it takes the best route trading::RouteFinder knows
+of in its system, buys a hold full at one end, takes it to the other,
+and goes again.
+
+Everything it knows about prices comes from the client's PriceHistory,
+so it needs one, and something (a MarketScout, say) keeping it fresh.
+Whatever it finds in the hold when it starts gets sold first, wherever
+pays the most for it.
+*/
+
+use super::{buy_here, refuel_here, sell_here, waypoints, ShipBehavior, Step, IDLE};
+use crate::api::transport::BoxFuture;
+use crate::api::Error;
+use crate::fleet::ShipContext;
+use crate::trading::{RouteFinder, RouteRanking, TradeShip};
+
+pub struct Hauler {
+    pub ranking: RouteRanking,
+    /// see TradeShip::max_transactions; the first trade_volume is the
+    /// only one priced at what the history says
+    pub max_transactions: Option<u32>,
+}
+
+impl Default for Hauler {
+    fn default() -> Self { Self::new() }
+}
+
+impl Hauler {
+    pub fn new() -> Self {
+        Self {
+            ranking: RouteRanking::ProfitPerSecond,
+            max_transactions: Some(1),
+        }
+    }
+
+    async fn next(&self, ship: &ShipContext) -> Result<Step, Error> {
+        let history = ship
+            .api
+            .price_history()
+            .ok_or("the hauler needs a client with a price history")?;
+        loop {
+            let here = ship.current().await?;
+            let system = &here.nav.system_symbol;
+
+            // sell what we've got where it's worth the most
+            let mut best = None;
+            for item in &here.cargo.inventory {
+                if let Some(record) = history.best_sell(system, item.symbol.clone())? {
+                    let value = record.sell_price as i64 * item.units as i64;
+                    if best.as_ref().is_none_or(|(best, _)| value > *best) {
+                        best = Some((value, record));
+                    }
+                }
+            }
+            if let Some((_, record)) = best {
+                if record.waypoint_symbol != here.nav.waypoint_symbol {
+                    return Ok(Step::NavigateTo(record.waypoint_symbol));
+                }
+                let units = here.cargo.units;
+                sell_here(ship, &[]).await?;
+                refuel_here(ship).await?;
+                if ship.current().await?.cargo.units == units {
+                    return Err(
+                        format!("{} wouldn't buy anything", here.nav.waypoint_symbol).into(),
+                    );
+                }
+                continue;
+            } else if !here.cargo.inventory.is_empty() {
+                return Err(format!("nowhere known in {system} buys what's in the hold").into());
+            }
+
+            let mut finder = RouteFinder::default();
+            for waypoint in waypoints(ship, &here).await? {
+                finder.add_waypoint(&waypoint.symbol, waypoint.x, waypoint.y);
+            }
+            finder.add_prices(history.latest_in_system(system)?);
+            let trader = TradeShip {
+                max_transactions: self.max_transactions,
+                ..TradeShip::from_ship(&here)
+            };
+            let routes = finder.routes(&trader, self.ranking);
+            let route = match routes.first() {
+                Some(route) => route,
+                None => return Ok(Step::Wait(IDLE)),
+            };
+            if route.buy_at != here.nav.waypoint_symbol {
+                return Ok(Step::NavigateTo(route.buy_at.clone()));
+            }
+            let bought = buy_here(ship, route.trade_symbol.clone(), route.units_per_trip).await?;
+            if bought == 0 {
+                return Err(format!("couldn't buy any {} here", route.trade_symbol).into());
+            }
+            refuel_here(ship).await?;
+            return Ok(Step::NavigateTo(route.sell_at.clone()));
+        }
+    }
+}
+
+impl ShipBehavior for Hauler {
+    fn on_start<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>> {
+        Box::pin(self.next(ship))
+    }
+}
@@ -0,0 +1,328 @@
+/*
+The miner extracts at the nearest asteroid field until its hold is
+full, using the best survey in the pool if there's one that hasn't
+expired, then sells at the nearest market that takes what it's
+carrying, tops up its fuel and heads back out. Expiry is by the
+server's clock, which it takes on trust to be ours unless it's given
+another.
+
+Whatever it extracts that isn't worth the room gets jettisoned on the
+spot: anything no market in the system buys, and anything the nearest
+market that does pays less than min_sell_price for right now. Markets
+only show prices to ships that are there, so with none of ours at that
+market the good is kept. If the market it went to sell at won't take
+anything after all, it gives up rather than go round again. Each trip
+out and back is a MiningCycle, handed to the logger once the hold's
+been sold, with the crew's wages for the trip charged against it.
+*/
+
+use super::{
+    by_distance, is_full, nearest_buyer, refuel_here, sell_here, waypoints, ShipBehavior, Step,
+    SurveyPool,
+};
+use crate::api::results::get_error_code;
+use crate::api::schema::{Ship, ShipCrew, ShipSymbol, TradeSymbol, Waypoint, WaypointType};
+use crate::api::transport::BoxFuture;
+use crate::api::Error;
+use crate::crew;
+use crate::fleet::ShipContext;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// one trip from the market out to the field and back again
+#[derive(Clone, Debug, PartialEq)]
+pub struct MiningCycle {
+    pub ship_symbol: ShipSymbol,
+    pub extractions: u32,
+    /// units extracted of each good
+    pub extracted: BTreeMap<TradeSymbol, u32>,
+    pub jettisoned: BTreeMap<TradeSymbol, u32>,
+    /// what the hold sold for
+    pub revenue: i64,
+    pub fuel_cost: i64,
+    /// the crew's wages for as long as the cycle took
+    pub crew_cost: i64,
+    /// from the first extraction until it was sold and refueled
+    pub duration: Duration,
+}
+
+impl MiningCycle {
+    pub fn units(&self) -> u32 { self.extracted.values().sum() }
+
+    pub fn profit(&self) -> i64 { self.revenue - self.fuel_cost - self.crew_cost }
+}
+
+impl fmt::Display for MiningCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let goods = |goods: &BTreeMap<TradeSymbol, u32>| {
+            let goods: Vec<String> = goods
+                .iter()
+                .map(|(symbol, units)| format!("{units} {symbol}"))
+                .collect();
+            goods.join(", ")
+        };
+        write!(
+            f,
+            "{} mined {} units in {} extractions ({})",
+            self.ship_symbol,
+            self.units(),
+            self.extractions,
+            goods(&self.extracted)
+        )?;
+        if !self.jettisoned.is_empty() {
+            write!(f, ", jettisoned {}", goods(&self.jettisoned))?;
+        }
+        write!(
+            f,
+            ", sold for {} less {} fuel and {} crew in {}s",
+            self.revenue,
+            self.fuel_cost,
+            self.crew_cost,
+            self.duration.as_secs()
+        )
+    }
+}
+
+/// a cycle in progress, and what's been decided about keeping each good
+struct Cycle {
+    report: MiningCycle,
+    started: Instant,
+    worth_keeping: HashMap<TradeSymbol, bool>,
+}
+
+pub struct Miner {
+    surveys: Arc<SurveyPool>,
+    clock: Box<dyn Fn() -> DateTime<Utc> + Send + Sync>,
+    /// goods the best known market pays less than this for get jettisoned
+    pub min_sell_price: u32,
+    cycles: Mutex<HashMap<ShipSymbol, Cycle>>,
+    log: Box<dyn Fn(&MiningCycle) + Send + Sync>,
+}
+
+impl Miner {
+    pub fn new(surveys: Arc<SurveyPool>) -> Self {
+        Self {
+            surveys,
+            clock: Box::new(Utc::now),
+            min_sell_price: 1,
+            cycles: Mutex::new(HashMap::new()),
+            log: Box::new(|_| {}),
+        }
+    }
+
+    pub fn with_min_sell_price(mut self, min_sell_price: u32) -> Self {
+        self.min_sell_price = min_sell_price;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: impl Fn() -> DateTime<Utc> + Send + Sync + 'static) -> Self {
+        //! what the server thinks the time is, for telling which surveys
+        //! have expired
+        self.clock = Box::new(clock);
+        self
+    }
+
+    pub fn with_logger(mut self, log: impl Fn(&MiningCycle) + Send + Sync + 'static) -> Self {
+        //! called with every finished cycle, which otherwise go unreported
+        self.log = Box::new(log);
+        self
+    }
+
+    pub fn with_stderr_log(self) -> Self {
+        //! prints every finished cycle to stderr
+        self.with_logger(|cycle| eprintln!("{cycle}"))
+    }
+
+    fn cycle<T>(&self, ship: &ShipSymbol, f: impl FnOnce(&mut Cycle) -> T) -> T {
+        let mut cycles = self.cycles.lock().unwrap();
+        let cycle = cycles.entry(ship.clone()).or_insert_with(|| Cycle {
+            report: MiningCycle {
+                ship_symbol: ship.clone(),
+                extractions: 0,
+                extracted: BTreeMap::new(),
+                jettisoned: BTreeMap::new(),
+                revenue: 0,
+                fuel_cost: 0,
+                crew_cost: 0,
+                duration: Duration::ZERO,
+            },
+            started: Instant::now(),
+            worth_keeping: HashMap::new(),
+        });
+        f(cycle)
+    }
+
+    fn finish(&self, ship: &ShipSymbol, crew: &ShipCrew) {
+        let cycle = self.cycles.lock().unwrap().remove(ship);
+        if let Some(Cycle {
+            mut report,
+            started,
+            ..
+        }) = cycle
+        {
+            report.duration = started.elapsed();
+            report.crew_cost = crew::wage_cost(crew, report.duration);
+            (self.log)(&report);
+        }
+    }
+
+    async fn worth_keeping(
+        &self,
+        ship: &ShipContext,
+        here: &Ship,
+        waypoints: &[Waypoint],
+        symbol: &TradeSymbol,
+    ) -> Result<bool, Error> {
+        //! by what the nearest market that buys it pays right now, since
+        //! that's where it'd be sold. decided once a cycle
+        if let Some(keep) = self.cycle(&ship.symbol, |cycle| {
+            cycle.worth_keeping.get(symbol).copied()
+        }) {
+            return Ok(keep);
+        }
+        let keep = match nearest_buyer(ship, here, waypoints, std::slice::from_ref(symbol)).await? {
+            None => false,
+            Some(buyer) => {
+                let market = ship.api.refresh_market(&buyer.symbol).await?;
+                // a market only shows prices while one of our ships is there,
+                // and no price doesn't make it worthless, just unknown
+                match market
+                    .trade_goods
+                    .iter()
+                    .find(|good| good.symbol == *symbol)
+                {
+                    Some(good) => good.sell_price >= self.min_sell_price,
+                    None => true,
+                }
+            }
+        };
+        self.cycle(&ship.symbol, |cycle| {
+            cycle.worth_keeping.insert(symbol.clone(), keep)
+        });
+        Ok(keep)
+    }
+
+    async fn jettison(
+        &self,
+        ship: &ShipContext,
+        here: &Ship,
+        waypoints: &[Waypoint],
+    ) -> Result<(), Error> {
+        //! gets rid of whatever in the hold isn't worth selling
+        for item in &here.cargo.inventory {
+            if self
+                .worth_keeping(ship, here, waypoints, &item.symbol)
+                .await?
+            {
+                continue;
+            }
+            ship.api
+                .jettison_cargo(&ship.symbol, item.symbol.clone(), item.units)
+                .await?;
+            self.cycle(&ship.symbol, |cycle| {
+                *cycle
+                    .report
+                    .jettisoned
+                    .entry(item.symbol.clone())
+                    .or_default() += item.units;
+            });
+        }
+        Ok(())
+    }
+
+    async fn next(&self, ship: &ShipContext) -> Result<Step, Error> {
+        loop {
+            let here = ship.current().await?;
+            let waypoints = waypoints(ship, &here).await?;
+
+            if is_full(&here) {
+                let goods: Vec<TradeSymbol> = here
+                    .cargo
+                    .inventory
+                    .iter()
+                    .map(|item| item.symbol.clone())
+                    .collect();
+                let buyer = nearest_buyer(ship, &here, &waypoints, &goods)
+                    .await?
+                    .ok_or_else(|| {
+                        format!(
+                            "nothing in {} buys what's in the hold",
+                            here.nav.system_symbol
+                        )
+                    })?;
+                if buyer.symbol != here.nav.waypoint_symbol {
+                    return Ok(Step::NavigateTo(buyer.symbol.clone()));
+                }
+                let revenue = sell_here(ship, &[]).await?;
+                // what it went by was out of date, so going round again
+                // would only bring it straight back here
+                if revenue == 0 {
+                    return Err(format!(
+                        "{} wouldn't buy anything in the hold after all",
+                        buyer.symbol
+                    )
+                    .into());
+                }
+                let fuel_cost = refuel_here(ship).await?;
+                self.cycle(&ship.symbol, |cycle| {
+                    cycle.report.revenue += revenue;
+                    cycle.report.fuel_cost += fuel_cost;
+                });
+                self.finish(&ship.symbol, &here.crew);
+                continue;
+            }
+
+            let fields = waypoints
+                .iter()
+                .filter(|waypoint| matches!(waypoint.kind, WaypointType::AsteroidField));
+            let field = match by_distance(&here, fields).first() {
+                Some(field) => field.symbol.clone(),
+                None => {
+                    return Err(format!("no asteroid field in {}", here.nav.system_symbol).into())
+                }
+            };
+            if field != here.nav.waypoint_symbol {
+                return Ok(Step::NavigateTo(field));
+            }
+            ship.orbit().await?;
+            let survey = self.surveys.best(&field, (self.clock)());
+            match ship
+                .api
+                .extract_resources(&ship.symbol, survey.as_ref())
+                .await
+            {
+                Ok(extracted) => {
+                    let got = extracted.extraction.yield_amount;
+                    let mut here = here;
+                    here.cargo = extracted.cargo;
+                    self.cycle(&ship.symbol, |cycle| {
+                        cycle.report.extractions += 1;
+                        *cycle.report.extracted.entry(got.symbol).or_default() += got.units as u32;
+                    });
+                    self.jettison(ship, &here, &waypoints).await?;
+                    return Ok(Step::Cooldown);
+                }
+                // still cooling down from whatever it did before
+                Err(error) if get_error_code(&error) == Some(4000) => return Ok(Step::Cooldown),
+                // expired or used up, so try again without it
+                Err(error) if matches!(get_error_code(&error), Some(4221 | 4224)) => {
+                    match survey {
+                        Some(survey) => self.surveys.remove(&survey.signature),
+                        None => return Err(error),
+                    };
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl ShipBehavior for Miner {
+    fn on_start<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>> {
+        Box::pin(self.next(ship))
+    }
+}
@@ -0,0 +1,124 @@
+/*
+The market scout tours every marketplace and shipyard in its system,
+refreshing each one while it's there so the prices get recorded (given
+a client with a PriceHistory) and the cache holds the latest listings.
+It always heads for the one it saw longest ago, and once it has seen
+them all within revisit it waits.
+
+A scout can instead be parked at one waypoint for good, where it polls
+the market and shipyard every revisit. Probes are cheap, so a few
+parked at the busiest markets keep their prices live.
+*/
+
+use super::{by_distance, has_trait, waypoints, ShipBehavior, Step};
+use crate::api::schema::{Waypoint, WaypointSymbol, WaypointTraitSymbol};
+use crate::api::transport::BoxFuture;
+use crate::api::Error;
+use crate::fleet::ShipContext;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+pub struct MarketScout {
+    /// how old a market's prices can get before it's worth another visit
+    pub revisit: Duration,
+    /// stay at this waypoint rather than touring
+    pub park_at: Option<WaypointSymbol>,
+    visited: Mutex<HashMap<WaypointSymbol, Instant>>,
+}
+
+impl Default for MarketScout {
+    fn default() -> Self { Self::new() }
+}
+
+impl MarketScout {
+    pub fn new() -> Self {
+        Self {
+            revisit: Duration::from_secs(15 * 60),
+            park_at: None,
+            visited: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_revisit(mut self, revisit: Duration) -> Self {
+        self.revisit = revisit;
+        self
+    }
+
+    pub fn with_park_at(mut self, waypoint: WaypointSymbol) -> Self {
+        self.park_at = Some(waypoint);
+        self
+    }
+
+    fn due_in(&self, waypoint: &WaypointSymbol) -> Duration {
+        match self.visited.lock().unwrap().get(waypoint) {
+            Some(visited) => self.revisit.saturating_sub(visited.elapsed()),
+            None => Duration::ZERO,
+        }
+    }
+
+    async fn refresh(&self, ship: &ShipContext, waypoint: &Waypoint) -> Result<(), Error> {
+        if has_trait(waypoint, WaypointTraitSymbol::Marketplace) {
+            ship.api.refresh_market(&waypoint.symbol).await?;
+        }
+        if has_trait(waypoint, WaypointTraitSymbol::Shipyard) {
+            ship.api.refresh_shipyard(&waypoint.symbol).await?;
+        }
+        self.visited
+            .lock()
+            .unwrap()
+            .insert(waypoint.symbol.clone(), Instant::now());
+        Ok(())
+    }
+
+    async fn next(&self, ship: &ShipContext) -> Result<Step, Error> {
+        let here = ship.current().await?;
+        let at = &here.nav.waypoint_symbol;
+
+        if let Some(park_at) = &self.park_at {
+            if park_at != at {
+                return Ok(Step::NavigateTo(park_at.clone()));
+            }
+            if self.due_in(at).is_zero() {
+                let waypoint = ship.api.get_waypoint(at).await?;
+                self.refresh(ship, &waypoint).await?;
+            }
+            return Ok(Step::Wait(self.due_in(at)));
+        }
+
+        let waypoints = waypoints(ship, &here).await?;
+        let targets = waypoints.iter().filter(|waypoint| {
+            has_trait(waypoint, WaypointTraitSymbol::Marketplace)
+                || has_trait(waypoint, WaypointTraitSymbol::Shipyard)
+        });
+        let targets = by_distance(&here, targets);
+        if targets.is_empty() {
+            return Err(format!("no markets to scout in {}", here.nav.system_symbol).into());
+        }
+
+        if let Some(target) = targets.iter().find(|target| target.symbol == *at) {
+            if self.due_in(at).is_zero() {
+                self.refresh(ship, target).await?;
+            }
+        }
+
+        // the one due soonest, and the closest of those
+        let next = targets
+            .iter()
+            .min_by_key(|target| self.due_in(&target.symbol))
+            .unwrap();
+        let due_in = self.due_in(&next.symbol);
+        if !due_in.is_zero() {
+            Ok(Step::Wait(due_in))
+        } else {
+            Ok(Step::NavigateTo(next.symbol.clone()))
+        }
+    }
+}
+
+impl ShipBehavior for MarketScout {
+    fn on_start<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>> {
+        Box::pin(self.next(ship))
+    }
+}
@@ -0,0 +1,50 @@
+/*
+The surveyor sits at the nearest asteroid field surveying it over and
+over, and puts every survey in the pool it shares with the miners.
+*/
+
+use super::{by_distance, waypoints, ShipBehavior, Step, SurveyPool};
+use crate::api::results::get_error_code;
+use crate::api::schema::WaypointType;
+use crate::api::transport::BoxFuture;
+use crate::api::Error;
+use crate::fleet::ShipContext;
+use std::sync::Arc;
+
+pub struct Surveyor {
+    surveys: Arc<SurveyPool>,
+}
+
+impl Surveyor {
+    pub fn new(surveys: Arc<SurveyPool>) -> Self { Self { surveys } }
+
+    async fn next(&self, ship: &ShipContext) -> Result<Step, Error> {
+        let here = ship.current().await?;
+        let waypoints = waypoints(ship, &here).await?;
+        let fields = waypoints
+            .iter()
+            .filter(|waypoint| matches!(waypoint.kind, WaypointType::AsteroidField));
+        let field = match by_distance(&here, fields).first() {
+            Some(field) => field.symbol.clone(),
+            None => return Err(format!("no asteroid field in {}", here.nav.system_symbol).into()),
+        };
+        if field != here.nav.waypoint_symbol {
+            return Ok(Step::NavigateTo(field));
+        }
+        ship.orbit().await?;
+        match ship.api.create_survey(&ship.symbol).await {
+            Ok(created) => {
+                self.surveys.add(created.surveys);
+                Ok(Step::Cooldown)
+            }
+            Err(error) if get_error_code(&error) == Some(4000) => Ok(Step::Cooldown),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl ShipBehavior for Surveyor {
+    fn on_start<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>> {
+        Box::pin(self.next(ship))
+    }
+}
@@ -0,0 +1,190 @@
+/*
+The cache module keeps a local copy of the parts of the universe
+that barely change within a reset: systems, waypoints, markets,
+shipyards and jump gates.
+
+Entries are stored in SQLite as JSON, keyed by what they are, their
+symbol, and the reset date they were fetched in. Anything fetched
+in a previous reset is never returned.
+*/
+
+use crate::api::Error;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CacheKind {
+    System,
+    SystemList,
+    Waypoint,
+    WaypointList,
+    Market,
+    Shipyard,
+    JumpGate,
+}
+
+impl CacheKind {
+    fn name(&self) -> &'static str {
+        match self {
+            CacheKind::System => "system",
+            CacheKind::SystemList => "system_list",
+            CacheKind::Waypoint => "waypoint",
+            CacheKind::WaypointList => "waypoint_list",
+            CacheKind::Market => "market",
+            CacheKind::Shipyard => "shipyard",
+            CacheKind::JumpGate => "jump_gate",
+        }
+    }
+
+    pub fn default_ttl(&self) -> Option<Duration> {
+        //! None means the entry stays fresh for the rest of the reset.
+        //! markets and shipyards have prices and stock that move, so
+        //! they expire on their own
+        match self {
+            CacheKind::Market => Some(Duration::minutes(15)),
+            CacheKind::Shipyard => Some(Duration::hours(1)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CacheEntry<T> {
+    pub value: T,
+    pub fetched_at: DateTime<Utc>,
+}
+
+pub struct Cache {
+    conn: Mutex<Connection>,
+    reset_date: String,
+    ttls: HashMap<CacheKind, Option<Duration>>,
+}
+
+impl Cache {
+    pub fn open(path: impl AsRef<Path>, reset_date: &str) -> Result<Self, Error> {
+        Self::init(Connection::open(path)?, reset_date)
+    }
+
+    pub fn open_in_memory(reset_date: &str) -> Result<Self, Error> {
+        Self::init(Connection::open_in_memory()?, reset_date)
+    }
+
+    fn init(conn: Connection, reset_date: &str) -> Result<Self, Error> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS entries (
+                kind TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                reset_date TEXT NOT NULL,
+                fetched_at TEXT NOT NULL,
+                data TEXT NOT NULL,
+                PRIMARY KEY (kind, symbol, reset_date)
+            );",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            reset_date: reset_date.to_owned(),
+            ttls: HashMap::new(),
+        })
+    }
+
+    pub fn reset_date(&self) -> &str { &self.reset_date }
+
+    pub fn ttl(&self, kind: CacheKind) -> Option<Duration> {
+        match self.ttls.get(&kind) {
+            Some(ttl) => *ttl,
+            None => kind.default_ttl(),
+        }
+    }
+
+    pub fn set_ttl(&mut self, kind: CacheKind, ttl: Option<Duration>) {
+        self.ttls.insert(kind, ttl);
+    }
+
+    pub fn get<T: DeserializeOwned>(
+        &self,
+        kind: CacheKind,
+        symbol: &str,
+    ) -> Result<Option<T>, Error> {
+        //! returns the entry only if it hasn't outlived its ttl
+        let entry = match self.get_entry::<T>(kind, symbol)? {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        let fresh = match self.ttl(kind) {
+            Some(ttl) => entry.fetched_at + ttl > Utc::now(),
+            None => true,
+        };
+        Ok(if fresh { Some(entry.value) } else { None })
+    }
+
+    pub fn get_entry<T: DeserializeOwned>(
+        &self,
+        kind: CacheKind,
+        symbol: &str,
+    ) -> Result<Option<CacheEntry<T>>, Error> {
+        //! returns the entry for this reset whether it's stale or not
+        let conn = self.conn.lock().unwrap();
+        let row: Option<(String, String)> = conn
+            .query_row(
+                "SELECT fetched_at, data FROM entries
+                WHERE kind = ?1 AND symbol = ?2 AND reset_date = ?3",
+                params![kind.name(), symbol, self.reset_date],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        match row {
+            Some((fetched_at, data)) => Ok(Some(CacheEntry {
+                value: serde_json::from_str(&data)?,
+                fetched_at: DateTime::parse_from_rfc3339(&fetched_at)?.into(),
+            })),
+            None => Ok(None),
+        }
+    }
+
+    pub fn put<T: Serialize>(&self, kind: CacheKind, symbol: &str, value: &T) -> Result<(), Error> {
+        self.put_at(kind, symbol, value, Utc::now())
+    }
+
+    pub(crate) fn put_at<T: Serialize>(
+        &self,
+        kind: CacheKind,
+        symbol: &str,
+        value: &T,
+        fetched_at: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        let data = serde_json::to_string(value)?;
+        self.conn.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO entries (kind, symbol, reset_date, fetched_at, data)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                kind.name(),
+                symbol,
+                self.reset_date,
+                fetched_at.to_rfc3339(),
+                data
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn invalidate(&self, kind: CacheKind, symbol: &str) -> Result<(), Error> {
+        self.conn.lock().unwrap().execute(
+            "DELETE FROM entries WHERE kind = ?1 AND symbol = ?2 AND reset_date = ?3",
+            params![kind.name(), symbol, self.reset_date],
+        )?;
+        Ok(())
+    }
+
+    pub fn prune_old_resets(&self) -> Result<usize, Error> {
+        //! deletes everything fetched in a reset other than the current one
+        let deleted = self.conn.lock().unwrap().execute(
+            "DELETE FROM entries WHERE reset_date != ?1",
+            params![self.reset_date],
+        )?;
+        Ok(deleted)
+    }
+}
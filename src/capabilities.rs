@@ -0,0 +1,143 @@
+/*
+The capabilities module says what a ship can do, going by what's
+fitted to it.
+
+What a ship is good for is spread over its modules and mounts, and
+otherwise every behavior and planner would have to know which of the
+symbols are mining lasers, which are siphons, which modules hold
+cargo and so on. ShipCapabilities works it all out once, from a ship
+we own or one a shipyard's selling, and everything else asks it.
+*/
+
+use crate::api::schema::{
+    Deposits, Ship, ShipModule, ShipModuleSymbols, ShipMount, ShipMountSymbols, ShipyardShip,
+    TradeSymbol,
+};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Refinery {
+    Micro,
+    Ore,
+    Fuel,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShipCapabilities {
+    pub can_mine: bool,
+    pub can_siphon: bool,
+    pub can_survey: bool,
+    /// has a jump drive, so it doesn't need a gate
+    pub can_jump: bool,
+    pub can_warp: bool,
+    pub refineries: Vec<Refinery>,
+    /// the mining lasers' strengths added up
+    pub mining_strength: u32,
+    pub siphon_strength: u32,
+    pub survey_strength: u32,
+    /// each surveyor makes its own survey
+    pub surveyors: u32,
+    /// the sensor arrays' strengths added up
+    pub scan_strength: u32,
+    /// every module's capacity, cargo holds or not
+    pub module_capacity: u32,
+    pub cargo_capacity: u32,
+    /// the longest jump or warp any of its drives can make
+    pub jump_range: u32,
+    pub warp_range: u32,
+    /// what the lasers can pull out of an asteroid, and the strength of
+    /// the lasers that can do it
+    pub deposits: BTreeMap<Deposits, u32>,
+}
+
+impl ShipCapabilities {
+    pub fn of(ship: &Ship) -> Self { Self::from_parts(&ship.modules, &ship.mounts) }
+
+    pub fn of_listing(ship: &ShipyardShip) -> Self {
+        //! what a ship for sale would be able to do once it's ours
+        Self::from_parts(&ship.modules, &ship.mounts)
+    }
+
+    pub fn from_parts(modules: &[ShipModule], mounts: &[ShipMount]) -> Self {
+        let mut capabilities = Self::default();
+        for module in modules {
+            let capacity = module.capacity.unwrap_or(0);
+            let range = module.range.unwrap_or(0);
+            capabilities.module_capacity += capacity;
+            match module.symbol {
+                ShipModuleSymbols::ModuleCargoHoldI => capabilities.cargo_capacity += capacity,
+                ShipModuleSymbols::ModuleMicroRefineryI => {
+                    capabilities.refineries.push(Refinery::Micro)
+                }
+                ShipModuleSymbols::ModuleOreRefineryI => {
+                    capabilities.refineries.push(Refinery::Ore)
+                }
+                ShipModuleSymbols::ModuleFuelRefineryI => {
+                    capabilities.refineries.push(Refinery::Fuel)
+                }
+                ShipModuleSymbols::ModuleJumpDriveI
+                | ShipModuleSymbols::ModuleJumpDriveIi
+                | ShipModuleSymbols::ModuleJumpDriveIii => {
+                    capabilities.can_jump = true;
+                    capabilities.jump_range = capabilities.jump_range.max(range);
+                }
+                ShipModuleSymbols::ModuleWarpDriveI
+                | ShipModuleSymbols::ModuleWarpDriveIi
+                | ShipModuleSymbols::ModuleWarpDriveIii => {
+                    capabilities.can_warp = true;
+                    capabilities.warp_range = capabilities.warp_range.max(range);
+                }
+                _ => {}
+            }
+        }
+        capabilities.refineries.sort();
+        capabilities.refineries.dedup();
+
+        for mount in mounts {
+            let strength = mount.strength.unwrap_or(0);
+            match mount.symbol {
+                ShipMountSymbols::MountMiningLaserI
+                | ShipMountSymbols::MountMiningLaserIi
+                | ShipMountSymbols::MountMiningLaserIii => {
+                    // a laser that doesn't say how strong it is still
+                    // pulls something out
+                    let strength = mount.strength.unwrap_or(1);
+                    capabilities.can_mine = true;
+                    capabilities.mining_strength += strength;
+                    for deposit in &mount.deposits {
+                        *capabilities.deposits.entry(deposit.clone()).or_default() += strength;
+                    }
+                }
+                ShipMountSymbols::MountGasSiphonI
+                | ShipMountSymbols::MountGasSiphonIi
+                | ShipMountSymbols::MountGasSiphonIii => {
+                    capabilities.can_siphon = true;
+                    capabilities.siphon_strength += strength;
+                }
+                ShipMountSymbols::MountSurveyorI
+                | ShipMountSymbols::MountSurveyorIi
+                | ShipMountSymbols::MountSurveyorIii => {
+                    capabilities.can_survey = true;
+                    capabilities.survey_strength += strength;
+                    capabilities.surveyors += 1;
+                }
+                ShipMountSymbols::MountSensorArrayI
+                | ShipMountSymbols::MountSensorArrayIi
+                | ShipMountSymbols::MountSensorArrayIii => capabilities.scan_strength += strength,
+                _ => {}
+            }
+        }
+        capabilities
+    }
+
+    pub fn can_refine(&self) -> bool { !self.refineries.is_empty() }
+
+    pub fn mining_strength_for(&self, trade_symbol: TradeSymbol) -> u32 {
+        //! the strength of the lasers that can mine trade_symbol, if any can
+        self.deposits
+            .iter()
+            .filter(|(deposit, _)| deposit.trade_symbol() == trade_symbol)
+            .map(|(_, strength)| *strength)
+            .sum()
+    }
+}
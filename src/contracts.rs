@@ -0,0 +1,413 @@
+/*
+The contracts module judges whether a procurement contract is worth
+taking before we accept it.
+
+For every good a contract wants delivered, the evaluator works out
+whether it's cheaper to buy it from a known market or mine it, how
+long fetching and delivering it would take with each of our ships,
+and whether that leaves any profit before the deadline, once the
+ship's crew have been paid for the time it takes. The result
+carries its reasoning so a bot can log why it took a contract or not.
+*/
+
+use crate::api::schema;
+use crate::capabilities::ShipCapabilities;
+use crate::crew;
+use crate::history::PriceRecord;
+use crate::navigation;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Acquisition {
+    Buy {
+        waypoint_symbol: schema::WaypointSymbol,
+        price_per_unit: u32,
+    },
+    Mine {
+        waypoint_symbol: schema::WaypointSymbol,
+    },
+}
+
+#[derive(Debug)]
+pub struct DeliveryPlan {
+    pub trade_symbol: schema::TradeSymbol,
+    pub destination_symbol: schema::WaypointSymbol,
+    pub units: u32,
+    pub acquisition: Acquisition,
+    pub cost: i64,
+    /// the crew's wages for as long as it takes
+    pub crew_cost: i64,
+    pub time: Duration,
+}
+
+#[derive(Debug)]
+pub struct Evaluation {
+    pub contract_id: String,
+    /// the ship the estimate was made for, if any could do the job
+    pub ship_symbol: Option<schema::ShipSymbol>,
+    pub plans: Vec<DeliveryPlan>,
+    pub acquisition_cost: i64,
+    pub crew_cost: i64,
+    pub payment: i64,
+    pub net_profit: i64,
+    pub estimated_time: Duration,
+    pub time_left: Duration,
+    pub go: bool,
+    pub reasons: Vec<String>,
+}
+
+impl fmt::Display for Evaluation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.go { "GO" } else { "NO GO" };
+        write!(f, "contract {}: {}", self.contract_id, verdict)?;
+        for reason in &self.reasons {
+            write!(f, "\n  - {}", reason)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct ContractEvaluator<'a> {
+    positions: HashMap<schema::WaypointSymbol, (i32, i32)>,
+    asteroid_fields: Vec<schema::WaypointSymbol>,
+    prices: Vec<PriceRecord>,
+    fleet: &'a [schema::Ship],
+    credits: Option<i64>,
+    /// how much time has to be left over before the deadline
+    pub safety_margin: Duration,
+    pub extraction_cooldown: Duration,
+    /// the share of extracted units expected to be the good we're after
+    pub mining_hit_rate: f64,
+}
+
+impl<'a> ContractEvaluator<'a> {
+    pub fn new(system: &schema::System, fleet: &'a [schema::Ship]) -> Self {
+        let mut evaluator = Self {
+            positions: HashMap::new(),
+            asteroid_fields: Vec::new(),
+            prices: Vec::new(),
+            fleet,
+            credits: None,
+            safety_margin: Duration::hours(1),
+            extraction_cooldown: Duration::seconds(70),
+            mining_hit_rate: 0.3,
+        };
+        for waypoint in &system.waypoints {
+            evaluator.add_waypoint(&waypoint.symbol, waypoint.x, waypoint.y);
+            if matches!(waypoint.kind, schema::WaypointType::AsteroidField) {
+                evaluator.asteroid_fields.push(waypoint.symbol.clone());
+            }
+        }
+        evaluator
+    }
+
+    pub fn add_waypoint(&mut self, symbol: &schema::WaypointSymbol, x: i32, y: i32) {
+        self.positions.insert(symbol.clone(), (x, y));
+    }
+
+    pub fn add_asteroid_field(&mut self, symbol: &schema::WaypointSymbol, x: i32, y: i32) {
+        self.add_waypoint(symbol, x, y);
+        self.asteroid_fields.push(symbol.clone());
+    }
+
+    pub fn add_market(&mut self, market: &schema::Market) {
+        self.prices
+            .extend(PriceRecord::from_market(market, Utc::now()));
+    }
+
+    pub fn add_prices(&mut self, prices: impl IntoIterator<Item = PriceRecord>) {
+        self.prices.extend(prices);
+    }
+
+    pub fn set_credits(&mut self, credits: i64) {
+        //! lets the evaluator turn down contracts we can't afford to source
+        self.credits = Some(credits);
+    }
+
+    pub fn evaluate(&self, contract: &schema::Contract) -> Evaluation {
+        self.evaluate_at(contract, Utc::now())
+    }
+
+    pub fn evaluate_at(&self, contract: &schema::Contract, now: DateTime<Utc>) -> Evaluation {
+        // once it's accepted, the advance is already ours and spent or not,
+        // so only what's paid on fulfilling it is still to come
+        let on_accepted = match contract.accepted {
+            true => 0,
+            false => contract.terms.payment.on_accepted as i64,
+        };
+        let payment = on_accepted + contract.terms.payment.on_fulfilled as i64;
+        let time_left = contract.terms.deadline - now;
+        let mut evaluation = Evaluation {
+            contract_id: contract.id.clone(),
+            ship_symbol: None,
+            plans: Vec::new(),
+            acquisition_cost: 0,
+            crew_cost: 0,
+            payment,
+            net_profit: 0,
+            estimated_time: Duration::zero(),
+            time_left,
+            go: false,
+            reasons: Vec::new(),
+        };
+
+        if !matches!(contract.kind, schema::ContractType::Procurement) {
+            evaluation
+                .reasons
+                .push("only procurement contracts can be evaluated".to_owned());
+            return evaluation;
+        }
+        if contract.fulfilled {
+            evaluation
+                .reasons
+                .push("contract is already fulfilled".to_owned());
+            return evaluation;
+        }
+        if time_left <= Duration::zero() {
+            evaluation
+                .reasons
+                .push("deadline has already passed".to_owned());
+            return evaluation;
+        }
+
+        // the ship that can do the whole contract for the most profit wins,
+        // and failing that, the one that gets it done the fastest
+        let mut best: Option<(&schema::Ship, Vec<DeliveryPlan>)> = None;
+        for ship in self.fleet {
+            let plans = match self.plan_for_ship(ship, contract, time_left) {
+                Some(plans) => plans,
+                None => continue,
+            };
+            let better = match &best {
+                None => true,
+                Some((_, best_plans)) => {
+                    let (cost, crew_cost, time) = totals(&plans);
+                    let (best_cost, best_crew_cost, best_time) = totals(best_plans);
+                    let (cost, best_cost) = (cost + crew_cost, best_cost + best_crew_cost);
+                    let fits = time + self.safety_margin <= time_left;
+                    let best_fits = best_time + self.safety_margin <= time_left;
+                    match (fits, best_fits) {
+                        (true, false) => true,
+                        (false, true) => false,
+                        (true, true) => cost < best_cost || (cost == best_cost && time < best_time),
+                        (false, false) => time < best_time,
+                    }
+                }
+            };
+            if better {
+                best = Some((ship, plans));
+            }
+        }
+
+        let (ship, plans) = match best {
+            Some(best) => best,
+            None => {
+                evaluation.reasons.push(
+                    "no ship can source every good from known markets or by mining".to_owned(),
+                );
+                return evaluation;
+            }
+        };
+        let (acquisition_cost, crew_cost, estimated_time) = totals(&plans);
+        evaluation.ship_symbol = Some(ship.symbol.clone());
+        evaluation.acquisition_cost = acquisition_cost;
+        evaluation.crew_cost = crew_cost;
+        evaluation.estimated_time = estimated_time;
+        evaluation.net_profit = payment - acquisition_cost - crew_cost;
+
+        for plan in &plans {
+            let how = match &plan.acquisition {
+                Acquisition::Buy {
+                    waypoint_symbol,
+                    price_per_unit,
+                } => format!("buy at {} for {} each", waypoint_symbol, price_per_unit),
+                Acquisition::Mine { waypoint_symbol } => format!("mine at {}", waypoint_symbol),
+            };
+            evaluation.reasons.push(format!(
+                "{} x{} for {}: {}, costing {} credits over ~{} minutes",
+                plan.trade_symbol,
+                plan.units,
+                plan.destination_symbol,
+                how,
+                plan.cost,
+                plan.time.num_minutes()
+            ));
+        }
+
+        let mut go = true;
+        if estimated_time + self.safety_margin > time_left {
+            go = false;
+            evaluation.reasons.push(format!(
+                "{} would need ~{} minutes but the deadline is {} minutes away",
+                ship.symbol,
+                estimated_time.num_minutes(),
+                time_left.num_minutes()
+            ));
+        }
+        let costs = match crew_cost {
+            0 => format!("sourcing costs {}", acquisition_cost),
+            _ => format!(
+                "sourcing costs {} and the crew's wages {}",
+                acquisition_cost, crew_cost
+            ),
+        };
+        if evaluation.net_profit <= 0 {
+            go = false;
+            evaluation.reasons.push(format!(
+                "pays {} but {}, a loss of {}",
+                payment, costs, -evaluation.net_profit
+            ));
+        } else {
+            evaluation.reasons.push(format!(
+                "pays {} and {}, a profit of {}",
+                payment, costs, evaluation.net_profit
+            ));
+        }
+        if let Some(credits) = self.credits {
+            let available = credits + on_accepted;
+            if acquisition_cost > available {
+                go = false;
+                let when = match contract.accepted {
+                    true => "",
+                    false => " after accepting",
+                };
+                evaluation.reasons.push(format!(
+                    "sourcing costs {} but only {} credits are available{}",
+                    acquisition_cost, available, when
+                ));
+            }
+        }
+        evaluation.go = go;
+        evaluation.plans = plans;
+        evaluation
+    }
+
+    fn position_of(&self, ship: &schema::Ship) -> (i32, i32) {
+        let destination = &ship.nav.route.destination;
+        (destination.x, destination.y)
+    }
+
+    fn plan_for_ship(
+        &self,
+        ship: &schema::Ship,
+        contract: &schema::Contract,
+        time_left: Duration,
+    ) -> Option<Vec<DeliveryPlan>> {
+        //! plans each good in turn, with the ship starting from wherever
+        //! the last delivery left it and only the time the earlier goods
+        //! haven't used up
+        let mut position = self.position_of(ship);
+        let mut time_left = time_left;
+        let mut plans = Vec::new();
+        for good in &contract.terms.deliver {
+            let units = (good.units_required - good.units_fulfilled).max(0) as u32;
+            if units == 0 {
+                continue;
+            }
+            let destination = *self.positions.get(&good.destination_symbol)?;
+            let plan = self.plan_good(ship, position, destination, good, units, time_left)?;
+            time_left -= plan.time;
+            plans.push(plan);
+            position = destination;
+        }
+        Some(plans)
+    }
+
+    fn travel(&self, ship: &schema::Ship, from: (i32, i32), to: (i32, i32)) -> Duration {
+        let distance = navigation::distance(from, to);
+        navigation::travel_time(distance, ship.engine.speed, ship.nav.flight_mode)
+    }
+
+    fn plan_good(
+        &self,
+        ship: &schema::Ship,
+        start: (i32, i32),
+        destination: (i32, i32),
+        good: &schema::ContractDeliverGood,
+        units: u32,
+        time_left: Duration,
+    ) -> Option<DeliveryPlan> {
+        let capacity = ship.cargo.capacity;
+        if capacity == 0 {
+            return None;
+        }
+        let trips = units.div_ceil(capacity) as i32;
+        let wages = |time: Duration| crew::wage_cost(&ship.crew, time.to_std().unwrap_or_default());
+        // get there once, then shuttle between the source and destination
+        let trip_time = |source: (i32, i32)| {
+            self.travel(ship, start, source)
+                + self.travel(ship, source, destination) * trips
+                + self.travel(ship, destination, source) * (trips - 1)
+        };
+
+        let mut options = Vec::new();
+        for price in self
+            .prices
+            .iter()
+            .filter(|p| p.trade_symbol == good.trade_symbol)
+        {
+            if let Some(&source) = self.positions.get(&price.waypoint_symbol) {
+                options.push(DeliveryPlan {
+                    trade_symbol: good.trade_symbol.clone(),
+                    destination_symbol: good.destination_symbol.clone(),
+                    units,
+                    acquisition: Acquisition::Buy {
+                        waypoint_symbol: price.waypoint_symbol.clone(),
+                        price_per_unit: price.purchase_price,
+                    },
+                    cost: price.purchase_price as i64 * units as i64,
+                    crew_cost: wages(trip_time(source)),
+                    time: trip_time(source),
+                });
+            }
+        }
+
+        let strength = ShipCapabilities::of(ship).mining_strength_for(good.trade_symbol.clone());
+        if strength > 0 {
+            let per_extraction = (strength as f64 * self.mining_hit_rate).max(1.0);
+            let extractions = (units as f64 / per_extraction).ceil() as i32;
+            for field in &self.asteroid_fields {
+                let source = self.positions[field];
+                let time = trip_time(source) + self.extraction_cooldown * extractions;
+                options.push(DeliveryPlan {
+                    trade_symbol: good.trade_symbol.clone(),
+                    destination_symbol: good.destination_symbol.clone(),
+                    units,
+                    acquisition: Acquisition::Mine {
+                        waypoint_symbol: field.clone(),
+                    },
+                    cost: 0,
+                    crew_cost: wages(time),
+                    time,
+                });
+            }
+        }
+
+        // the cheapest option, wages and all, that makes the deadline, or the quickest if none do
+        let fits = |plan: &DeliveryPlan| plan.time + self.safety_margin <= time_left;
+        if options.iter().any(fits) {
+            options
+                .into_iter()
+                .filter(fits)
+                .min_by_key(|plan| (plan.cost + plan.crew_cost, plan.time))
+        } else {
+            options.into_iter().min_by(|a, b| a.time.cmp(&b.time))
+        }
+    }
+}
+
+fn totals(plans: &[DeliveryPlan]) -> (i64, i64, Duration) {
+    //! what the plans cost to source, what the crew are paid meanwhile,
+    //! and how long they take
+    plans
+        .iter()
+        .fold((0, 0, Duration::zero()), |(cost, crew_cost, time), plan| {
+            (
+                cost + plan.cost,
+                crew_cost + plan.crew_cost,
+                time + plan.time,
+            )
+        })
+}
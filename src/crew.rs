@@ -0,0 +1,160 @@
+/*
+The crew module keeps account of what our crews cost and how they're
+doing.
+
+Every crew member is paid wages credits an hour, so a ship costs
+current × wages an hour to run before it's burned any fuel. A ship
+short of its required crew, or whose crew's morale has dropped, works
+worse than it should, so CrewAccounts warns about both. It also totals
+the fleet's wages over whatever period we're budgeting for.
+
+Wages are a cost like any other, so everything that weighs up profit
+counts them: the miner charges each MiningCycle for its crew's time,
+routes are charged for the round trip, contracts for as long as
+sourcing them takes, and the expansion planner takes a new ship's
+wages out of what it'd earn. Shipyard listings don't say what a crew
+will be paid, only how many a ship needs, which required_crew adds
+up.
+*/
+
+use crate::api::schema::{Ship, ShipCrew, ShipSymbol, ShipyardShip};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+pub fn wages_per_hour(crew: &ShipCrew) -> i64 { crew.current.max(0) as i64 * crew.wages as i64 }
+
+pub fn wage_cost(crew: &ShipCrew, period: Duration) -> i64 {
+    //! what the crew are paid over period, to the nearest credit
+    wages_over(wages_per_hour(crew), period)
+}
+
+pub fn wages_over(wages_per_hour: i64, period: Duration) -> i64 {
+    (wages_per_hour as f64 * period.as_secs_f64() / 3600.0).round() as i64
+}
+
+pub fn required_crew(ship: &ShipyardShip) -> i32 {
+    //! the crew a ship for sale would need, going by what each of its
+    //! parts requires
+    let parts = [
+        &ship.frame.requirements,
+        &ship.reactor.requirements,
+        &ship.engine.requirements,
+    ];
+    let modules = ship.modules.iter().map(|module| &module.requirements);
+    let mounts = ship.mounts.iter().map(|mount| &mount.requirements);
+    parts
+        .into_iter()
+        .chain(modules)
+        .chain(mounts)
+        .filter_map(|requirements| requirements.crew)
+        .sum()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CrewWarning {
+    Understaffed {
+        ship_symbol: ShipSymbol,
+        current: i32,
+        required: i32,
+    },
+    LowMorale {
+        ship_symbol: ShipSymbol,
+        morale: u8,
+    },
+}
+
+impl fmt::Display for CrewWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrewWarning::Understaffed {
+                ship_symbol,
+                current,
+                required,
+            } => write!(
+                f,
+                "{} has {} crew but needs {}",
+                ship_symbol, current, required
+            ),
+            CrewWarning::LowMorale {
+                ship_symbol,
+                morale,
+            } => write!(f, "{}'s crew morale is down to {}", ship_symbol, morale),
+        }
+    }
+}
+
+pub struct CrewAccounts {
+    crews: BTreeMap<ShipSymbol, ShipCrew>,
+    /// morale below this gets a warning
+    pub low_morale: u8,
+}
+
+impl Default for CrewAccounts {
+    fn default() -> Self { Self::new() }
+}
+
+impl CrewAccounts {
+    pub fn new() -> Self {
+        Self {
+            crews: BTreeMap::new(),
+            low_morale: 50,
+        }
+    }
+
+    pub fn with_low_morale(mut self, low_morale: u8) -> Self {
+        self.low_morale = low_morale;
+        self
+    }
+
+    pub fn add_ship(&mut self, ship: &Ship) {
+        //! or replaces what we had for it
+        self.crews.insert(ship.symbol.clone(), ship.crew.clone());
+    }
+
+    pub fn add_fleet<'a>(&mut self, ships: impl IntoIterator<Item = &'a Ship>) {
+        for ship in ships {
+            self.add_ship(ship);
+        }
+    }
+
+    pub fn crew(&self, ship: &ShipSymbol) -> Option<&ShipCrew> { self.crews.get(ship) }
+
+    pub fn wages_per_hour(&self) -> i64 { self.crews.values().map(wages_per_hour).sum() }
+
+    pub fn wages(&self, period: Duration) -> i64 {
+        //! the whole fleet's wages over period
+        self.crews
+            .values()
+            .map(|crew| wage_cost(crew, period))
+            .sum()
+    }
+
+    pub fn wages_by_ship(&self, period: Duration) -> BTreeMap<ShipSymbol, i64> {
+        self.crews
+            .iter()
+            .map(|(ship, crew)| (ship.clone(), wage_cost(crew, period)))
+            .collect()
+    }
+
+    pub fn warnings(&self) -> Vec<CrewWarning> {
+        let mut warnings = Vec::new();
+        for (ship, crew) in &self.crews {
+            if crew.current < crew.required {
+                warnings.push(CrewWarning::Understaffed {
+                    ship_symbol: ship.clone(),
+                    current: crew.current,
+                    required: crew.required,
+                });
+            }
+            // a ship without crew has no morale to speak of
+            if crew.current > 0 && crew.morale < self.low_morale {
+                warnings.push(CrewWarning::LowMorale {
+                    ship_symbol: ship.clone(),
+                    morale: crew.morale,
+                });
+            }
+        }
+        warnings
+    }
+}
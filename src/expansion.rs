@@ -0,0 +1,425 @@
+/*
+The expansion module works out which ship to buy next, and where.
+
+Every ship a shipyard lists is a candidate, and each one is given an
+income for the job it'd do best. A ship with mining lasers and a hold
+can mine: fill up at an asteroid field, one extraction per cooldown,
+then sell at the nearest market we know. What it mines is valued at
+what our own MiningCycles made per unit, once there are some to go
+by. A ship with a hold can haul along the best route the RouteFinder
+can find for it. Either way the crew's wages come out of that income,
+at crew_wages a head for the crew the listing needs, and mined units
+are valued at what our miners made before their own crew's wages so
+those aren't counted twice. The payback period is the purchase price over that
+hourly income, and the affordable candidate that pays for itself
+soonest is the one recommended. Like the contract evaluator, each
+candidate carries its reasoning.
+
+expand() goes one step further and buys the recommended ship, but
+only once it pays for itself within max_payback and leaves reserve
+credits in the bank. Since that spends money, it checks against the
+balance in the client's AgentState, not whatever set_credits was
+given, and won't buy at all while the balance is unknown.
+*/
+
+use crate::api::responses::fleet;
+use crate::api::schema;
+use crate::api::{Error, SpaceTraderApi};
+use crate::behavior::MiningCycle;
+use crate::capabilities::ShipCapabilities;
+use crate::crew;
+use crate::history::PriceRecord;
+use crate::navigation;
+use crate::trading::{RouteFinder, RouteRanking, TradeShip};
+use chrono::Duration;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Job {
+    Mining,
+    Hauling,
+}
+
+#[derive(Debug)]
+pub struct Candidate {
+    pub ship_type: schema::ShipType,
+    pub waypoint_symbol: schema::WaypointSymbol,
+    pub purchase_price: u32,
+    /// whichever job it'd earn the most at, if it can earn anything
+    pub job: Option<Job>,
+    pub income_per_hour: f64,
+    /// how long until it's earned back what it cost
+    pub payback: Option<Duration>,
+    pub affordable: bool,
+    /// affordable, and pays back within max_payback
+    pub go: bool,
+    pub reasons: Vec<String>,
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.go { "GO" } else { "NO GO" };
+        write!(
+            f,
+            "{} at {} for {}: {}",
+            self.ship_type, self.waypoint_symbol, self.purchase_price, verdict
+        )?;
+        for reason in &self.reasons {
+            write!(f, "\n  - {}", reason)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct ExpansionPlanner {
+    positions: HashMap<schema::WaypointSymbol, (i32, i32)>,
+    asteroid_fields: Vec<schema::WaypointSymbol>,
+    markets: HashSet<schema::WaypointSymbol>,
+    routes: RouteFinder,
+    shipyards: BTreeMap<schema::WaypointSymbol, schema::Shipyard>,
+    credits: Option<i64>,
+    mined_units: u32,
+    mined_profit: i64,
+    mining_cycles: usize,
+    /// candidates that take longer than this to pay for themselves aren't a go
+    pub max_payback: Duration,
+    /// credits that have to be left over after buying
+    pub reserve: i64,
+    pub extraction_cooldown: Duration,
+    /// units per extraction for each point of laser strength
+    pub mining_yield: f64,
+    /// what a mined unit is assumed to sell for until there are cycles to go by
+    pub mined_unit_value: u32,
+    /// what each crew member is paid an hour. listings don't say, so
+    /// it's best set from what our own crews get
+    pub crew_wages: u32,
+}
+
+impl ExpansionPlanner {
+    pub fn new(system: &schema::System) -> Self {
+        let mut planner = Self {
+            positions: HashMap::new(),
+            asteroid_fields: Vec::new(),
+            markets: HashSet::new(),
+            routes: RouteFinder::new(system),
+            shipyards: BTreeMap::new(),
+            credits: None,
+            mined_units: 0,
+            mined_profit: 0,
+            mining_cycles: 0,
+            max_payback: Duration::hours(12),
+            reserve: 0,
+            extraction_cooldown: Duration::seconds(70),
+            mining_yield: 0.5,
+            mined_unit_value: 25,
+            crew_wages: 0,
+        };
+        for waypoint in &system.waypoints {
+            planner
+                .positions
+                .insert(waypoint.symbol.clone(), (waypoint.x, waypoint.y));
+            if matches!(waypoint.kind, schema::WaypointType::AsteroidField) {
+                planner.asteroid_fields.push(waypoint.symbol.clone());
+            }
+        }
+        planner
+    }
+
+    pub fn add_waypoint(&mut self, symbol: &schema::WaypointSymbol, x: i32, y: i32) {
+        self.positions.insert(symbol.clone(), (x, y));
+        self.routes.add_waypoint(symbol, x, y);
+    }
+
+    pub fn add_asteroid_field(&mut self, symbol: &schema::WaypointSymbol, x: i32, y: i32) {
+        self.add_waypoint(symbol, x, y);
+        self.asteroid_fields.push(symbol.clone());
+    }
+
+    pub fn add_market(&mut self, market: &schema::Market) {
+        self.markets.insert(market.symbol.clone());
+        self.routes.add_market(market);
+    }
+
+    pub fn add_prices(&mut self, prices: impl IntoIterator<Item = PriceRecord>) {
+        //! meant for PriceHistory::latest_in_system
+        let prices: Vec<PriceRecord> = prices.into_iter().collect();
+        self.markets
+            .extend(prices.iter().map(|price| price.waypoint_symbol.clone()));
+        self.routes.add_prices(prices);
+    }
+
+    pub fn add_shipyard(&mut self, shipyard: &schema::Shipyard) {
+        //! replaces what we had for the same shipyard. its ships are only
+        //! listed while one of ours is there, so one fetched from afar
+        //! doesn't wipe out the listings we already have
+        if shipyard.ships.is_empty() && self.shipyards.contains_key(&shipyard.symbol) {
+            return;
+        }
+        self.shipyards
+            .insert(shipyard.symbol.clone(), shipyard.clone());
+    }
+
+    pub fn add_mining_cycle(&mut self, cycle: &MiningCycle) {
+        //! what our miners actually made, for valuing what a new one mines.
+        //! a new one's wages are charged separately, so not theirs
+        self.mined_units += cycle.units();
+        self.mined_profit += cycle.profit() + cycle.crew_cost;
+        self.mining_cycles += 1;
+    }
+
+    pub fn set_credits(&mut self, credits: i64) {
+        //! lets the planner turn down ships we can't afford
+        self.credits = Some(credits);
+    }
+
+    pub fn plan(&self) -> Vec<Candidate> {
+        //! every ship on offer, soonest to pay for itself first
+        self.plan_with(self.credits)
+    }
+
+    fn plan_with(&self, credits: Option<i64>) -> Vec<Candidate> {
+        let mut candidates: Vec<Candidate> = self
+            .shipyards
+            .values()
+            .flat_map(|shipyard| {
+                shipyard.ships.iter().filter_map(|ship| {
+                    Some(self.candidate(&shipyard.symbol, ship.kind.clone()?, ship, credits))
+                })
+            })
+            .collect();
+        candidates.sort_by(|a, b| match (a.payback, b.payback) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.purchase_price.cmp(&b.purchase_price),
+        });
+        candidates
+    }
+
+    pub fn recommend(&self) -> Option<Candidate> {
+        //! the affordable ship that pays for itself soonest, go or not
+        Self::pick(self.plan())
+    }
+
+    fn pick(plan: Vec<Candidate>) -> Option<Candidate> {
+        plan.into_iter()
+            .find(|candidate| candidate.affordable && candidate.payback.is_some())
+    }
+
+    pub async fn expand(&self, api: &SpaceTraderApi) -> Result<Option<fleet::PurchaseShip>, Error> {
+        //! buys the recommended ship if it's a go. one of our ships has to
+        //! be at the shipyard for the server to sell it. it goes by the
+        //! client's AgentState rather than set_credits, and won't spend
+        //! anything until that knows our balance
+        let credits = api
+            .agent_state()
+            .credits()
+            .ok_or("don't know how many credits we have, so not buying anything")?;
+        match Self::pick(self.plan_with(Some(credits))) {
+            Some(candidate) if candidate.go => {
+                let bought = api
+                    .purchase_ship(candidate.ship_type, &candidate.waypoint_symbol)
+                    .await?;
+                Ok(Some(bought))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn candidate(
+        &self,
+        waypoint_symbol: &schema::WaypointSymbol,
+        ship_type: schema::ShipType,
+        ship: &schema::ShipyardShip,
+        credits: Option<i64>,
+    ) -> Candidate {
+        let purchase_price = ship.purchase_price.max(0) as u32;
+        let mut candidate = Candidate {
+            ship_type,
+            waypoint_symbol: waypoint_symbol.clone(),
+            purchase_price,
+            job: None,
+            income_per_hour: 0.0,
+            payback: None,
+            affordable: true,
+            go: false,
+            reasons: Vec::new(),
+        };
+
+        let mining = self.mining_income(ship, &mut candidate.reasons);
+        let hauling = self.hauling_income(ship, &mut candidate.reasons);
+        let best = [(Job::Mining, mining), (Job::Hauling, hauling)]
+            .into_iter()
+            .filter_map(|(job, income)| Some((job, income?)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match best {
+            Some((job, income)) if income > 0.0 => {
+                candidate.job = Some(job);
+                candidate.income_per_hour = income;
+                let hours = purchase_price as f64 / income;
+                let payback = Duration::seconds((hours * 3600.0).ceil() as i64);
+                candidate.payback = Some(payback);
+                candidate.reasons.push(format!(
+                    "best at {:?}, earning ~{:.0} an hour and paying for itself in ~{:.1} hours",
+                    job,
+                    income,
+                    payback.num_minutes() as f64 / 60.0
+                ));
+            }
+            _ => candidate
+                .reasons
+                .push("no job it could earn anything at".to_owned()),
+        }
+
+        if let Some(credits) = credits {
+            let left = credits - purchase_price as i64;
+            if left < self.reserve {
+                candidate.affordable = false;
+                candidate.reasons.push(format!(
+                    "costs {} but only {} of our {} credits can be spent",
+                    purchase_price,
+                    (credits - self.reserve).max(0),
+                    credits
+                ));
+            }
+        }
+        candidate.go = candidate.affordable
+            && candidate
+                .payback
+                .is_some_and(|payback| payback <= self.max_payback);
+        if let Some(payback) = candidate.payback {
+            if payback > self.max_payback {
+                candidate.reasons.push(format!(
+                    "takes longer than the {} hours allowed to pay for itself",
+                    self.max_payback.num_hours()
+                ));
+            }
+        }
+        candidate
+    }
+
+    fn wages_per_hour(&self, ship: &schema::ShipyardShip) -> i64 {
+        crew::required_crew(ship).max(0) as i64 * self.crew_wages as i64
+    }
+
+    fn travel(&self, ship: &schema::ShipyardShip, from: (i32, i32), to: (i32, i32)) -> Duration {
+        let distance = navigation::distance(from, to);
+        navigation::travel_time(
+            distance,
+            ship.engine.speed,
+            schema::ShipNavFlightMode::Cruise,
+        )
+    }
+
+    fn mining_income(&self, ship: &schema::ShipyardShip, reasons: &mut Vec<String>) -> Option<f64> {
+        //! credits an hour from filling the hold at the best field and
+        //! selling at the market nearest it
+        let capabilities = ShipCapabilities::of_listing(ship);
+        let strength = capabilities.mining_strength;
+        let capacity = capabilities.cargo_capacity;
+        if strength == 0 || capacity == 0 {
+            return None;
+        }
+        let markets: Vec<(i32, i32)> = self
+            .markets
+            .iter()
+            .filter_map(|market| self.positions.get(market).copied())
+            .collect();
+        let sell_trip = self
+            .asteroid_fields
+            .iter()
+            .filter_map(|field| self.positions.get(field))
+            .filter_map(|&field| {
+                markets
+                    .iter()
+                    .map(|&market| self.travel(ship, field, market) * 2)
+                    .min()
+            })
+            .min();
+        let sell_trip = match sell_trip {
+            Some(sell_trip) => sell_trip,
+            None => {
+                reasons.push(
+                    "can mine, but there's no known field with a market to sell at".to_owned(),
+                );
+                return None;
+            }
+        };
+
+        let per_extraction = (strength as f64 * self.mining_yield).clamp(1.0, capacity as f64);
+        let extractions = (capacity as f64 / per_extraction).ceil() as i32;
+        let cycle = self.extraction_cooldown * extractions + sell_trip;
+        let unit_value = match self.mined_units {
+            0 => {
+                reasons.push(format!(
+                    "no mining cycles recorded yet, so mined units are taken to be worth {}",
+                    self.mined_unit_value
+                ));
+                self.mined_unit_value as f64
+            }
+            units => {
+                let value = self.mined_profit as f64 / units as f64;
+                reasons.push(format!(
+                    "our {} recorded mining cycles made {:.1} a unit before wages",
+                    self.mining_cycles, value
+                ));
+                value
+            }
+        };
+        let income = capacity as f64 * unit_value / (cycle.num_seconds().max(1) as f64 / 3600.0)
+            - self.wages_per_hour(ship) as f64;
+        reasons.push(format!(
+            "mining fills its {} hold in {} extractions, {} minutes a trip with selling, for ~{:.0} an hour after {} in wages",
+            capacity,
+            extractions,
+            cycle.num_minutes(),
+            income,
+            self.wages_per_hour(ship)
+        ));
+        Some(income)
+    }
+
+    fn hauling_income(
+        &self,
+        ship: &schema::ShipyardShip,
+        reasons: &mut Vec<String>,
+    ) -> Option<f64> {
+        //! credits an hour along the best route for a hold its size
+        let capacity = ShipCapabilities::of_listing(ship).cargo_capacity;
+        if capacity == 0 {
+            return None;
+        }
+        let trade_ship = TradeShip {
+            cargo_capacity: capacity,
+            engine_speed: ship.engine.speed,
+            flight_mode: schema::ShipNavFlightMode::Cruise,
+            // as the Hauler trades, so prices don't run away from it
+            max_transactions: Some(1),
+            wages_per_hour: self.wages_per_hour(ship),
+        };
+        let routes = self
+            .routes
+            .routes(&trade_ship, RouteRanking::ProfitPerSecond);
+        let route = match routes.first() {
+            Some(route) => route,
+            None => {
+                reasons.push("no profitable trade route known to haul along".to_owned());
+                return None;
+            }
+        };
+        let income = route.profit_per_second * 3600.0;
+        reasons.push(format!(
+            "hauling {} {} from {} to {} makes {} a trip of {} minutes after {} in wages, for ~{:.0} an hour",
+            route.units_per_trip,
+            route.trade_symbol,
+            route.buy_at,
+            route.sell_at,
+            route.profit_per_trip,
+            route.round_trip.num_minutes(),
+            route.crew_cost,
+            income
+        ));
+        Some(income)
+    }
+}
@@ -0,0 +1,362 @@
+/*
+The fleet module runs every ship at once, each in its own tokio task.
+
+A Behavior is whatever a ship should be doing, written as one async
+run() that keeps going until the job is done or something goes wrong.
+The Fleet loads our ships, gives each one a behavior, and supervises
+it: a run that fails or panics is started again after a backoff that
+doubles up to a limit, and one that finishes leaves the ship idle
+until it's told something new.
+
+Each ship has a ShipControl to pause, resume or reassign it. Pausing
+and reassigning stop the current run wherever it is, so a behavior
+should be able to pick up from whatever state the ship was left in;
+AgentState has the latest copy of the ship to start from. All the
+ships share one client, and so one rate limit and one AgentState.
+*/
+
+use crate::api::schema::{Ship, ShipNavStatus, ShipSymbol, SystemSymbol, WaypointSymbol};
+use crate::api::transport::{BoxFuture, RateLimited};
+use crate::api::{Error, SpaceTraderApi};
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::{JoinError, JoinHandle};
+use tokio::time::Instant;
+
+pub trait Behavior: Send + Sync {
+    fn run<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<(), Error>>;
+}
+
+/// what a behavior gets to work with
+#[derive(Clone)]
+pub struct ShipContext {
+    pub symbol: ShipSymbol,
+    pub api: Arc<SpaceTraderApi>,
+}
+
+impl ShipContext {
+    pub fn ship(&self) -> Option<Ship> {
+        //! as of the latest response that said anything about it
+        self.api.agent_state().ship(&self.symbol)
+    }
+
+    pub async fn current(&self) -> Result<Ship, Error> {
+        //! like ship(), but asks the server if we've never seen it
+        match self.ship() {
+            Some(ship) => Ok(ship),
+            None => self.api.get_ship(&self.symbol).await,
+        }
+    }
+
+    pub async fn orbit(&self) -> Result<(), Error> {
+        //! unless it already is
+        if !matches!(self.current().await?.nav.status, ShipNavStatus::InOrbit) {
+            self.api.orbit_ship(&self.symbol).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn dock(&self) -> Result<(), Error> {
+        //! unless it already is
+        if !matches!(self.current().await?.nav.status, ShipNavStatus::Docked) {
+            self.api.dock_ship(&self.symbol).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn navigate(&self, waypoint: &WaypointSymbol) -> Result<(), Error> {
+        //! flies to waypoint and returns once it's there, or straight away
+        //! if it already is
+        self.wait_for_arrival().await?;
+        if self.current().await?.nav.waypoint_symbol == *waypoint {
+            return Ok(());
+        }
+        self.orbit().await?;
+        let route = self
+            .api
+            .navigate_ship(&self.symbol, waypoint)
+            .await?
+            .nav
+            .route;
+        // the trip's length by the server's clock, which needn't match ours
+        let trip = route.arrival - route.departure_time;
+        tokio::time::sleep(trip.to_std().unwrap_or_default()).await;
+        self.api.get_ship_nav(&self.symbol).await?;
+        self.wait_for_arrival().await
+    }
+
+    pub async fn jump(&self, system: &SystemSymbol) -> Result<(), Error> {
+        //! jumps through the gate the ship is at, once it's cooled down from
+        //! the last jump. there's no trip to wait for on the other side
+        self.wait_for_arrival().await?;
+        self.orbit().await?;
+        self.wait_for_cooldown().await?;
+        self.api.jump_ship(&self.symbol, system).await?;
+        Ok(())
+    }
+
+    pub async fn wait_for_arrival(&self) -> Result<(), Error> {
+        //! returns once the ship isn't in transit
+        let mut nav = self.current().await?.nav;
+        while matches!(nav.status, ShipNavStatus::InTransit) {
+            // a trip we didn't start could be anywhere along the way, and
+            // our clock is all there is to go by
+            let trip = nav.route.arrival - nav.route.departure_time;
+            let left = (nav.route.arrival - Utc::now()).clamp(chrono::Duration::zero(), trip);
+            let left = left.to_std().unwrap_or_default();
+            tokio::time::sleep(left.max(Duration::from_secs(1))).await;
+            nav = self.api.get_ship_nav(&self.symbol).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn wait_for_cooldown(&self) -> Result<(), Error> {
+        //! returns once the ship can extract, survey and so on again
+        while let Some(cooldown) = self.api.get_ship_cooldown(&self.symbol).await? {
+            if cooldown.remaining_seconds == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_secs(cooldown.remaining_seconds as u64)).await;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShipStatus {
+    Running,
+    Paused,
+    /// the behavior returned, and the ship is waiting for a new one
+    Finished,
+    /// the behavior failed and will be started again after retry_in
+    Failed {
+        error: String,
+        restarts: u32,
+        retry_in: Duration,
+    },
+}
+
+#[derive(Clone)]
+struct Assignment {
+    behavior: Arc<dyn Behavior>,
+    paused: bool,
+}
+
+/// pauses, resumes and reassigns one ship
+#[derive(Clone)]
+pub struct ShipControl {
+    assignment: Arc<watch::Sender<Assignment>>,
+    status: watch::Receiver<ShipStatus>,
+}
+
+impl ShipControl {
+    pub fn pause(&self) { self.assignment.send_modify(|a| a.paused = true); }
+
+    pub fn resume(&self) {
+        self.assignment
+            .send_if_modified(|a| std::mem::replace(&mut a.paused, false));
+    }
+
+    pub fn reassign(&self, behavior: Arc<dyn Behavior>) {
+        //! starts behavior in place of the current one, unless paused
+        self.assignment.send_modify(|a| a.behavior = behavior);
+    }
+
+    pub fn status(&self) -> ShipStatus { self.status.borrow().clone() }
+
+    pub fn watch_status(&self) -> watch::Receiver<ShipStatus> { self.status.clone() }
+}
+
+struct AbortOnDrop<T>(JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) { self.0.abort(); }
+}
+
+struct Supervised {
+    control: ShipControl,
+    _task: AbortOnDrop<()>,
+}
+
+pub struct Fleet {
+    api: Arc<SpaceTraderApi>,
+    ships: Mutex<BTreeMap<ShipSymbol, Supervised>>,
+    min_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Fleet {
+    pub fn new(api: SpaceTraderApi) -> Self {
+        //! every ship's requests go through api, behind the server's rate limit
+        Self::with_client(Arc::new(api.layer(RateLimited::spacetraders)))
+    }
+
+    pub fn with_client(api: Arc<SpaceTraderApi>) -> Self {
+        //! shares api as it is, for when it's already rate limited
+        Self {
+            api,
+            ships: Mutex::new(BTreeMap::new()),
+            min_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5 * 60),
+        }
+    }
+
+    pub fn with_backoff(mut self, min: Duration, max: Duration) -> Self {
+        //! how long to wait before restarting a failed behavior the first
+        //! time, and at most. a behavior that ran for at least max before
+        //! failing starts over from min
+        self.min_backoff = min;
+        self.max_backoff = max;
+        self
+    }
+
+    pub fn api(&self) -> &Arc<SpaceTraderApi> { &self.api }
+
+    pub async fn start<F>(&self, pick: F) -> Result<usize, Error>
+    where F: Fn(&Ship) -> Arc<dyn Behavior> {
+        //! loads every ship and starts any that isn't running yet with the
+        //! behavior pick chooses for it. returns how many were started
+        let ships = self.api.list_ships().await?;
+        let mut started = 0;
+        for ship in &ships {
+            if !self.ships.lock().unwrap().contains_key(&ship.symbol) {
+                self.add(&ship.symbol, pick(ship));
+                started += 1;
+            }
+        }
+        Ok(started)
+    }
+
+    pub fn add(&self, symbol: &ShipSymbol, behavior: Arc<dyn Behavior>) -> ShipControl {
+        //! starts supervising one ship, replacing whatever was running it
+        let (assignment, receiver) = watch::channel(Assignment {
+            behavior,
+            paused: false,
+        });
+        let (status, status_receiver) = watch::channel(ShipStatus::Running);
+        let control = ShipControl {
+            assignment: Arc::new(assignment),
+            status: status_receiver,
+        };
+        let context = ShipContext {
+            symbol: symbol.clone(),
+            api: self.api.clone(),
+        };
+        let backoff = (self.min_backoff, self.max_backoff);
+        let task = tokio::spawn(supervise(context, receiver, status, backoff));
+        self.ships.lock().unwrap().insert(
+            symbol.clone(),
+            Supervised {
+                control: control.clone(),
+                _task: AbortOnDrop(task),
+            },
+        );
+        control
+    }
+
+    pub fn control(&self, symbol: &ShipSymbol) -> Option<ShipControl> {
+        self.ships
+            .lock()
+            .unwrap()
+            .get(symbol)
+            .map(|ship| ship.control.clone())
+    }
+
+    pub fn ships(&self) -> Vec<ShipSymbol> { self.ships.lock().unwrap().keys().cloned().collect() }
+
+    pub fn remove(&self, symbol: &ShipSymbol) -> bool {
+        //! stops supervising a ship, and stops whatever it was doing
+        self.ships.lock().unwrap().remove(symbol).is_some()
+    }
+}
+
+async fn supervise(
+    context: ShipContext,
+    mut assignment: watch::Receiver<Assignment>,
+    status: watch::Sender<ShipStatus>,
+    (min_backoff, max_backoff): (Duration, Duration),
+) {
+    //! runs one ship until its Fleet lets go of it
+    let mut backoff = min_backoff;
+    let mut restarts = 0;
+    loop {
+        let current = assignment.borrow_and_update().clone();
+        if current.paused {
+            status.send_replace(ShipStatus::Paused);
+            if assignment.changed().await.is_err() {
+                return;
+            }
+            continue;
+        }
+
+        status.send_replace(ShipStatus::Running);
+        let started = Instant::now();
+        // in a task of its own, so a panic is just another failure
+        let mut run = {
+            let context = context.clone();
+            AbortOnDrop(tokio::spawn(
+                async move { current.behavior.run(&context).await },
+            ))
+        };
+        let outcome = tokio::select! {
+            joined = &mut run.0 => match joined {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(error)) => Err(error.to_string()),
+                Err(error) => Err(panic_message(error)),
+            },
+            // paused or reassigned: drop the run wherever it is
+            changed = assignment.changed() => match changed {
+                Ok(()) => {
+                    backoff = min_backoff;
+                    continue;
+                }
+                Err(_) => return,
+            },
+        };
+
+        match outcome {
+            Ok(()) => {
+                status.send_replace(ShipStatus::Finished);
+                backoff = min_backoff;
+                if assignment.changed().await.is_err() {
+                    return;
+                }
+            }
+            Err(error) => {
+                if started.elapsed() >= max_backoff {
+                    backoff = min_backoff;
+                }
+                restarts += 1;
+                status.send_replace(ShipStatus::Failed {
+                    error,
+                    restarts,
+                    retry_in: backoff,
+                });
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => backoff = (backoff * 2).min(max_backoff),
+                    changed = assignment.changed() => match changed {
+                        Ok(()) => backoff = min_backoff,
+                        Err(_) => return,
+                    },
+                }
+            }
+        }
+    }
+}
+
+fn panic_message(error: JoinError) -> String {
+    let panic = match error.try_into_panic() {
+        Ok(panic) => panic,
+        Err(error) => return error.to_string(),
+    };
+    match panic.downcast::<String>() {
+        Ok(message) => format!("panicked: {message}"),
+        Err(panic) => match panic.downcast::<&str>() {
+            Ok(message) => format!("panicked: {message}"),
+            Err(_) => "panicked".to_owned(),
+        },
+    }
+}
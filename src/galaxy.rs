@@ -0,0 +1,246 @@
+/*
+The galaxy module is our own map of the parts of the universe we've
+been to: every waypoint we've seen and every jump gate we've looked
+through.
+
+The cache forgets all of it at the next reset and the server only
+answers about one thing at a time, so explorers record what they find
+here, in SQLite as JSON, and anything that wants to plan across
+systems asks it. A waypoint, once charted, stays charted: recording
+an older copy that still says UNCHARTED doesn't undo it.
+*/
+
+use crate::api::schema::{JumpGate, SystemSymbol, Waypoint, WaypointSymbol, WaypointTraitSymbol};
+use crate::api::Error;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::sync::Mutex;
+
+fn is_charted(waypoint: &Waypoint) -> bool {
+    !waypoint
+        .traits
+        .iter()
+        .any(|t| t.symbol == WaypointTraitSymbol::Uncharted)
+}
+
+pub struct Galaxy {
+    conn: Mutex<Connection>,
+}
+
+impl Galaxy {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Error> { Self::init(Connection::open_in_memory()?) }
+
+    fn init(conn: Connection) -> Result<Self, Error> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS waypoints (
+                symbol TEXT PRIMARY KEY,
+                system_symbol TEXT NOT NULL,
+                charted INTEGER NOT NULL,
+                waypoint TEXT NOT NULL,
+                recorded_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS waypoints_by_system
+                ON waypoints (system_symbol, charted);
+            CREATE TABLE IF NOT EXISTS jump_gates (
+                symbol TEXT PRIMARY KEY,
+                system_symbol TEXT NOT NULL,
+                jump_gate TEXT NOT NULL,
+                recorded_at INTEGER NOT NULL
+            );",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn record_waypoints(&self, waypoints: &[Waypoint]) -> Result<usize, Error> {
+        //! adds or updates each waypoint, unless we already have it charted
+        //! and this copy isn't
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for waypoint in waypoints {
+            tx.execute(
+                "INSERT INTO waypoints (symbol, system_symbol, charted, waypoint, recorded_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT (symbol) DO UPDATE SET
+                    charted = excluded.charted,
+                    waypoint = excluded.waypoint,
+                    recorded_at = excluded.recorded_at
+                WHERE excluded.charted >= waypoints.charted",
+                params![
+                    waypoint.symbol.as_str(),
+                    waypoint.system_symbol.as_str(),
+                    is_charted(waypoint),
+                    serde_json::to_string(waypoint)?,
+                    Utc::now().timestamp_millis(),
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(waypoints.len())
+    }
+
+    pub fn record_waypoint(&self, waypoint: &Waypoint) -> Result<(), Error> {
+        self.record_waypoints(std::slice::from_ref(waypoint))?;
+        Ok(())
+    }
+
+    pub fn waypoint(&self, symbol: &WaypointSymbol) -> Result<Option<Waypoint>, Error> {
+        let conn = self.conn.lock().unwrap();
+        let json: Option<String> = conn
+            .query_row(
+                "SELECT waypoint FROM waypoints WHERE symbol = ?1",
+                params![symbol.as_str()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(json.map(|json| serde_json::from_str(&json)).transpose()?)
+    }
+
+    fn query_waypoints(
+        &self,
+        sql: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<Waypoint>, Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn.prepare(sql)?;
+        let rows = statement.query_map(params, |row| row.get::<_, String>(0))?;
+        let mut waypoints = Vec::new();
+        for json in rows {
+            waypoints.push(serde_json::from_str(&json?)?);
+        }
+        Ok(waypoints)
+    }
+
+    pub fn waypoints_in_system(&self, system: &SystemSymbol) -> Result<Vec<Waypoint>, Error> {
+        self.query_waypoints(
+            "SELECT waypoint FROM waypoints WHERE system_symbol = ?1 ORDER BY symbol",
+            params![system.as_str()],
+        )
+    }
+
+    pub fn uncharted_in_system(&self, system: &SystemSymbol) -> Result<Vec<Waypoint>, Error> {
+        self.query_waypoints(
+            "SELECT waypoint FROM waypoints WHERE system_symbol = ?1 AND NOT charted
+            ORDER BY symbol",
+            params![system.as_str()],
+        )
+    }
+
+    fn query_systems(&self, sql: &str) -> Result<Vec<SystemSymbol>, Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn.prepare(sql)?;
+        let rows = statement.query_map([], |row| row.get::<_, String>(0))?;
+        let mut systems = Vec::new();
+        for system in rows {
+            systems.push(system?.parse()?);
+        }
+        Ok(systems)
+    }
+
+    pub fn systems(&self) -> Result<Vec<SystemSymbol>, Error> {
+        //! every system we've recorded a waypoint in
+        self.query_systems("SELECT DISTINCT system_symbol FROM waypoints ORDER BY system_symbol")
+    }
+
+    pub fn uncharted_systems(&self) -> Result<Vec<SystemSymbol>, Error> {
+        //! systems with a waypoint we know of that nobody's charted yet
+        self.query_systems(
+            "SELECT DISTINCT system_symbol FROM waypoints WHERE NOT charted
+            ORDER BY system_symbol",
+        )
+    }
+
+    pub fn record_jump_gate(
+        &self,
+        symbol: &WaypointSymbol,
+        jump_gate: &JumpGate,
+    ) -> Result<(), Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO jump_gates (symbol, system_symbol, jump_gate, recorded_at)
+            VALUES (?1, ?2, ?3, ?4)",
+            params![
+                symbol.as_str(),
+                symbol.system().as_str(),
+                serde_json::to_string(jump_gate)?,
+                Utc::now().timestamp_millis(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn jump_gate(&self, symbol: &WaypointSymbol) -> Result<Option<JumpGate>, Error> {
+        let conn = self.conn.lock().unwrap();
+        let json: Option<String> = conn
+            .query_row(
+                "SELECT jump_gate FROM jump_gates WHERE symbol = ?1",
+                params![symbol.as_str()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(json.map(|json| serde_json::from_str(&json)).transpose()?)
+    }
+
+    pub fn jump_gates(&self) -> Result<Vec<(WaypointSymbol, JumpGate)>, Error> {
+        //! every gate we know of, by the waypoint it's at
+        let conn = self.conn.lock().unwrap();
+        let mut statement =
+            conn.prepare("SELECT symbol, jump_gate FROM jump_gates ORDER BY symbol")?;
+        let rows = statement.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut gates = Vec::new();
+        for row in rows {
+            let (symbol, json) = row?;
+            gates.push((symbol.parse()?, serde_json::from_str(&json)?));
+        }
+        Ok(gates)
+    }
+
+    pub fn jump_route(
+        &self,
+        from: &SystemSymbol,
+        wanted: impl Fn(&SystemSymbol) -> bool,
+    ) -> Result<Option<Vec<SystemSymbol>>, Error> {
+        //! the systems to jump to, in order, to reach the nearest wanted one
+        //! through the gates we know of. Some(vec![]) if from is wanted
+        //! itself, and None if no wanted system can be reached
+        let mut links: HashMap<SystemSymbol, Vec<SystemSymbol>> = HashMap::new();
+        for (symbol, gate) in self.jump_gates()? {
+            let connected = gate
+                .connected_systems
+                .iter()
+                .map(|system| system.symbol.clone());
+            links.entry(symbol.system()).or_default().extend(connected);
+        }
+
+        let mut came_from: HashMap<SystemSymbol, SystemSymbol> = HashMap::new();
+        let mut seen = HashSet::from([from.clone()]);
+        let mut queue = VecDeque::from([from.clone()]);
+        while let Some(system) = queue.pop_front() {
+            if wanted(&system) {
+                let mut route = vec![system];
+                while let Some(previous) = came_from.get(route.last().unwrap()) {
+                    route.push(previous.clone());
+                }
+                route.pop();
+                route.reverse();
+                return Ok(Some(route));
+            }
+            for next in links.get(&system).into_iter().flatten() {
+                if seen.insert(next.clone()) {
+                    came_from.insert(next.clone(), system.clone());
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+        Ok(None)
+    }
+}
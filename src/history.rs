@@ -0,0 +1,365 @@
+/*
+The history module keeps every market snapshot we've fetched.
+
+A Market's trade goods are only a point-in-time view of prices, and
+they're gone as soon as the next request comes in. Each snapshot is
+appended to a SQLite table with the time it was recorded, so traders
+can look at the latest prices everywhere without revisiting markets.
+
+Shipyards are the same, listing prices only while one of our ships is
+there, so what ships they had and for how much goes in a table of its
+own.
+*/
+
+use crate::api::{schema, Error};
+use chrono::{DateTime, TimeZone, Utc};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+#[derive(Debug)]
+pub struct PriceRecord {
+    pub waypoint_symbol: schema::WaypointSymbol,
+    pub trade_symbol: schema::TradeSymbol,
+    pub supply: schema::SupplyLevel,
+    pub purchase_price: u32,
+    pub sell_price: u32,
+    pub trade_volume: u32,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl PriceRecord {
+    pub fn from_market(market: &schema::Market, recorded_at: DateTime<Utc>) -> Vec<Self> {
+        market
+            .trade_goods
+            .iter()
+            .map(|good| Self {
+                waypoint_symbol: market.symbol.clone(),
+                trade_symbol: good.symbol.clone(),
+                supply: good.supply.clone(),
+                purchase_price: good.purchase_price,
+                sell_price: good.sell_price,
+                trade_volume: good.trade_volume,
+                recorded_at,
+            })
+            .collect()
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            waypoint_symbol: from_text(row, 0)?,
+            trade_symbol: from_text(row, 1)?,
+            supply: from_text(row, 2)?,
+            purchase_price: row.get(3)?,
+            sell_price: row.get(4)?,
+            trade_volume: row.get(5)?,
+            recorded_at: Utc.timestamp_millis_opt(row.get(6)?).unwrap(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ShipPriceRecord {
+    pub waypoint_symbol: schema::WaypointSymbol,
+    pub ship_type: schema::ShipType,
+    pub purchase_price: u32,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl ShipPriceRecord {
+    pub fn from_shipyard(shipyard: &schema::Shipyard, recorded_at: DateTime<Utc>) -> Vec<Self> {
+        shipyard
+            .ships
+            .iter()
+            .filter_map(|ship| {
+                Some(Self {
+                    waypoint_symbol: shipyard.symbol.clone(),
+                    ship_type: ship.kind.clone()?,
+                    purchase_price: ship.purchase_price.max(0) as u32,
+                    recorded_at,
+                })
+            })
+            .collect()
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            waypoint_symbol: from_text(row, 0)?,
+            ship_type: from_text(row, 1)?,
+            purchase_price: row.get(2)?,
+            recorded_at: Utc.timestamp_millis_opt(row.get(3)?).unwrap(),
+        })
+    }
+}
+
+const COLUMNS: &str =
+    "waypoint_symbol, trade_symbol, supply, purchase_price, sell_price, trade_volume, recorded_at";
+
+// only keeps the newest row for each good at each waypoint
+const LATEST: &str = "recorded_at = (
+    SELECT MAX(recorded_at) FROM prices AS newer
+    WHERE newer.waypoint_symbol = prices.waypoint_symbol
+    AND newer.trade_symbol = prices.trade_symbol
+)";
+
+// symbols are stored as they are, and the enums as their SCREAMING_SNAKE_CASE names
+fn from_text<T>(row: &Row, index: usize) -> rusqlite::Result<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static, {
+    let text: String = row.get(index)?;
+    text.parse()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+pub struct PriceHistory {
+    conn: Mutex<Connection>,
+}
+
+impl PriceHistory {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Error> { Self::init(Connection::open_in_memory()?) }
+
+    fn init(conn: Connection) -> Result<Self, Error> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS prices (
+                waypoint_symbol TEXT NOT NULL,
+                system_symbol TEXT NOT NULL,
+                trade_symbol TEXT NOT NULL,
+                supply TEXT NOT NULL,
+                purchase_price INTEGER NOT NULL,
+                sell_price INTEGER NOT NULL,
+                trade_volume INTEGER NOT NULL,
+                recorded_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS prices_by_good
+                ON prices (trade_symbol, waypoint_symbol, recorded_at);
+            CREATE INDEX IF NOT EXISTS prices_by_system
+                ON prices (system_symbol, trade_symbol);
+            CREATE TABLE IF NOT EXISTS ship_prices (
+                waypoint_symbol TEXT NOT NULL,
+                system_symbol TEXT NOT NULL,
+                ship_type TEXT NOT NULL,
+                purchase_price INTEGER NOT NULL,
+                recorded_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS ship_prices_by_system
+                ON ship_prices (system_symbol, ship_type);",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn record(&self, market: &schema::Market) -> Result<usize, Error> {
+        self.record_at(market, Utc::now())
+    }
+
+    pub fn record_at(
+        &self,
+        market: &schema::Market,
+        recorded_at: DateTime<Utc>,
+    ) -> Result<usize, Error> {
+        //! markets only list trade goods while one of our ships is present,
+        //! so this may well record nothing
+        let records = PriceRecord::from_market(market, recorded_at);
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for record in &records {
+            tx.execute(
+                "INSERT INTO prices (waypoint_symbol, system_symbol, trade_symbol, supply,
+                    purchase_price, sell_price, trade_volume, recorded_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    record.waypoint_symbol.as_str(),
+                    record.waypoint_symbol.system().as_str(),
+                    record.trade_symbol.as_str(),
+                    record.supply.as_str(),
+                    record.purchase_price,
+                    record.sell_price,
+                    record.trade_volume,
+                    record.recorded_at.timestamp_millis(),
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(records.len())
+    }
+
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<PriceRecord>, Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn.prepare(sql)?;
+        let records = statement.query_map(params, PriceRecord::from_row)?;
+        Ok(records.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn latest_prices(
+        &self,
+        waypoint_symbol: &schema::WaypointSymbol,
+    ) -> Result<Vec<PriceRecord>, Error> {
+        //! the newest price of every good ever seen at a waypoint
+        self.query(
+            &format!(
+                "SELECT {COLUMNS} FROM prices WHERE waypoint_symbol = ?1 AND {LATEST}
+                ORDER BY trade_symbol"
+            ),
+            params![waypoint_symbol.as_str()],
+        )
+    }
+
+    pub fn latest_price(
+        &self,
+        waypoint_symbol: &schema::WaypointSymbol,
+        trade_symbol: schema::TradeSymbol,
+    ) -> Result<Option<PriceRecord>, Error> {
+        let conn = self.conn.lock().unwrap();
+        let record = conn
+            .query_row(
+                &format!(
+                    "SELECT {COLUMNS} FROM prices WHERE waypoint_symbol = ?1 AND trade_symbol = ?2
+                    ORDER BY recorded_at DESC LIMIT 1"
+                ),
+                params![waypoint_symbol.as_str(), trade_symbol.as_str()],
+                PriceRecord::from_row,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    pub fn latest_in_system(
+        &self,
+        system_symbol: &schema::SystemSymbol,
+    ) -> Result<Vec<PriceRecord>, Error> {
+        //! the newest price of every good at every market we know in a system
+        self.query(
+            &format!(
+                "SELECT {COLUMNS} FROM prices WHERE system_symbol = ?1 AND {LATEST}
+                ORDER BY waypoint_symbol, trade_symbol"
+            ),
+            params![system_symbol.as_str()],
+        )
+    }
+
+    pub fn history(
+        &self,
+        waypoint_symbol: &schema::WaypointSymbol,
+        trade_symbol: schema::TradeSymbol,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<PriceRecord>, Error> {
+        //! every recorded price of a good at a waypoint, oldest first
+        let since = since.map_or(i64::MIN, |since| since.timestamp_millis());
+        self.query(
+            &format!(
+                "SELECT {COLUMNS} FROM prices
+                WHERE waypoint_symbol = ?1 AND trade_symbol = ?2 AND recorded_at >= ?3
+                ORDER BY recorded_at"
+            ),
+            params![waypoint_symbol.as_str(), trade_symbol.as_str(), since],
+        )
+    }
+
+    pub fn best_buy(
+        &self,
+        system_symbol: &schema::SystemSymbol,
+        trade_symbol: schema::TradeSymbol,
+    ) -> Result<Option<PriceRecord>, Error> {
+        //! the market in a system that last sold a good to us the cheapest
+        self.best(system_symbol, trade_symbol, "purchase_price ASC")
+    }
+
+    pub fn best_sell(
+        &self,
+        system_symbol: &schema::SystemSymbol,
+        trade_symbol: schema::TradeSymbol,
+    ) -> Result<Option<PriceRecord>, Error> {
+        //! the market in a system that last paid the most for a good
+        self.best(system_symbol, trade_symbol, "sell_price DESC")
+    }
+
+    fn best(
+        &self,
+        system_symbol: &schema::SystemSymbol,
+        trade_symbol: schema::TradeSymbol,
+        order: &str,
+    ) -> Result<Option<PriceRecord>, Error> {
+        let records = self.query(
+            &format!(
+                "SELECT {COLUMNS} FROM prices
+                WHERE system_symbol = ?1 AND trade_symbol = ?2 AND {LATEST}
+                ORDER BY {order} LIMIT 1"
+            ),
+            params![system_symbol.as_str(), trade_symbol.as_str()],
+        )?;
+        Ok(records.into_iter().next())
+    }
+
+    pub fn record_shipyard(&self, shipyard: &schema::Shipyard) -> Result<usize, Error> {
+        self.record_shipyard_at(shipyard, Utc::now())
+    }
+
+    pub fn record_shipyard_at(
+        &self,
+        shipyard: &schema::Shipyard,
+        recorded_at: DateTime<Utc>,
+    ) -> Result<usize, Error> {
+        //! like record_at, this only has anything to record while one of
+        //! our ships is at the shipyard
+        let records = ShipPriceRecord::from_shipyard(shipyard, recorded_at);
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for record in &records {
+            tx.execute(
+                "INSERT INTO ship_prices (waypoint_symbol, system_symbol, ship_type,
+                    purchase_price, recorded_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    record.waypoint_symbol.as_str(),
+                    record.waypoint_symbol.system().as_str(),
+                    record.ship_type.as_str(),
+                    record.purchase_price,
+                    record.recorded_at.timestamp_millis(),
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(records.len())
+    }
+
+    pub fn ship_prices_in_system(
+        &self,
+        system_symbol: &schema::SystemSymbol,
+    ) -> Result<Vec<ShipPriceRecord>, Error> {
+        //! the newest price of every ship type at every shipyard we know in
+        //! a system, cheapest first
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn.prepare(
+            "SELECT waypoint_symbol, ship_type, purchase_price, recorded_at FROM ship_prices
+            WHERE system_symbol = ?1 AND recorded_at = (
+                SELECT MAX(recorded_at) FROM ship_prices AS newer
+                WHERE newer.waypoint_symbol = ship_prices.waypoint_symbol
+                AND newer.ship_type = ship_prices.ship_type
+            )
+            ORDER BY purchase_price, waypoint_symbol",
+        )?;
+        let records =
+            statement.query_map(params![system_symbol.as_str()], ShipPriceRecord::from_row)?;
+        Ok(records.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn best_ship_price(
+        &self,
+        system_symbol: &schema::SystemSymbol,
+        ship_type: schema::ShipType,
+    ) -> Result<Option<ShipPriceRecord>, Error> {
+        //! the shipyard in a system that last had a ship type the cheapest
+        let records = self.ship_prices_in_system(system_symbol)?;
+        Ok(records
+            .into_iter()
+            .find(|record| record.ship_type == ship_type))
+    }
+}
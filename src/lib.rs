@@ -1,6 +1,22 @@
-use reqwest;
 pub mod api;
-use api::results::ApiResult;
+pub mod behavior;
+pub mod cache;
+pub mod capabilities;
+pub mod contracts;
+pub mod crew;
+pub mod expansion;
+pub mod fleet;
+pub mod galaxy;
+pub mod history;
+pub mod loadout;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod navigation;
+pub mod recording;
+#[cfg(feature = "mock")]
+pub mod sim;
+pub mod state;
+pub mod trading;
 
 #[derive(Debug)]
 pub struct SpaceTraderAgent {
@@ -13,7 +29,7 @@ impl SpaceTraderAgent {
     pub fn new(token: String) -> Self {
         Self {
             client: reqwest::Client::new(),
-            token: token,
+            token,
         }
     }
 
@@ -22,34 +38,53 @@ impl SpaceTraderAgent {
     #[tokio::main]
     pub async fn register(
         symbol: &str,
-        faction: Option<&str>,
+        faction: Option<&api::schema::FactionSymbol>,
         client: Option<reqwest::Client>,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
+    ) -> Result<Self, api::Error> {
         //! symbol should be a unique alphanumeric identifier, otherwise known as an agent
         //!
         //! faction can be any of "COSMIC", "VOID", "GALACTIC", or "QUANTUM" at the time
         //! of writing. COSMIC is said to be the default faction
-        let client = client.unwrap_or_else(reqwest::Client::new);
-        let params: [(&str, &str); 2] =
-            [("symbol", symbol), ("faction", faction.unwrap_or("COSMIC"))];
-        let result = client
-            .post("https://api.spacetraders.io/v2/register")
-            .header("Content-Type", "application/json")
-            .form(&params)
-            .send()
-            .await?
-            .json::<ApiResult<i32>>()
-            .await?
-            .to_result()?;
+        let client = client.unwrap_or_default();
+        let api = api::SpaceTraderApi::new(String::new()).with_transport(
+            api::transport::ReqwestTransport::with_client(client.clone()),
+        );
+        let faction = match faction {
+            Some(faction) => faction.clone(),
+            None => "COSMIC".parse()?,
+        };
+        let result = api.register(symbol, &faction).await?;
 
         Ok(SpaceTraderAgent {
-            client: client,
-            token: "result.token".to_string(),
+            client,
+            token: result.token,
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    mod behavior;
+    mod cache;
+    mod capabilities;
+    mod conformance;
+    mod contracts;
+    mod crew;
+    mod expansion;
+    mod fidelity;
+    mod fixtures;
+    mod fleet;
+    mod galaxy;
+    mod history;
+    mod loadout;
+    #[cfg(feature = "mock")]
+    mod mock;
+    mod recording;
     mod schema;
+    #[cfg(feature = "mock")]
+    mod sim;
+    mod state;
+    mod symbols;
+    mod trading;
+    mod transport;
 }
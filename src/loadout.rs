@@ -0,0 +1,302 @@
+/*
+The loadout module checks whether a combination of ship components
+fits together.
+
+Frames, reactors, engines, modules and mounts each need some power,
+crew and slots. The reactor's power_output has to cover the power, the
+crew quarters have to hold the crew, modules have to fit the frame's
+module_slots and mounts its mounting_points. A Loadout is the parts
+list, taken from a ship we own or one a shipyard's selling, and the
+with_* and without_* methods swap parts in and out to plan a refit.
+check() says what it adds up to, and for anything over the limit,
+which parts are taking it.
+*/
+
+use crate::api::schema::{
+    Ship, ShipEngine, ShipFrame, ShipModule, ShipModuleSymbols, ShipMount, ShipMountSymbols,
+    ShipReactor, ShipRequirements, ShipyardShip,
+};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resource {
+    Power,
+    Crew,
+    ModuleSlots,
+    MountingPoints,
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Resource::Power => "power",
+            Resource::Crew => "crew",
+            Resource::ModuleSlots => "module slots",
+            Resource::MountingPoints => "mounting points",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    pub resource: Resource,
+    pub needed: i32,
+    pub available: i32,
+    /// each part that takes some, most first
+    pub takers: Vec<(String, i32)>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "needs {} {} but only has {}",
+            self.needed, self.resource, self.available
+        )?;
+        let takers: Vec<String> = self
+            .takers
+            .iter()
+            .map(|(part, amount)| format!("{} {}", part, amount))
+            .collect();
+        if !takers.is_empty() {
+            write!(f, " ({})", takers.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadoutReport {
+    pub power: (i32, i32),
+    pub crew: (i32, i32),
+    pub module_slots: (i32, i32),
+    pub mounting_points: (i32, i32),
+    pub violations: Vec<Violation>,
+}
+
+impl LoadoutReport {
+    pub fn fits(&self) -> bool { self.violations.is_empty() }
+
+    pub fn spare(&self, resource: Resource) -> i32 {
+        //! what's left over, or how far over it is if negative
+        let (used, available) = match resource {
+            Resource::Power => self.power,
+            Resource::Crew => self.crew,
+            Resource::ModuleSlots => self.module_slots,
+            Resource::MountingPoints => self.mounting_points,
+        };
+        available - used
+    }
+}
+
+impl fmt::Display for LoadoutReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.fits() { "FITS" } else { "DOESN'T FIT" };
+        write!(
+            f,
+            "{}: power {}/{}, crew {}/{}, modules {}/{}, mounts {}/{}",
+            verdict,
+            self.power.0,
+            self.power.1,
+            self.crew.0,
+            self.crew.1,
+            self.module_slots.0,
+            self.module_slots.1,
+            self.mounting_points.0,
+            self.mounting_points.1
+        )?;
+        for violation in &self.violations {
+            write!(f, "\n  - {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Loadout {
+    pub frame: ShipFrame,
+    pub reactor: ShipReactor,
+    pub engine: ShipEngine,
+    pub modules: Vec<ShipModule>,
+    pub mounts: Vec<ShipMount>,
+    /// how many crew it has room for
+    pub crew_capacity: i32,
+}
+
+fn crew_quarters(module: &ShipModule) -> i32 {
+    match module.symbol {
+        ShipModuleSymbols::ModuleCrewQuartersI => module.capacity.unwrap_or(0) as i32,
+        _ => 0,
+    }
+}
+
+impl Loadout {
+    pub fn of(ship: &Ship) -> Self {
+        Self {
+            frame: ship.frame.clone(),
+            reactor: ship.reactor.clone(),
+            engine: ship.engine.clone(),
+            modules: ship.modules.clone(),
+            mounts: ship.mounts.clone(),
+            crew_capacity: ship.crew.capacity,
+        }
+    }
+
+    pub fn of_listing(ship: &ShipyardShip) -> Self {
+        //! a listing doesn't say how much crew it has room for, so that's
+        //! what its crew quarters hold
+        Self {
+            frame: ship.frame.clone(),
+            reactor: ship.reactor.clone(),
+            engine: ship.engine.clone(),
+            modules: ship.modules.clone(),
+            mounts: ship.mounts.clone(),
+            crew_capacity: ship.modules.iter().map(crew_quarters).sum(),
+        }
+    }
+
+    pub fn with_reactor(mut self, reactor: ShipReactor) -> Self {
+        self.reactor = reactor;
+        self
+    }
+
+    pub fn with_engine(mut self, engine: ShipEngine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    pub fn with_module(mut self, module: ShipModule) -> Self {
+        self.crew_capacity += crew_quarters(&module);
+        self.modules.push(module);
+        self
+    }
+
+    pub fn without_module(mut self, symbol: ShipModuleSymbols) -> Self {
+        //! takes out one module of that kind, if there is one
+        if let Some(index) = self.modules.iter().position(|m| m.symbol == symbol) {
+            let module = self.modules.remove(index);
+            self.crew_capacity -= crew_quarters(&module);
+        }
+        self
+    }
+
+    pub fn with_mount(mut self, mount: ShipMount) -> Self {
+        self.mounts.push(mount);
+        self
+    }
+
+    pub fn without_mount(mut self, symbol: ShipMountSymbols) -> Self {
+        //! takes out one mount of that kind, if there is one
+        if let Some(index) = self.mounts.iter().position(|m| m.symbol == symbol) {
+            self.mounts.remove(index);
+        }
+        self
+    }
+
+    fn parts(&self) -> Vec<(String, &ShipRequirements)> {
+        let mut parts = vec![
+            (self.frame.symbol.to_string(), &self.frame.requirements),
+            (self.reactor.symbol.to_string(), &self.reactor.requirements),
+            (self.engine.symbol.to_string(), &self.engine.requirements),
+        ];
+        parts.extend(
+            self.modules
+                .iter()
+                .map(|module| (module.symbol.to_string(), &module.requirements)),
+        );
+        parts.extend(
+            self.mounts
+                .iter()
+                .map(|mount| (mount.symbol.to_string(), &mount.requirements)),
+        );
+        parts
+    }
+
+    pub fn check(&self) -> LoadoutReport {
+        let requirement = |need: fn(&ShipRequirements) -> Option<i32>| {
+            tally(
+                self.parts()
+                    .into_iter()
+                    .map(|(part, requirements)| (part, need(requirements).unwrap_or(0))),
+            )
+        };
+        let power = requirement(|requirements| requirements.power);
+        let crew = requirement(|requirements| requirements.crew);
+        // modules say how many slots they take, or one apiece if they don't
+        let slots = tally(self.modules.iter().map(|module| {
+            (
+                module.symbol.to_string(),
+                module.requirements.slots.unwrap_or(1),
+            )
+        }));
+        let points = tally(
+            self.mounts
+                .iter()
+                .map(|mount| (mount.symbol.to_string(), 1)),
+        );
+
+        let mut report = LoadoutReport {
+            power: (0, self.reactor.power_output as i32),
+            crew: (0, self.crew_capacity),
+            module_slots: (0, self.frame.module_slots as i32),
+            mounting_points: (0, self.frame.mounting_points as i32),
+            violations: Vec::new(),
+        };
+        let checks = [
+            (Resource::Power, power, &mut report.power),
+            (Resource::Crew, crew, &mut report.crew),
+            (Resource::ModuleSlots, slots, &mut report.module_slots),
+            (
+                Resource::MountingPoints,
+                points,
+                &mut report.mounting_points,
+            ),
+        ];
+        let mut violations = Vec::new();
+        for (resource, takers, (used, available)) in checks {
+            *used = takers.iter().map(|(_, amount)| amount).sum();
+            if *used > *available {
+                violations.push(Violation {
+                    resource,
+                    needed: *used,
+                    available: *available,
+                    takers,
+                });
+            }
+        }
+        report.violations = violations;
+        report
+    }
+}
+
+fn tally(parts: impl Iterator<Item = (String, i32)>) -> Vec<(String, i32)> {
+    //! what each kind of part takes, most first, leaving out what takes none
+    let mut takers: Vec<(String, i32)> = Vec::new();
+    for (part, amount) in parts.filter(|(_, amount)| *amount != 0) {
+        match takers.iter_mut().find(|(name, _)| *name == part) {
+            Some((_, total)) => *total += amount,
+            None => takers.push((part, amount)),
+        }
+    }
+    takers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    takers
+}
+
+pub fn compare(ships: &[ShipyardShip]) -> Vec<(&ShipyardShip, LoadoutReport)> {
+    //! every listing checked, the ones that fit first, then the ones with
+    //! the most room to spare for refits, then the cheapest
+    let mut reports: Vec<_> = ships
+        .iter()
+        .map(|ship| (ship, Loadout::of_listing(ship).check()))
+        .collect();
+    let room = |report: &LoadoutReport| {
+        report.spare(Resource::ModuleSlots) + report.spare(Resource::MountingPoints)
+    };
+    reports.sort_by(|(a_ship, a), (b_ship, b)| {
+        b.fits()
+            .cmp(&a.fits())
+            .then_with(|| room(b).cmp(&room(a)))
+            .then_with(|| a_ship.purchase_price.cmp(&b_ship.purchase_price))
+    });
+    reports
+}
@@ -0,0 +1,414 @@
+/*
+The mock module runs a fake SpaceTraders server inside the test
+process, so the client can be tested end to end without touching
+the real API.
+
+It answers the v2 endpoints with the payloads in schema-examples/,
+wrapped in the same "data"/"meta"/"error" envelope the real server
+uses (see api::results), and can be scripted to fail, rate limit or
+stall specific requests. Only built with the "mock" feature.
+*/
+
+use crate::api::results::get_error_name;
+use crate::api::transport::{InMemoryTransport, Response as TransportResponse};
+use crate::api::Error;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+const FIXTURES: &[(&str, &str)] = &[
+    ("Contract", include_str!("../schema-examples/Contract.json")),
+    ("Faction", include_str!("../schema-examples/Faction.json")),
+    ("JumpGate", include_str!("../schema-examples/JumpGate.json")),
+    ("Market", include_str!("../schema-examples/Market.json")),
+    ("Ship", include_str!("../schema-examples/Ship.json")),
+    ("ShipNav", include_str!("../schema-examples/ShipNav.json")),
+    ("Shipyard", include_str!("../schema-examples/Shipyard.json")),
+    ("System", include_str!("../schema-examples/System.json")),
+    ("Waypoint", include_str!("../schema-examples/Waypoint.json")),
+];
+
+pub fn fixture(name: &str) -> Value {
+    //! the example payload for one of the schema types, e.g. "Ship"
+    let (_, text) = FIXTURES
+        .iter()
+        .find(|(fixture, _)| *fixture == name)
+        .unwrap_or_else(|| panic!("no fixture named {}", name));
+    serde_json::from_str(text).unwrap()
+}
+
+fn with_field(mut value: Value, field: &str, symbol: &str) -> Value {
+    // so a request for X1-DF55 gets back something that calls itself X1-DF55
+    value[field] = Value::String(symbol.to_owned());
+    value
+}
+
+#[derive(Debug)]
+enum Scripted {
+    Error(i32),
+    RateLimit(f64),
+    Response(u16, Value),
+    Delay(Duration),
+}
+
+#[derive(Default)]
+struct MockState {
+    scripted: VecDeque<(String, Scripted)>,
+    overrides: HashMap<String, Value>,
+    delay: Duration,
+    requests: Vec<String>,
+}
+
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    pub async fn start() -> Result<Self, Error> {
+        //! binds to a random local port and serves the fixtures until dropped
+        Self::start_with(Arc::new(Fixtures)).await
+    }
+
+    pub async fn start_with(backend: Arc<dyn Backend>) -> Result<Self, Error> {
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            let backend = backend.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    handle(state.clone(), backend.clone(), req)
+                }))
+            }
+        });
+        let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))?.serve(make_service);
+        let addr = server.local_addr();
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+        tokio::spawn(server.with_graceful_shutdown(async {
+            shutdown_rx.await.ok();
+        }));
+        Ok(Self {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    pub fn url(&self) -> String {
+        //! pass this to SpaceTraderApi::with_base_url
+        format!("http://{}/v2", self.addr)
+    }
+
+    fn push(&self, path: &str, scripted: Scripted) {
+        self.state
+            .lock()
+            .unwrap()
+            .scripted
+            .push_back((path.to_owned(), scripted));
+    }
+
+    // paths are relative to /v2, like "/my/ships/SHIP-1", and "*" matches any
+    // request. every push_ answers exactly one request, in the order pushed
+
+    pub fn push_error(&self, path: &str, code: i32) {
+        //! answers with one of the error codes from api::results
+        self.push(path, Scripted::Error(code));
+    }
+
+    pub fn push_rate_limit(&self, path: &str, retry_after: f64) {
+        self.push(path, Scripted::RateLimit(retry_after));
+    }
+
+    pub fn push_response(&self, path: &str, status: u16, body: Value) {
+        //! answers with body as-is, without wrapping it in "data"
+        self.push(path, Scripted::Response(status, body));
+    }
+
+    pub fn push_delay(&self, path: &str, delay: Duration) {
+        //! stalls the next matching request before answering it as usual
+        self.push(path, Scripted::Delay(delay));
+    }
+
+    pub fn set_delay(&self, delay: Duration) {
+        //! stalls every request
+        self.state.lock().unwrap().delay = delay;
+    }
+
+    pub fn set_data(&self, path: &str, data: Value) {
+        //! replaces the fixture served at path. arrays are paginated like lists
+        self.state
+            .lock()
+            .unwrap()
+            .overrides
+            .insert(path.to_owned(), data);
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        //! every request received so far, as "METHOD /path?query"
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+pub(crate) fn error(code: i32) -> (u16, Value) {
+    error_message(
+        code,
+        &format!("mock server scripted a {}", get_error_name(code)),
+    )
+}
+
+pub(crate) fn error_message(code: i32, message: &str) -> (u16, Value) {
+    let status = match code {
+        400..=599 => code as u16,
+        4000..=4999 => 400,
+        _ => 500,
+    };
+    let body = json!({
+        "error": {
+            "message": message,
+            "code": code,
+        }
+    });
+    (status, body)
+}
+
+pub(crate) fn page(items: Vec<Value>, query: &HashMap<String, String>) -> (u16, Value) {
+    let page = query
+        .get("page")
+        .and_then(|page| page.parse().ok())
+        .unwrap_or(1usize)
+        .max(1);
+    let limit = query
+        .get("limit")
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(10usize);
+    let total = items.len();
+    let data: Vec<Value> = items
+        .into_iter()
+        .skip((page - 1) * limit)
+        .take(limit)
+        .collect();
+    let body = json!({
+        "data": data,
+        "meta": { "total": total, "page": page, "limit": limit },
+    });
+    (200, body)
+}
+
+pub(crate) fn data(value: Value) -> (u16, Value) { (200, json!({ "data": value })) }
+
+pub struct MockRequest {
+    pub method: Method,
+    /// relative to /v2, e.g. "/my/ships/SHIP-1/orbit"
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// Null when the request didn't have one
+    pub body: Value,
+}
+
+impl MockRequest {
+    pub fn segments(&self) -> Vec<&str> {
+        self.path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect()
+    }
+}
+
+/// whatever actually answers the requests a MockServer receives, once
+/// scripted responses and set_data overrides have had their turn
+pub trait Backend: Send + Sync {
+    /// an HTTP status and the full JSON body, envelope included.
+    /// a 204 is sent without a body
+    fn handle(&self, request: &MockRequest) -> (u16, Value);
+}
+
+/// the default backend, serving the same fixture for every symbol
+pub struct Fixtures;
+
+impl Backend for Fixtures {
+    fn handle(&self, request: &MockRequest) -> (u16, Value) {
+        if request.method != Method::GET {
+            return error(404);
+        }
+        fixture_route(&request.segments(), &request.query)
+    }
+}
+
+fn fixture_route(segments: &[&str], query: &HashMap<String, String>) -> (u16, Value) {
+    match segments {
+        [] => (
+            200,
+            json!({
+                "status": "SpaceTraders is currently online and available to play",
+                "version": "v2",
+                "resetDate": "2023-05-20",
+                "description": "mock server",
+                "stats": {"agents": 1, "ships": 2, "systems": 1, "waypoints": 1},
+            }),
+        ),
+        ["systems"] => page(vec![fixture("System")], query),
+        ["systems", system] => data(with_field(fixture("System"), "symbol", system)),
+        ["systems", system, "waypoints"] => {
+            let waypoint = with_field(fixture("Waypoint"), "systemSymbol", system);
+            page(vec![waypoint], query)
+        }
+        ["systems", system, "waypoints", waypoint] => {
+            let value = with_field(fixture("Waypoint"), "systemSymbol", system);
+            data(with_field(value, "symbol", waypoint))
+        }
+        ["systems", _, "waypoints", waypoint, "market"] => {
+            data(with_field(fixture("Market"), "symbol", waypoint))
+        }
+        ["systems", _, "waypoints", waypoint, "shipyard"] => {
+            data(with_field(fixture("Shipyard"), "symbol", waypoint))
+        }
+        ["systems", _, "waypoints", _, "jump-gate"] => data(fixture("JumpGate")),
+        ["my", "ships"] => page(vec![fixture("Ship")], query),
+        ["my", "ships", ship] => data(with_field(fixture("Ship"), "symbol", ship)),
+        ["my", "ships", _, "nav"] => data(fixture("ShipNav")),
+        ["my", "contracts"] => page(vec![fixture("Contract")], query),
+        ["my", "contracts", id] => data(with_field(fixture("Contract"), "id", id)),
+        ["factions"] => page(vec![fixture("Faction")], query),
+        ["factions", faction] => data(with_field(fixture("Faction"), "symbol", faction)),
+        _ => error(404),
+    }
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .collect()
+}
+
+pub fn in_memory(backend: Arc<dyn Backend>) -> InMemoryTransport {
+    //! serves backend straight to a SpaceTraderApi, without a server in
+    //! between. scripting and set_data need a MockServer
+    InMemoryTransport::new(move |request| {
+        let (path, query) = request.path.split_once('?').unwrap_or((&request.path, ""));
+        let request = MockRequest {
+            method: request.method.clone(),
+            path: path.to_owned(),
+            query: parse_query(query),
+            body: request.body.clone().unwrap_or(Value::Null),
+        };
+        let (status, body) = backend.handle(&request);
+        let headers = [("content-type".to_owned(), "application/json".to_owned())].into();
+        let body = if status == 204 {
+            String::new()
+        } else {
+            body.to_string()
+        };
+        TransportResponse {
+            status,
+            headers,
+            body,
+        }
+    })
+}
+
+async fn handle(
+    state: Arc<Mutex<MockState>>,
+    backend: Arc<dyn Backend>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let method = req.method().clone();
+    let uri = req.uri().clone();
+    let path = uri.path().trim_start_matches("/v2").to_owned();
+    let query = parse_query(uri.query().unwrap_or_default());
+
+    let (delay, scripted) = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(format!("{} {}", method, uri));
+        let mut delay = state.delay;
+        let mut scripted = None;
+        // delays pile up until something scripted actually answers
+        while let Some(index) = state
+            .scripted
+            .iter()
+            .position(|(p, _)| p == "*" || *p == path)
+        {
+            match state.scripted.remove(index).map(|(_, scripted)| scripted) {
+                Some(Scripted::Delay(extra)) => delay += extra,
+                other => {
+                    scripted = other;
+                    break;
+                }
+            }
+        }
+        (delay, scripted)
+    };
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+    let request = MockRequest {
+        method,
+        path,
+        query,
+        body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+    };
+
+    let mut retry_after = None;
+    let (status, body) = match scripted {
+        Some(Scripted::Error(code)) => error(code),
+        Some(Scripted::RateLimit(seconds)) => {
+            retry_after = Some(seconds);
+            let body = json!({
+                "error": {
+                    "message": "You have reached your API limit. Please try again later.",
+                    "code": 429,
+                    "data": {
+                        "type": "IP",
+                        "retryAfter": seconds,
+                        "limitBurst": 10,
+                        "limitPerSecond": 2,
+                        "remaining": 0,
+                    }
+                }
+            });
+            (429, body)
+        }
+        Some(Scripted::Response(status, body)) => (status, body),
+        Some(Scripted::Delay(_)) | None => {
+            let overridden = state.lock().unwrap().overrides.get(&request.path).cloned();
+            match overridden {
+                Some(Value::Array(items)) => page(items, &request.query),
+                Some(value) => data(value),
+                None => backend.handle(&request),
+            }
+        }
+    };
+
+    let mut response = Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json");
+    if let Some(seconds) = retry_after {
+        response = response.header("Retry-After", seconds.ceil().to_string());
+    }
+    let body = if status == 204 {
+        Body::empty()
+    } else {
+        Body::from(body.to_string())
+    };
+    Ok(response.body(body).unwrap())
+}
@@ -0,0 +1,39 @@
+/*
+Estimates for getting from one waypoint to another within a system.
+
+These follow the formulas the game server uses for in-system travel;
+the server always has the final say, so treat them as estimates.
+*/
+
+use crate::api::schema::ShipNavFlightMode;
+use chrono::Duration;
+
+pub fn distance(from: (i32, i32), to: (i32, i32)) -> f64 {
+    let dx = (to.0 - from.0) as f64;
+    let dy = (to.1 - from.1) as f64;
+    (dx * dx + dy * dy).sqrt()
+}
+
+fn multiplier(flight_mode: ShipNavFlightMode) -> f64 {
+    match flight_mode {
+        ShipNavFlightMode::Burn => 7.5,
+        ShipNavFlightMode::Cruise => 15.0,
+        ShipNavFlightMode::Stealth => 30.0,
+        ShipNavFlightMode::Drift => 150.0,
+    }
+}
+
+pub fn travel_time(distance: f64, engine_speed: u32, flight_mode: ShipNavFlightMode) -> Duration {
+    let distance = distance.max(1.0).round();
+    let seconds = distance * multiplier(flight_mode) / engine_speed.max(1) as f64 + 15.0;
+    Duration::seconds(seconds.round() as i64)
+}
+
+pub fn fuel_cost(distance: f64, flight_mode: ShipNavFlightMode) -> u32 {
+    let distance = distance.round() as u32;
+    match flight_mode {
+        ShipNavFlightMode::Drift => 1,
+        ShipNavFlightMode::Burn => (distance * 2).max(2),
+        ShipNavFlightMode::Cruise | ShipNavFlightMode::Stealth => distance.max(1),
+    }
+}
@@ -0,0 +1,146 @@
+/*
+The recording module captures the traffic between the client and the
+server, so a run that went wrong can be played back offline.
+
+A Recorder attached to SpaceTraderApi (see with_recorder) appends one
+JSON line per request: method, path, request body, status, response
+headers, the raw response body and how long it took. The token is
+never written down.
+
+Replay plays a recording back as a client's transport (see
+SpaceTraderApi::with_transport), one response per request in the order
+they were recorded. Each comes back as it was sent, headers and raw
+body included, and takes as long as it took the first time, so the
+middleware above it (Retrying, RateLimited, Logged) behaves as it did.
+It complains loudly when the client goes off script.
+*/
+
+use crate::api::transport::{BoxFuture, Request, Response, Transport};
+use crate::api::Error;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Recording {
+    pub method: String,
+    /// relative to the base url, query included, e.g. "/my/ships?page=1&limit=20"
+    pub path: String,
+    pub request_body: Option<serde_json::Value>,
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    /// exactly what the server sent, which isn't always JSON
+    pub body: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+}
+
+pub struct Recorder {
+    file: Mutex<File>,
+}
+
+impl Recorder {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error> {
+        //! starts a new recording, replacing whatever was at path
+        Ok(Self::from_file(File::create(path)?))
+    }
+
+    pub fn append(path: impl AsRef<Path>) -> Result<Self, Error> {
+        //! keeps adding to the recording at path, creating it if needed
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::from_file(file))
+    }
+
+    fn from_file(file: File) -> Self {
+        Self {
+            file: Mutex::new(file),
+        }
+    }
+
+    pub fn record(&self, recording: &Recording) -> Result<(), Error> {
+        let line = serde_json::to_string(recording)?;
+        // one write per line, so a crash never leaves half a recording behind
+        self.file
+            .lock()
+            .unwrap()
+            .write_all(format!("{line}\n").as_bytes())?;
+        Ok(())
+    }
+}
+
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<Recording>, Error> {
+    //! every recording in the file at path, skipping blank lines
+    let mut recordings = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            recordings.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(recordings)
+}
+
+pub struct Replay {
+    recordings: Mutex<VecDeque<Recording>>,
+}
+
+impl Replay {
+    pub fn new(recordings: Vec<Recording>) -> Self {
+        Self {
+            recordings: Mutex::new(recordings.into()),
+        }
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> { Ok(Self::new(load(path)?)) }
+
+    pub fn remaining(&self) -> usize {
+        //! how many recordings haven't been asked for yet
+        self.recordings.lock().unwrap().len()
+    }
+}
+
+fn off_script(message: String) -> Response {
+    //! the error a request nobody recorded gets, as the server would put it
+    let body = json!({ "error": { "message": message, "code": 6000 } });
+    Response {
+        status: 400,
+        headers: BTreeMap::from([("content-type".to_owned(), "application/json".to_owned())]),
+        body: body.to_string(),
+    }
+}
+
+impl Transport for Replay {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, Error>> {
+        let next = self.recordings.lock().unwrap().pop_front();
+        Box::pin(async move {
+            let recording = match next {
+                Some(recording) => recording,
+                None => {
+                    return Ok(off_script(format!(
+                        "replay has nothing left for {} {}",
+                        request.method, request.path
+                    )))
+                }
+            };
+            if recording.method != request.method.as_str() || recording.path != request.path {
+                return Ok(off_script(format!(
+                    "replay expected {} {}, got {} {}",
+                    recording.method, recording.path, request.method, request.path
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(recording.duration_ms)).await;
+            Ok(Response {
+                status: recording.status,
+                headers: recording.headers,
+                body: recording.body,
+            })
+        })
+    }
+}
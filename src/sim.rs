@@ -0,0 +1,1449 @@
+/*
+The sim module is a small simulated SpaceTraders universe for testing
+bots offline. It plugs into mock::MockServer as a Backend, so the same
+SpaceTraderApi calls work against it as against the real server.
+
+It owns the agent, ships, waypoints, markets, shipyards, jump gates
+and contracts, and applies the core rules to them: travel takes time
+and fuel, extracting and surveying have a cooldown, prices move with
+every trade and drift back over time, and contracts pay out once
+every good is delivered. Uncharted waypoints stay that way until a
+ship charts them, and jump gates take a ship to the gate of any
+system they connect to. Error codes match the ones in api::results.
+
+Time only moves when you say so. The clock runs at time_scale times
+real time (0 by default) plus whatever advance() adds, so a bot can
+be run over hours of game time in a few seconds. Extraction yields
+come from a seeded rng, so the same requests always produce the same
+universe. Only built with the "mock" feature.
+*/
+
+use crate::api::schema::{
+    Agent, Contract, Market, MarketTradeGood, MarketTransaction, MarketTransactionTypes, Ship,
+    ShipCargoItem, ShipNavFlightMode, ShipNavRouteWaypoint, ShipNavStatus, ShipRole, ShipSymbol,
+    ShipType, Shipyard, ShipyardShip, ShipyardTransaction, SupplyLevel, System, SystemSymbol,
+    TradeSymbol, Waypoint, WaypointSymbol, WaypointTrait, WaypointTraitSymbol, WaypointType,
+};
+use crate::capabilities::ShipCapabilities;
+use crate::mock::{self, Backend, MockRequest};
+use crate::navigation;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+use tokio::time::Instant;
+
+/// how long a ship needs between extractions, or surveys
+pub const EXTRACTION_COOLDOWN: i64 = 70;
+/// how long a ship needs after jumping before it can jump again
+pub const JUMP_COOLDOWN: i64 = 60;
+/// how long a survey lasts before the server won't take it any more
+const SURVEY_LIFETIME: i64 = 15 * 60;
+/// how much fuel one unit of FUEL bought at a market is worth
+pub const FUEL_PER_UNIT: u32 = 100;
+/// how long it takes a market to get halfway back to normal supply
+const SUPPLY_HALF_LIFE: f64 = 3600.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GoodKind {
+    Import,
+    Export,
+    Exchange,
+}
+
+/// one good traded at a simulated market
+#[derive(Clone, Debug)]
+pub struct SimGood {
+    pub symbol: TradeSymbol,
+    pub kind: GoodKind,
+    /// the price in the middle of the spread at normal supply
+    pub base_price: u32,
+    pub trade_volume: u32,
+    /// 1.0 is normal. buying from the market lowers it and pushes the
+    /// price up, selling raises it and pushes the price down
+    pub supply: f64,
+}
+
+impl SimGood {
+    pub fn new(symbol: TradeSymbol, kind: GoodKind, base_price: u32) -> Self {
+        Self {
+            symbol,
+            kind,
+            base_price,
+            trade_volume: 10,
+            supply: 1.0,
+        }
+    }
+
+    fn mid_price(&self) -> f64 {
+        self.base_price as f64 * (1.5 - 0.5 * self.supply.clamp(0.0, 2.0))
+    }
+
+    pub fn purchase_price(&self) -> u32 { (self.mid_price() * 1.05).ceil() as u32 }
+
+    pub fn sell_price(&self) -> u32 { (self.mid_price() * 0.95).floor().max(1.0) as u32 }
+
+    pub fn supply_level(&self) -> SupplyLevel {
+        match self.supply {
+            s if s < 0.5 => SupplyLevel::Scarce,
+            s if s < 0.9 => SupplyLevel::Limited,
+            s if s < 1.3 => SupplyLevel::Moderate,
+            _ => SupplyLevel::Abundant,
+        }
+    }
+
+    fn trade(&mut self, units: u32, kind: &MarketTransactionTypes) {
+        let change = units as f64 / (self.trade_volume.max(1) as f64 * 10.0);
+        match kind {
+            MarketTransactionTypes::Purchase => self.supply = (self.supply - change).max(0.0),
+            MarketTransactionTypes::Sell => self.supply = (self.supply + change).min(2.0),
+        }
+    }
+}
+
+struct SimMarket {
+    goods: Vec<SimGood>,
+    transactions: Vec<MarketTransaction>,
+}
+
+struct SimShipyard {
+    ships: Vec<ShipyardShip>,
+    transactions: Vec<ShipyardTransaction>,
+}
+
+struct SimState {
+    start: DateTime<Utc>,
+    started: Instant,
+    time_scale: f64,
+    offset: Duration,
+    settled_at: DateTime<Utc>,
+    rng: u64,
+    agent: Agent,
+    // keyed by symbol, which can be looked up with the &str from a request path
+    systems: BTreeMap<SystemSymbol, System>,
+    waypoints: BTreeMap<WaypointSymbol, Waypoint>,
+    deposits: HashMap<WaypointSymbol, Vec<TradeSymbol>>,
+    markets: BTreeMap<WaypointSymbol, SimMarket>,
+    shipyards: BTreeMap<WaypointSymbol, SimShipyard>,
+    jump_gates: BTreeMap<WaypointSymbol, Vec<SystemSymbol>>,
+    ships: BTreeMap<ShipSymbol, Ship>,
+    // when each runs out, and how long it was
+    cooldowns: HashMap<ShipSymbol, (DateTime<Utc>, i64)>,
+    contracts: BTreeMap<String, Contract>,
+}
+
+type Outcome = Result<Value, i32>;
+
+fn to_json<T: Serialize>(value: &T) -> Value { serde_json::to_value(value).unwrap() }
+
+impl SimState {
+    fn now(&self) -> DateTime<Utc> {
+        let elapsed = self.started.elapsed().as_secs_f64() * self.time_scale;
+        self.start + Duration::milliseconds((elapsed * 1000.0) as i64) + self.offset
+    }
+
+    fn random(&mut self) -> f64 {
+        // xorshift64*, plenty for dice rolls
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        (self.rng.wrapping_mul(0x2545F4914F6CDD1D) >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn settle(&mut self) {
+        //! lands ships whose arrival has passed and lets supply recover
+        let now = self.now();
+        for ship in self.ships.values_mut() {
+            if matches!(ship.nav.status, ShipNavStatus::InTransit) && ship.nav.route.arrival <= now
+            {
+                ship.nav.status = ShipNavStatus::InOrbit;
+            }
+        }
+        self.cooldowns
+            .retain(|_, (expiration, _)| *expiration > now);
+
+        let elapsed = (now - self.settled_at).num_milliseconds() as f64 / 1000.0;
+        if elapsed > 0.0 {
+            let recovered = 1.0 - 0.5f64.powf(elapsed / SUPPLY_HALF_LIFE);
+            for market in self.markets.values_mut() {
+                for good in &mut market.goods {
+                    good.supply += (1.0 - good.supply) * recovered;
+                    // close enough, and it keeps prices from being off by one forever
+                    if (1.0 - good.supply).abs() < 0.001 {
+                        good.supply = 1.0;
+                    }
+                }
+            }
+            self.settled_at = now;
+        }
+    }
+
+    fn ship(&mut self, symbol: &str) -> Result<&mut Ship, i32> {
+        self.ships.get_mut(symbol).ok_or(404)
+    }
+
+    fn docked_ship(&mut self, symbol: &str) -> Result<&mut Ship, i32> {
+        let ship = self.ship(symbol)?;
+        match ship.nav.status {
+            ShipNavStatus::InTransit => Err(4214),
+            ShipNavStatus::InOrbit => Err(4244),
+            ShipNavStatus::Docked => Ok(ship),
+        }
+    }
+
+    fn orbiting_ship(&mut self, symbol: &str) -> Result<&mut Ship, i32> {
+        let ship = self.ship(symbol)?;
+        match ship.nav.status {
+            ShipNavStatus::InTransit => Err(4214),
+            ShipNavStatus::Docked => Err(4236),
+            ShipNavStatus::InOrbit => Ok(ship),
+        }
+    }
+
+    fn market_json(&self, symbol: &str) -> Option<Value> {
+        let market = self.markets.get(symbol)?;
+        let listed = |kind: GoodKind| -> Vec<Value> {
+            market
+                .goods
+                .iter()
+                .filter(|good| good.kind == kind)
+                .map(|good| json!({ "symbol": good.symbol, "name": good.symbol.to_string(), "description": "" }))
+                .collect()
+        };
+        // like the real thing, prices are only visible with a ship present
+        let (trade_goods, transactions) = if self.ship_present(symbol) {
+            let goods: Vec<MarketTradeGood> = market
+                .goods
+                .iter()
+                .map(|good| MarketTradeGood {
+                    symbol: good.symbol.clone(),
+                    trade_volume: good.trade_volume,
+                    supply: good.supply_level(),
+                    purchase_price: good.purchase_price(),
+                    sell_price: good.sell_price(),
+                })
+                .collect();
+            (to_json(&goods), to_json(&market.transactions))
+        } else {
+            (json!([]), json!([]))
+        };
+        Some(json!({
+            "symbol": symbol,
+            "exports": listed(GoodKind::Export),
+            "imports": listed(GoodKind::Import),
+            "exchange": listed(GoodKind::Exchange),
+            "transactions": transactions,
+            "tradeGoods": trade_goods,
+        }))
+    }
+
+    fn shipyard_json(&self, symbol: &str) -> Option<Value> {
+        let shipyard = self.shipyards.get(symbol)?;
+        let ship_types: Vec<Value> = shipyard
+            .ships
+            .iter()
+            .filter_map(|ship| ship.kind.clone())
+            .map(|kind| json!({ "type": kind }))
+            .collect();
+        let (ships, transactions) = if self.ship_present(symbol) {
+            (to_json(&shipyard.ships), to_json(&shipyard.transactions))
+        } else {
+            (json!([]), json!([]))
+        };
+        Some(json!({
+            "symbol": symbol,
+            "shipTypes": ship_types,
+            "transactions": transactions,
+            "ships": ships,
+        }))
+    }
+
+    fn ship_present(&self, waypoint: &str) -> bool {
+        self.ships.values().any(|ship| {
+            ship.nav.waypoint_symbol == waypoint
+                && !matches!(ship.nav.status, ShipNavStatus::InTransit)
+        })
+    }
+
+    fn cooldown_json(&self, ship_symbol: &str) -> Option<Value> {
+        let (expiration, total) = self.cooldowns.get(ship_symbol)?;
+        let remaining = (*expiration - self.now()).num_seconds().max(0);
+        Some(json!({
+            "shipSymbol": ship_symbol,
+            "totalSeconds": total,
+            "remainingSeconds": remaining,
+            "expiration": expiration,
+        }))
+    }
+
+    fn route_waypoint(&self, symbol: &str) -> Result<ShipNavRouteWaypoint, i32> {
+        let waypoint = self.waypoints.get(symbol).ok_or(4201)?;
+        Ok(ShipNavRouteWaypoint {
+            symbol: waypoint.symbol.clone(),
+            kind: waypoint.kind.clone(),
+            system_symbol: waypoint.system_symbol.clone(),
+            x: waypoint.x,
+            y: waypoint.y,
+        })
+    }
+
+    fn orbit(&mut self, ship_symbol: &str) -> Outcome {
+        let ship = self.ship(ship_symbol)?;
+        if matches!(ship.nav.status, ShipNavStatus::InTransit) {
+            return Err(4214);
+        }
+        ship.nav.status = ShipNavStatus::InOrbit;
+        Ok(json!({ "nav": ship.nav }))
+    }
+
+    fn dock(&mut self, ship_symbol: &str) -> Outcome {
+        let ship = self.ship(ship_symbol)?;
+        if matches!(ship.nav.status, ShipNavStatus::InTransit) {
+            return Err(4214);
+        }
+        ship.nav.status = ShipNavStatus::Docked;
+        Ok(json!({ "nav": ship.nav }))
+    }
+
+    fn navigate(&mut self, ship_symbol: &str, destination: &str) -> Outcome {
+        let now = self.now();
+        let to = self.route_waypoint(destination)?;
+        let ship = self.orbiting_ship(ship_symbol)?;
+        if ship.nav.waypoint_symbol == destination {
+            return Err(4204);
+        }
+        if ship.nav.system_symbol != to.system_symbol {
+            return Err(4202);
+        }
+        let from = self.route_waypoint(self.ships[ship_symbol].nav.waypoint_symbol.as_str())?;
+        let ship = self.ship(ship_symbol)?;
+        let distance = navigation::distance((from.x, from.y), (to.x, to.y));
+        let fuel = match ship.fuel.capacity {
+            // probes and the like don't use fuel at all
+            0 => 0,
+            _ => navigation::fuel_cost(distance, ship.nav.flight_mode),
+        };
+        if fuel > ship.fuel.current {
+            return Err(4203);
+        }
+        ship.fuel.current -= fuel;
+        ship.fuel.consumed =
+            Some(serde_json::from_value(json!({ "amount": fuel, "timestamp": now })).unwrap());
+        ship.nav.route.arrival =
+            now + navigation::travel_time(distance, ship.engine.speed, ship.nav.flight_mode);
+        ship.nav.route.departure_time = now;
+        ship.nav.route.departure = from;
+        ship.nav.route.destination = to;
+        ship.nav.waypoint_symbol = ship.nav.route.destination.symbol.clone();
+        ship.nav.status = ShipNavStatus::InTransit;
+        Ok(json!({ "fuel": ship.fuel, "nav": ship.nav }))
+    }
+
+    fn make_ship(&self, symbol: &ShipSymbol, role: ShipRole, waypoint: &str) -> Result<Ship, i32> {
+        let mut value = mock::fixture("Ship");
+        value["symbol"] = json!(symbol);
+        value["registration"] = json!({
+            "name": symbol,
+            "factionSymbol": "COSMIC",
+            "role": role,
+        });
+        let here = to_json(&self.route_waypoint(waypoint)?);
+        let now = self.now();
+        value["nav"] = json!({
+            "systemSymbol": here["systemSymbol"],
+            "waypointSymbol": waypoint,
+            "route": {
+                "destination": here,
+                "departure": here,
+                "departureTime": now,
+                "arrival": now,
+            },
+            "status": "DOCKED",
+            "flightMode": "CRUISE",
+        });
+        value["engine"]["speed"] = json!(30);
+        value["mounts"] = json!([]);
+        value["cargo"] = json!({ "capacity": 40, "units": 0, "inventory": [] });
+        value["fuel"] = json!({ "current": 400, "capacity": 400, "consumed": null });
+        Ok(serde_json::from_value(value).unwrap())
+    }
+
+    fn purchase_ship(&mut self, kind: ShipType, waypoint: &str) -> Outcome {
+        let now = self.now();
+        let shipyard = self.shipyards.get(waypoint).ok_or(404)?;
+        let listing = shipyard
+            .ships
+            .iter()
+            .find(|ship| ship.kind.as_ref() == Some(&kind))
+            .cloned()
+            .ok_or(422)?;
+        // like the prices, the ships are only for sale while one of ours is there
+        if !self.ship_present(waypoint) {
+            return Err(4001);
+        }
+        let price = listing.purchase_price.max(0) as u32;
+        if self.agent.credits < price as i64 {
+            return Err(4216);
+        }
+
+        let symbol: ShipSymbol = (1..)
+            .map(|n| format!("{}-{:X}", self.agent.symbol, n))
+            .find(|symbol| !self.ships.contains_key(symbol.as_str()))
+            .unwrap()
+            .parse()
+            .unwrap();
+        let role = match kind {
+            ShipType::ShipProbe => ShipRole::Satellite,
+            ShipType::ShipMiningDrone | ShipType::ShipOreHound => ShipRole::Excavator,
+            ShipType::ShipLightHauler | ShipType::ShipHeavyFreighter => ShipRole::Hauler,
+            ShipType::ShipLightShuttle => ShipRole::Transport,
+            ShipType::ShipExplorer => ShipRole::Explorer,
+            ShipType::ShipRefiningFreighter => ShipRole::Refinery,
+            ShipType::ShipInterceptor => ShipRole::Interceptor,
+            ShipType::ShipCommandFrigate => ShipRole::Command,
+            // nothing the simulator doesn't know is ever for sale
+            ShipType::Unknown(_) => return Err(422),
+        };
+        let mut ship = self.make_ship(&symbol, role, waypoint)?;
+        ship.cargo.capacity = ShipCapabilities::of_listing(&listing).cargo_capacity;
+        ship.fuel.capacity = listing.frame.fuel_capacity;
+        ship.fuel.current = listing.frame.fuel_capacity;
+        ship.frame = listing.frame;
+        ship.reactor = listing.reactor;
+        ship.engine = listing.engine;
+        ship.modules = listing.modules;
+        ship.mounts = listing.mounts;
+
+        self.agent.credits -= price as i64;
+        let transaction = ShipyardTransaction {
+            waypoint_symbol: ship.nav.waypoint_symbol.clone(),
+            ship_symbol: symbol.to_string(),
+            price,
+            agent_symbol: self.agent.symbol.clone(),
+            timestamp: now,
+        };
+        let shipyard = self.shipyards.get_mut(waypoint).unwrap();
+        shipyard.transactions.push(transaction.clone());
+        self.ships.insert(symbol, ship.clone());
+        Ok(json!({ "agent": self.agent, "ship": ship, "transaction": transaction }))
+    }
+
+    fn jump(&mut self, ship_symbol: &str, system: &str) -> Outcome {
+        let now = self.now();
+        if self.cooldowns.contains_key(ship_symbol) {
+            return Err(4000);
+        }
+        let ship = self.orbiting_ship(ship_symbol)?;
+        let from = ship.nav.waypoint_symbol.clone();
+        if ship.nav.system_symbol == system {
+            return Err(4208);
+        }
+        let connected = self.jump_gates.get(&from).ok_or(4211)?;
+        if !connected.iter().any(|connected| *connected == system) {
+            return Err(4207);
+        }
+        // ships come out at the gate on the other side
+        let gate = self
+            .waypoints
+            .values()
+            .find(|waypoint| {
+                waypoint.system_symbol == system && self.jump_gates.contains_key(&waypoint.symbol)
+            })
+            .ok_or(4207)?;
+        let to = self.route_waypoint(gate.symbol.as_str())?;
+        let from = self.route_waypoint(from.as_str())?;
+        let ship = self.ship(ship_symbol)?;
+        ship.nav.route.arrival = now;
+        ship.nav.route.departure_time = now;
+        ship.nav.route.departure = from;
+        ship.nav.route.destination = to;
+        ship.nav.system_symbol = ship.nav.route.destination.system_symbol.clone();
+        ship.nav.waypoint_symbol = ship.nav.route.destination.symbol.clone();
+        let expiration = now + Duration::seconds(JUMP_COOLDOWN);
+        self.cooldowns.insert(
+            self.ships[ship_symbol].symbol.clone(),
+            (expiration, JUMP_COOLDOWN),
+        );
+        Ok(json!({
+            "cooldown": self.cooldown_json(ship_symbol),
+            "nav": self.ships[ship_symbol].nav,
+        }))
+    }
+
+    fn jump_gate_json(&self, symbol: &str) -> Option<Value> {
+        let connected: Vec<Value> = self
+            .jump_gates
+            .get(symbol)?
+            .iter()
+            .filter_map(|system| self.systems.get(system))
+            .map(|system| {
+                json!({
+                    "symbol": system.symbol,
+                    "sectorSymbol": system.sector_symbol,
+                    "type": system.kind,
+                    "factionSymbol": "COSMIC",
+                    "x": system.x,
+                    "y": system.y,
+                    "distance": 0,
+                })
+            })
+            .collect();
+        Some(json!({
+            "jumpRange": 2000,
+            "factionSymbol": "COSMIC",
+            "connectedSystems": connected,
+        }))
+    }
+
+    fn chart(&mut self, ship_symbol: &str) -> Outcome {
+        let now = self.now();
+        let agent = self.agent.symbol.clone();
+        let ship = self.ship(ship_symbol)?;
+        if matches!(ship.nav.status, ShipNavStatus::InTransit) {
+            return Err(4214);
+        }
+        let symbol = ship.nav.waypoint_symbol.clone();
+        let waypoint = self.waypoints.get_mut(&symbol).ok_or(4201)?;
+        let uncharted = |t: &WaypointTrait| t.symbol == WaypointTraitSymbol::Uncharted;
+        if !waypoint.traits.iter().any(uncharted) {
+            return Err(4230);
+        }
+        waypoint.traits.retain(|t| !uncharted(t));
+        let chart = json!({
+            "waypointSymbol": symbol,
+            "submittedBy": agent,
+            "submittedOn": now,
+        });
+        waypoint.chart = Some(serde_json::from_value(chart.clone()).unwrap());
+        Ok(json!({ "chart": chart, "waypoint": waypoint }))
+    }
+
+    fn set_flight_mode(&mut self, ship_symbol: &str, flight_mode: ShipNavFlightMode) -> Outcome {
+        let ship = self.ship(ship_symbol)?;
+        ship.nav.flight_mode = flight_mode;
+        Ok(to_json(&ship.nav))
+    }
+
+    fn refuel(&mut self, ship_symbol: &str) -> Outcome {
+        let ship = self.ship(ship_symbol)?;
+        if !matches!(ship.nav.status, ShipNavStatus::Docked) {
+            return Err(4225);
+        }
+        let waypoint = ship.nav.waypoint_symbol.clone();
+        let missing = ship.fuel.capacity - ship.fuel.current;
+        let units = missing.div_ceil(FUEL_PER_UNIT);
+        let good = self
+            .markets
+            .get(&waypoint)
+            .and_then(|market| {
+                market
+                    .goods
+                    .iter()
+                    .find(|good| good.symbol == TradeSymbol::Fuel)
+            })
+            .ok_or(4226)?;
+        let total = good.purchase_price() * units;
+        if total as i64 > self.agent.credits {
+            return Err(4600);
+        }
+        if units > 0 {
+            self.trade(
+                ship_symbol,
+                &waypoint,
+                TradeSymbol::Fuel,
+                units,
+                MarketTransactionTypes::Purchase,
+            );
+        }
+        let ship = self.ship(ship_symbol)?;
+        ship.fuel.current = ship.fuel.capacity;
+        Ok(json!({ "agent": self.agent, "fuel": self.ships[ship_symbol].fuel }))
+    }
+
+    fn extract(&mut self, ship_symbol: &str, survey: &Value) -> Outcome {
+        let now = self.now();
+        if self.cooldowns.contains_key(ship_symbol) {
+            return Err(4000);
+        }
+        let ship = self.orbiting_ship(ship_symbol)?;
+        let waypoint = ship.nav.waypoint_symbol.clone();
+        let capabilities = ShipCapabilities::of(ship);
+        if !capabilities.can_mine {
+            return Err(4227);
+        }
+        let strength = capabilities.mining_strength;
+        let reachable: Vec<TradeSymbol> = capabilities
+            .deposits
+            .keys()
+            .map(|deposit| deposit.trade_symbol())
+            .collect();
+        let free = ship.cargo.capacity - ship.cargo.units;
+        if free == 0 {
+            return Err(4228);
+        }
+
+        let mut candidates: Vec<TradeSymbol> = self.deposits.get(&waypoint).cloned().ok_or(4205)?;
+        if !survey.is_null() {
+            if survey["symbol"].as_str() != Some(waypoint.as_str()) {
+                return Err(4220);
+            }
+            let expiration = survey["expiration"]
+                .as_str()
+                .map(DateTime::parse_from_rfc3339);
+            if matches!(expiration, Some(Ok(expiration)) if expiration < now) {
+                return Err(4221);
+            }
+            // a survey narrows things down to what it found, duplicates and all
+            candidates = survey["deposits"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|deposit| serde_json::from_value(deposit["symbol"].clone()).ok())
+                .collect();
+        }
+        if !reachable.is_empty() {
+            candidates.retain(|symbol| reachable.contains(symbol));
+        }
+        if candidates.is_empty() {
+            return Err(4205);
+        }
+
+        let symbol = candidates
+            [(self.random() * candidates.len() as f64) as usize % candidates.len()]
+        .clone();
+        let units = ((strength as f64 * (0.3 + 0.4 * self.random())).round() as u32).clamp(1, free);
+        self.add_cargo(ship_symbol, symbol.clone(), units);
+        let expiration = now + Duration::seconds(EXTRACTION_COOLDOWN);
+        self.cooldowns.insert(
+            self.ships[ship_symbol].symbol.clone(),
+            (expiration, EXTRACTION_COOLDOWN),
+        );
+        Ok(json!({
+            "cooldown": self.cooldown_json(ship_symbol),
+            "extraction": {
+                "shipSymbol": ship_symbol,
+                "yield": { "symbol": symbol, "units": units },
+            },
+            "cargo": self.ships[ship_symbol].cargo,
+        }))
+    }
+
+    fn survey(&mut self, ship_symbol: &str) -> Outcome {
+        let now = self.now();
+        if self.cooldowns.contains_key(ship_symbol) {
+            return Err(4000);
+        }
+        let ship = self.orbiting_ship(ship_symbol)?;
+        let waypoint = ship.nav.waypoint_symbol.clone();
+        let capabilities = ShipCapabilities::of(ship);
+        if !capabilities.can_survey {
+            return Err(4240);
+        }
+        let deposits = self.deposits.get(&waypoint).cloned().ok_or(4222)?;
+
+        // one survey per surveyor, each a handful of draws from the field
+        let mut surveys = Vec::new();
+        for _ in 0..capabilities.surveyors {
+            let found: Vec<Value> = (0..3)
+                .map(|_| {
+                    let index = (self.random() * deposits.len() as f64) as usize;
+                    json!({ "symbol": deposits[index % deposits.len()] })
+                })
+                .collect();
+            let signature = format!("{}-{:06X}", waypoint, (self.random() * 16e6) as u32);
+            surveys.push(json!({
+                "signature": signature,
+                "symbol": waypoint,
+                "deposits": found,
+                "expiration": now + Duration::seconds(SURVEY_LIFETIME),
+                "size": "SMALL",
+            }));
+        }
+        let expiration = now + Duration::seconds(EXTRACTION_COOLDOWN);
+        self.cooldowns.insert(
+            self.ships[ship_symbol].symbol.clone(),
+            (expiration, EXTRACTION_COOLDOWN),
+        );
+        Ok(json!({ "cooldown": self.cooldown_json(ship_symbol), "surveys": surveys }))
+    }
+
+    fn add_cargo(&mut self, ship_symbol: &str, symbol: TradeSymbol, units: u32) {
+        let cargo = &mut self.ships.get_mut(ship_symbol).unwrap().cargo;
+        cargo.units += units;
+        match cargo
+            .inventory
+            .iter_mut()
+            .find(|item| item.symbol == symbol)
+        {
+            Some(item) => item.units += units,
+            None => cargo.inventory.push(ShipCargoItem {
+                name: symbol.to_string(),
+                symbol,
+                description: String::new(),
+                units,
+            }),
+        }
+    }
+
+    fn remove_cargo(
+        &mut self,
+        ship_symbol: &str,
+        symbol: TradeSymbol,
+        units: u32,
+    ) -> Result<(), i32> {
+        let cargo = &mut self.ship(ship_symbol)?.cargo;
+        let index = cargo
+            .inventory
+            .iter()
+            .position(|item| item.symbol == symbol)
+            .ok_or(4218)?;
+        if cargo.inventory[index].units < units {
+            return Err(4219);
+        }
+        cargo.inventory[index].units -= units;
+        cargo.units -= units;
+        if cargo.inventory[index].units == 0 {
+            cargo.inventory.remove(index);
+        }
+        Ok(())
+    }
+
+    fn trade(
+        &mut self,
+        ship_symbol: &str,
+        waypoint: &WaypointSymbol,
+        symbol: TradeSymbol,
+        units: u32,
+        kind: MarketTransactionTypes,
+    ) -> MarketTransaction {
+        //! moves the credits and the market, but not the cargo
+        let now = self.now();
+        let good = self
+            .markets
+            .get_mut(waypoint)
+            .and_then(|market| market.goods.iter_mut().find(|good| good.symbol == symbol))
+            .unwrap();
+        let price_per_unit = match kind {
+            MarketTransactionTypes::Purchase => good.purchase_price(),
+            MarketTransactionTypes::Sell => good.sell_price(),
+        };
+        good.trade(units, &kind);
+        let total_price = price_per_unit * units;
+        match kind {
+            MarketTransactionTypes::Purchase => self.agent.credits -= total_price as i64,
+            MarketTransactionTypes::Sell => self.agent.credits += total_price as i64,
+        }
+        let transaction = MarketTransaction {
+            waypoint_symbol: waypoint.clone(),
+            ship_symbol: self.ships[ship_symbol].symbol.clone(),
+            trade_symbol: symbol,
+            kind,
+            units,
+            price_per_unit,
+            total_price,
+            timestamp: now,
+        };
+        self.markets
+            .get_mut(waypoint)
+            .unwrap()
+            .transactions
+            .push(transaction.clone());
+        transaction
+    }
+
+    fn sell(&mut self, ship_symbol: &str, symbol: TradeSymbol, units: u32) -> Outcome {
+        let waypoint = self.docked_ship(ship_symbol)?.nav.waypoint_symbol.clone();
+        let market = self.markets.get(&waypoint).ok_or(4603)?;
+        let good = market
+            .goods
+            .iter()
+            .find(|good| good.symbol == symbol)
+            .ok_or(4602)?;
+        if units > good.trade_volume {
+            return Err(4604);
+        }
+        self.remove_cargo(ship_symbol, symbol.clone(), units)?;
+        let transaction = self.trade(
+            ship_symbol,
+            &waypoint,
+            symbol,
+            units,
+            MarketTransactionTypes::Sell,
+        );
+        Ok(json!({
+            "agent": self.agent,
+            "cargo": self.ships[ship_symbol].cargo,
+            "transaction": transaction,
+        }))
+    }
+
+    fn purchase(&mut self, ship_symbol: &str, symbol: TradeSymbol, units: u32) -> Outcome {
+        let ship = self.docked_ship(ship_symbol)?;
+        let waypoint = ship.nav.waypoint_symbol.clone();
+        let free = ship.cargo.capacity - ship.cargo.units;
+        let market = self.markets.get(&waypoint).ok_or(4603)?;
+        let good = market
+            .goods
+            .iter()
+            .find(|good| good.symbol == symbol)
+            .ok_or(4601)?;
+        if units > good.trade_volume {
+            return Err(4604);
+        }
+        if units > free {
+            return Err(4217);
+        }
+        if (good.purchase_price() * units) as i64 > self.agent.credits {
+            return Err(4600);
+        }
+        let transaction = self.trade(
+            ship_symbol,
+            &waypoint,
+            symbol.clone(),
+            units,
+            MarketTransactionTypes::Purchase,
+        );
+        self.add_cargo(ship_symbol, symbol, units);
+        Ok(json!({
+            "agent": self.agent,
+            "cargo": self.ships[ship_symbol].cargo,
+            "transaction": transaction,
+        }))
+    }
+
+    fn jettison(&mut self, ship_symbol: &str, symbol: TradeSymbol, units: u32) -> Outcome {
+        self.remove_cargo(ship_symbol, symbol, units)?;
+        Ok(json!({ "cargo": self.ships[ship_symbol].cargo }))
+    }
+
+    fn contract(&mut self, id: &str) -> Result<&mut Contract, i32> {
+        self.contracts.get_mut(id).ok_or(404)
+    }
+
+    fn accept_contract(&mut self, id: &str) -> Outcome {
+        let contract = self.contract(id)?;
+        if contract.accepted {
+            return Err(4501);
+        }
+        contract.accepted = true;
+        self.agent.credits += contract.terms.payment.on_accepted as i64;
+        Ok(json!({ "agent": self.agent, "contract": self.contracts[id] }))
+    }
+
+    fn deliver_contract(
+        &mut self,
+        id: &str,
+        ship_symbol: &str,
+        symbol: TradeSymbol,
+        units: u32,
+    ) -> Outcome {
+        let waypoint = self.docked_ship(ship_symbol)?.nav.waypoint_symbol.clone();
+        let contract = self.contract(id)?;
+        if !contract.accepted {
+            return Err(4505);
+        }
+        let term = contract
+            .terms
+            .deliver
+            .iter()
+            .find(|term| term.trade_symbol == symbol)
+            .ok_or(4508)?;
+        if term.destination_symbol != waypoint {
+            return Err(4510);
+        }
+        if term.units_fulfilled + units as i32 > term.units_required {
+            return Err(4509);
+        }
+        self.remove_cargo(ship_symbol, symbol.clone(), units)?;
+        let contract = self.contract(id)?;
+        let term = contract
+            .terms
+            .deliver
+            .iter_mut()
+            .find(|term| term.trade_symbol == symbol)
+            .unwrap();
+        term.units_fulfilled += units as i32;
+        Ok(json!({ "contract": self.contracts[id], "cargo": self.ships[ship_symbol].cargo }))
+    }
+
+    fn negotiate_contract(&mut self, ship_symbol: &str) -> Outcome {
+        let now = self.now();
+        self.docked_ship(ship_symbol)?;
+        // one contract on the go, or on offer, at a time
+        let open = self.contracts.values().any(|contract| {
+            !contract.fulfilled && contract.terms.deadline > now && contract.expiration > now
+        });
+        if open {
+            return Err(4511);
+        }
+        // something a market here imports, delivered there
+        let wanted: Vec<(WaypointSymbol, TradeSymbol, u32)> = self
+            .markets
+            .iter()
+            .flat_map(|(waypoint, market)| {
+                market
+                    .goods
+                    .iter()
+                    .filter(|good| good.kind == GoodKind::Import)
+                    .map(|good| (waypoint.clone(), good.symbol.clone(), good.base_price))
+            })
+            .collect();
+        if wanted.is_empty() {
+            return Err(4500);
+        }
+        let (destination, symbol, price) =
+            wanted[(self.random() * wanted.len() as f64) as usize % wanted.len()].clone();
+        let units = 20 + 10 * (self.random() * 5.0) as u32;
+        let id = format!("sim-contract-{}", self.contracts.len() + 1);
+        let contract: Contract = serde_json::from_value(json!({
+            "id": id,
+            "factionSymbol": "COSMIC",
+            "type": "PROCUREMENT",
+            "terms": {
+                "deadline": now + Duration::days(7),
+                "payment": {
+                    "onAccepted": price * units / 4,
+                    "onFulfilled": price * units,
+                },
+                "deliver": [{
+                    "tradeSymbol": symbol,
+                    "destinationSymbol": destination,
+                    "unitsRequired": units,
+                    "unitsFulfilled": 0,
+                }],
+            },
+            "accepted": false,
+            "fulfilled": false,
+            "expiration": now + Duration::days(1),
+        }))
+        .unwrap();
+        self.contracts.insert(id.clone(), contract);
+        Ok(json!({ "contract": self.contracts[&id] }))
+    }
+
+    fn fulfill_contract(&mut self, id: &str) -> Outcome {
+        let now = self.now();
+        let contract = self.contract(id)?;
+        if !contract.accepted {
+            return Err(4505);
+        }
+        if contract.fulfilled {
+            return Err(4504);
+        }
+        if contract.terms.deadline < now {
+            return Err(4503);
+        }
+        let delivered = contract
+            .terms
+            .deliver
+            .iter()
+            .all(|term| term.units_fulfilled >= term.units_required);
+        if !delivered {
+            return Err(4502);
+        }
+        contract.fulfilled = true;
+        self.agent.credits += contract.terms.payment.on_fulfilled as i64;
+        Ok(json!({ "agent": self.agent, "contract": self.contracts[id] }))
+    }
+
+    fn route(&mut self, request: &MockRequest) -> (u16, Value) {
+        let body = &request.body;
+        let symbol =
+            || serde_json::from_value::<TradeSymbol>(body["symbol"].clone()).map_err(|_| 422);
+        let units = || body["units"].as_u64().map(|units| units as u32).ok_or(422);
+        let outcome = match (request.method.as_str(), request.segments().as_slice()) {
+            ("GET", ["my", "agent"]) => Ok(to_json(&self.agent)),
+            ("GET", ["my", "ships"]) => {
+                return mock::page(self.ships.values().map(to_json).collect(), &request.query)
+            }
+            ("POST", ["my", "ships"]) => {
+                let kind = serde_json::from_value(body["shipType"].clone()).map_err(|_| 422);
+                match (kind, body["waypointSymbol"].as_str()) {
+                    (Ok(kind), Some(waypoint)) => self.purchase_ship(kind, waypoint),
+                    _ => Err(422),
+                }
+            }
+            ("GET", ["my", "ships", ship]) => self.ship(ship).map(|ship| to_json(ship)),
+            ("GET", ["my", "ships", ship, "nav"]) => self.ship(ship).map(|ship| to_json(&ship.nav)),
+            ("PATCH", ["my", "ships", ship, "nav"]) => {
+                match serde_json::from_value(body["flightMode"].clone()) {
+                    Ok(flight_mode) => self.set_flight_mode(ship, flight_mode),
+                    Err(_) => Err(422),
+                }
+            }
+            ("GET", ["my", "ships", ship, "cargo"]) => {
+                self.ship(ship).map(|ship| to_json(&ship.cargo))
+            }
+            ("GET", ["my", "ships", ship, "cooldown"]) => match self.ship(ship) {
+                Ok(_) => match self.cooldown_json(ship) {
+                    Some(cooldown) => Ok(cooldown),
+                    None => return (204, Value::Null),
+                },
+                Err(code) => Err(code),
+            },
+            ("POST", ["my", "ships", ship, "orbit"]) => self.orbit(ship),
+            ("POST", ["my", "ships", ship, "dock"]) => self.dock(ship),
+            ("POST", ["my", "ships", ship, "navigate"]) => match body["waypointSymbol"].as_str() {
+                Some(destination) => self.navigate(ship, destination),
+                None => Err(422),
+            },
+            ("POST", ["my", "ships", ship, "refuel"]) => self.refuel(ship),
+            ("POST", ["my", "ships", ship, "extract"]) => self.extract(ship, &body["survey"]),
+            ("POST", ["my", "ships", ship, "survey"]) => self.survey(ship),
+            ("POST", ["my", "ships", ship, "chart"]) => self.chart(ship),
+            ("POST", ["my", "ships", ship, "jump"]) => match body["systemSymbol"].as_str() {
+                Some(system) => self.jump(ship, system),
+                None => Err(422),
+            },
+            ("POST", ["my", "ships", ship, "negotiate", "contract"]) => {
+                self.negotiate_contract(ship)
+            }
+            ("POST", ["my", "ships", ship, "sell"]) => {
+                symbol().and_then(|symbol| self.sell(ship, symbol, units()?))
+            }
+            ("POST", ["my", "ships", ship, "purchase"]) => {
+                symbol().and_then(|symbol| self.purchase(ship, symbol, units()?))
+            }
+            ("POST", ["my", "ships", ship, "jettison"]) => {
+                symbol().and_then(|symbol| self.jettison(ship, symbol, units()?))
+            }
+            ("GET", ["my", "contracts"]) => {
+                return mock::page(
+                    self.contracts.values().map(to_json).collect(),
+                    &request.query,
+                )
+            }
+            ("GET", ["my", "contracts", id]) => self.contract(id).map(|contract| to_json(contract)),
+            ("POST", ["my", "contracts", id, "accept"]) => self.accept_contract(id),
+            ("POST", ["my", "contracts", id, "deliver"]) => {
+                let trade_symbol =
+                    serde_json::from_value(body["tradeSymbol"].clone()).map_err(|_| 422);
+                match (body["shipSymbol"].as_str(), trade_symbol) {
+                    (Some(ship), Ok(trade_symbol)) => units()
+                        .and_then(|units| self.deliver_contract(id, ship, trade_symbol, units)),
+                    _ => Err(422),
+                }
+            }
+            ("POST", ["my", "contracts", id, "fulfill"]) => self.fulfill_contract(id),
+            ("GET", ["systems"]) => {
+                return mock::page(self.systems.values().map(to_json).collect(), &request.query)
+            }
+            ("GET", ["systems", system]) => self.systems.get(*system).map(to_json).ok_or(404),
+            ("GET", ["systems", system, "waypoints"]) => {
+                let waypoints = self
+                    .waypoints
+                    .values()
+                    .filter(|waypoint| waypoint.system_symbol == *system)
+                    .map(to_json)
+                    .collect();
+                return mock::page(waypoints, &request.query);
+            }
+            ("GET", ["systems", _, "waypoints", waypoint]) => {
+                self.waypoints.get(*waypoint).map(to_json).ok_or(404)
+            }
+            ("GET", ["systems", _, "waypoints", waypoint, "market"]) => {
+                self.market_json(waypoint).ok_or(404)
+            }
+            ("GET", ["systems", _, "waypoints", waypoint, "shipyard"]) => {
+                self.shipyard_json(waypoint).ok_or(404)
+            }
+            ("GET", ["systems", _, "waypoints", waypoint, "jump-gate"]) => {
+                self.jump_gate_json(waypoint).ok_or(404)
+            }
+            _ => Err(404),
+        };
+        match outcome {
+            Ok(value) => mock::data(value),
+            Err(code) => mock::error_message(code, "rejected by the simulator"),
+        }
+    }
+}
+
+pub struct Simulator {
+    state: Mutex<SimState>,
+}
+
+impl Simulator {
+    pub fn new(seed: u64) -> Self {
+        //! the default universe: one system, X1-SIM, with a headquarters
+        //! planet, an asteroid field, a couple of markets, a shipyard at the
+        //! station, a mining ship, a probe and a procurement contract for
+        //! iron ore
+        let sim = Self::empty("SIMULATOR", 100_000, seed);
+        let symbol = |symbol: &str| -> WaypointSymbol { symbol.parse().unwrap() };
+        let hq = &symbol("X1-SIM-HQ");
+        let asteroids = &symbol("X1-SIM-ASTEROIDS");
+        let moon = &symbol("X1-SIM-MOON");
+        let station = &symbol("X1-SIM-STATION");
+        sim.add_waypoint(hq, WaypointType::Planet, 0, 0);
+        sim.add_waypoint(asteroids, WaypointType::AsteroidField, 30, 40);
+        sim.add_waypoint(moon, WaypointType::Moon, -60, 80);
+        sim.add_waypoint(station, WaypointType::OrbitalStation, 120, -50);
+        sim.set_deposits(
+            asteroids,
+            vec![
+                TradeSymbol::IronOre,
+                TradeSymbol::IronOre,
+                TradeSymbol::CopperOre,
+                TradeSymbol::QuartzSand,
+                TradeSymbol::SiliconCrystals,
+            ],
+        );
+        sim.add_market(
+            hq,
+            vec![
+                SimGood::new(TradeSymbol::Fuel, GoodKind::Exchange, 70),
+                SimGood::new(TradeSymbol::IronOre, GoodKind::Import, 40),
+                SimGood::new(TradeSymbol::CopperOre, GoodKind::Import, 45),
+                SimGood::new(TradeSymbol::Electronics, GoodKind::Export, 300),
+            ],
+        );
+        sim.add_market(
+            moon,
+            vec![
+                SimGood::new(TradeSymbol::Fuel, GoodKind::Exchange, 80),
+                SimGood::new(TradeSymbol::QuartzSand, GoodKind::Import, 25),
+                SimGood::new(TradeSymbol::SiliconCrystals, GoodKind::Import, 50),
+                SimGood::new(TradeSymbol::Electronics, GoodKind::Import, 420),
+                SimGood::new(TradeSymbol::IronOre, GoodKind::Export, 30),
+            ],
+        );
+        sim.add_market(
+            station,
+            vec![
+                SimGood::new(TradeSymbol::Fuel, GoodKind::Exchange, 60),
+                SimGood::new(TradeSymbol::CopperOre, GoodKind::Import, 60),
+                SimGood::new(TradeSymbol::Electronics, GoodKind::Import, 380),
+            ],
+        );
+
+        let probe = sim.make_shipyard_ship(ShipType::ShipProbe, 20_000);
+        let mut drone = sim.make_shipyard_ship(ShipType::ShipMiningDrone, 45_000);
+        drone.mounts = serde_json::from_value(json!([{
+            "symbol": "MOUNT_MINING_LASER_I",
+            "name": "Mining Laser I",
+            "strength": 10,
+            "requirements": {},
+        }]))
+        .unwrap();
+        drone.modules = serde_json::from_value(json!([{
+            "symbol": "MODULE_CARGO_HOLD_I",
+            "name": "Cargo Hold",
+            "capacity": 15,
+            "requirements": {},
+        }]))
+        .unwrap();
+        sim.add_shipyard(station, vec![probe, drone]);
+
+        let mut miner = sim.make_ship(&"SIMULATOR-1".parse().unwrap(), ShipRole::Excavator, hq);
+        miner.mounts = serde_json::from_value(json!([{
+            "symbol": "MOUNT_MINING_LASER_I",
+            "name": "Mining Laser I",
+            "strength": 10,
+            "requirements": {},
+        }]))
+        .unwrap();
+        sim.add_ship(miner);
+        let mut probe = sim.make_ship(&"SIMULATOR-2".parse().unwrap(), ShipRole::Satellite, hq);
+        probe.cargo.capacity = 0;
+        probe.fuel.capacity = 0;
+        probe.fuel.current = 0;
+        sim.add_ship(probe);
+
+        let now = sim.now();
+        let contract = json!({
+            "id": "sim-contract-1",
+            "factionSymbol": "COSMIC",
+            "type": "PROCUREMENT",
+            "terms": {
+                "deadline": now + Duration::days(7),
+                "payment": { "onAccepted": 5_000, "onFulfilled": 20_000 },
+                "deliver": [{
+                    "tradeSymbol": "IRON_ORE",
+                    "destinationSymbol": hq,
+                    "unitsRequired": 60,
+                    "unitsFulfilled": 0,
+                }],
+            },
+            "accepted": false,
+            "fulfilled": false,
+            "expiration": now + Duration::days(1),
+        });
+        sim.add_contract(serde_json::from_value(contract).unwrap());
+        sim
+    }
+
+    pub fn empty(agent_symbol: &str, credits: i64, seed: u64) -> Self {
+        //! a universe with nothing but an agent in it
+        let start = DateTime::parse_from_rfc3339("2023-05-20T00:00:00Z")
+            .unwrap()
+            .into();
+        let state = SimState {
+            start,
+            started: Instant::now(),
+            time_scale: 0.0,
+            offset: Duration::zero(),
+            settled_at: start,
+            // xorshift gets stuck on zero
+            rng: seed.max(1),
+            agent: Agent {
+                account_id: format!("sim-{}", agent_symbol.to_lowercase()),
+                symbol: agent_symbol.to_owned(),
+                // until the first waypoint is added
+                headquarters: serde_json::from_value(json!("")).unwrap(),
+                credits,
+            },
+            systems: BTreeMap::new(),
+            waypoints: BTreeMap::new(),
+            deposits: HashMap::new(),
+            markets: BTreeMap::new(),
+            shipyards: BTreeMap::new(),
+            jump_gates: BTreeMap::new(),
+            ships: BTreeMap::new(),
+            cooldowns: HashMap::new(),
+            contracts: BTreeMap::new(),
+        };
+        Self {
+            state: Mutex::new(state),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SimState> {
+        let mut state = self.state.lock().unwrap();
+        state.settle();
+        state
+    }
+
+    pub fn now(&self) -> DateTime<Utc> { self.lock().now() }
+
+    pub fn advance(&self, duration: Duration) {
+        //! moves the clock forward, landing ships and recovering markets
+        self.state.lock().unwrap().offset += duration;
+    }
+
+    pub fn set_time_scale(&self, scale: f64) {
+        //! how many game seconds pass per real second, on top of advance()
+        let mut state = self.lock();
+        let now = state.now();
+        state.offset = now - state.start;
+        state.started = Instant::now();
+        state.time_scale = scale;
+    }
+
+    pub fn add_waypoint(&self, symbol: &WaypointSymbol, kind: WaypointType, x: i32, y: i32) {
+        //! the system is created along with its first waypoint. the first
+        //! waypoint added also becomes the agent's headquarters
+        let mut state = self.lock();
+        let system_symbol = symbol.system();
+        let waypoint = json!({
+            "symbol": symbol,
+            "type": kind,
+            "systemSymbol": system_symbol,
+            "x": x,
+            "y": y,
+            "orbitals": [],
+            "faction": { "symbol": "COSMIC" },
+            "traits": [],
+            "chart": null,
+        });
+        let system = state
+            .systems
+            .entry(system_symbol.clone())
+            .or_insert_with(|| {
+                serde_json::from_value(json!({
+                    "symbol": system_symbol,
+                    "sectorSymbol": system_symbol.sector(),
+                    "type": "RED_STAR",
+                    "x": 0,
+                    "y": 0,
+                    "waypoints": [],
+                    "factions": [],
+                }))
+                .unwrap()
+            });
+        system.waypoints.push(
+            serde_json::from_value(json!({ "symbol": symbol, "type": kind, "x": x, "y": y }))
+                .unwrap(),
+        );
+        state
+            .waypoints
+            .insert(symbol.clone(), serde_json::from_value(waypoint).unwrap());
+        if state.agent.headquarters.as_str().is_empty() {
+            state.agent.headquarters = symbol.clone();
+        }
+    }
+
+    pub fn set_deposits(&self, waypoint: &WaypointSymbol, deposits: Vec<TradeSymbol>) {
+        //! makes waypoint minable. repeat a symbol to make it more likely
+        self.lock().deposits.insert(waypoint.clone(), deposits);
+    }
+
+    pub fn add_market(&self, waypoint: &WaypointSymbol, goods: Vec<SimGood>) {
+        let mut state = self.lock();
+        if let Some(waypoint) = state.waypoints.get_mut(waypoint) {
+            waypoint.traits.push(
+                serde_json::from_value(json!({
+                    "symbol": "MARKETPLACE",
+                    "name": "Marketplace",
+                    "description": "",
+                }))
+                .unwrap(),
+            );
+        }
+        state.markets.insert(
+            waypoint.clone(),
+            SimMarket {
+                goods,
+                transactions: Vec::new(),
+            },
+        );
+    }
+
+    pub fn add_shipyard(&self, waypoint: &WaypointSymbol, ships: Vec<ShipyardShip>) {
+        let mut state = self.lock();
+        if let Some(waypoint) = state.waypoints.get_mut(waypoint) {
+            waypoint.traits.push(
+                serde_json::from_value(json!({
+                    "symbol": "SHIPYARD",
+                    "name": "Shipyard",
+                    "description": "",
+                }))
+                .unwrap(),
+            );
+        }
+        state.shipyards.insert(
+            waypoint.clone(),
+            SimShipyard {
+                ships,
+                transactions: Vec::new(),
+            },
+        );
+    }
+
+    pub fn add_jump_gate(&self, waypoint: &WaypointSymbol, connected: Vec<SystemSymbol>) {
+        //! makes waypoint a gate to the gates of connected, which only go
+        //! back the other way if they're given this system too
+        self.lock().jump_gates.insert(waypoint.clone(), connected);
+    }
+
+    pub fn set_uncharted(&self, waypoint: &WaypointSymbol) {
+        //! until a ship charts it
+        let mut state = self.lock();
+        if let Some(waypoint) = state.waypoints.get_mut(waypoint) {
+            waypoint.traits.push(
+                serde_json::from_value(json!({
+                    "symbol": "UNCHARTED",
+                    "name": "Uncharted",
+                    "description": "",
+                }))
+                .unwrap(),
+            );
+        }
+    }
+
+    pub fn make_shipyard_ship(&self, kind: ShipType, purchase_price: u32) -> ShipyardShip {
+        //! a listing with the example ship's frame, reactor, engine, modules
+        //! and mounts. tweak it, then pass it to add_shipyard
+        let ship = mock::fixture("Ship");
+        serde_json::from_value(json!({
+            "type": kind,
+            "name": kind.to_string(),
+            "description": "",
+            "purchasePrice": purchase_price,
+            "frame": ship["frame"],
+            "reactor": ship["reactor"],
+            "engine": ship["engine"],
+            "modules": ship["modules"],
+            "mounts": ship["mounts"],
+        }))
+        .unwrap()
+    }
+
+    pub fn make_ship(
+        &self,
+        symbol: &ShipSymbol,
+        role: ShipRole,
+        waypoint: &WaypointSymbol,
+    ) -> Ship {
+        //! a plain ship docked at waypoint, with no mounts, 40 cargo, 400
+        //! fuel and a speed of 30. tweak it, then pass it to add_ship
+        self.lock()
+            .make_ship(symbol, role, waypoint.as_str())
+            .unwrap_or_else(|_| panic!("no waypoint named {}", waypoint))
+    }
+
+    pub fn add_ship(&self, ship: Ship) { self.lock().ships.insert(ship.symbol.clone(), ship); }
+
+    pub fn add_contract(&self, contract: Contract) {
+        self.lock().contracts.insert(contract.id.clone(), contract);
+    }
+
+    // the rest look at the universe without going through the API
+
+    pub fn agent(&self) -> Agent { self.lock().agent.clone() }
+
+    pub fn ship(&self, symbol: &ShipSymbol) -> Option<Ship> {
+        self.lock().ships.get(symbol).cloned()
+    }
+
+    pub fn contract(&self, id: &str) -> Option<Contract> { self.lock().contracts.get(id).cloned() }
+
+    pub fn waypoint(&self, symbol: &WaypointSymbol) -> Option<Waypoint> {
+        self.lock().waypoints.get(symbol).cloned()
+    }
+
+    pub fn shipyard(&self, waypoint: &WaypointSymbol) -> Option<Shipyard> {
+        //! with its ships listed, whether or not a ship is there
+        let state = self.lock();
+        let shipyard = state.shipyards.get(waypoint)?;
+        let mut value = state.shipyard_json(waypoint.as_str())?;
+        value["ships"] = to_json(&shipyard.ships);
+        value["transactions"] = to_json(&shipyard.transactions);
+        serde_json::from_value(value).ok()
+    }
+
+    pub fn market(&self, waypoint: &WaypointSymbol) -> Option<Market> {
+        //! with prices, whether or not a ship is there
+        let state = self.lock();
+        let market = state.markets.get(waypoint)?;
+        let mut value = state.market_json(waypoint.as_str())?;
+        let goods: Vec<MarketTradeGood> = market
+            .goods
+            .iter()
+            .map(|good| MarketTradeGood {
+                symbol: good.symbol.clone(),
+                trade_volume: good.trade_volume,
+                supply: good.supply_level(),
+                purchase_price: good.purchase_price(),
+                sell_price: good.sell_price(),
+            })
+            .collect();
+        value["tradeGoods"] = to_json(&goods);
+        value["transactions"] = to_json(&market.transactions);
+        serde_json::from_value(value).ok()
+    }
+}
+
+impl Backend for Simulator {
+    fn handle(&self, request: &MockRequest) -> (u16, Value) {
+        if request.path.trim_end_matches('/').is_empty() {
+            let state = self.lock();
+            let reset_date = state.now().format("%Y-%m-%d").to_string();
+            let status = json!({
+                "status": "SpaceTraders is currently online and available to play",
+                "version": "v2",
+                "resetDate": reset_date,
+                "description": "simulated universe",
+                "stats": {
+                    "agents": 1,
+                    "ships": state.ships.len(),
+                    "systems": state.systems.len(),
+                    "waypoints": state.waypoints.len(),
+                },
+            });
+            return (200, status);
+        }
+        self.lock().route(request)
+    }
+}
@@ -0,0 +1,161 @@
+/*
+The state module keeps what we know about our agent and fleet up to
+date without asking for it.
+
+Most actions answer with whatever they changed: selling returns the
+agent and cargo, navigating the nav and fuel, buying a ship the agent
+and the ship. SpaceTraderApi hands every successful response to its
+AgentState, which folds those parts into its copy of the agent and
+each ship. Anything that wants to know when credits or the fleet
+change can subscribe() instead of polling MyAgentDetails.
+
+A ship has to be seen whole once (listing or fetching ships, buying
+one, registering) before its parts can be updated; until then a new
+nav or cargo has nothing to land on and is dropped.
+*/
+
+use crate::api::schema::{Agent, Ship, ShipSymbol};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tokio::sync::watch;
+
+/// the agent and fleet as of the latest response
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    pub agent: Option<Agent>,
+    pub ships: BTreeMap<ShipSymbol, Ship>,
+}
+
+impl Snapshot {
+    pub fn credits(&self) -> Option<i64> { self.agent.as_ref().map(|agent| agent.credits) }
+}
+
+fn replace<T: DeserializeOwned + PartialEq>(slot: &mut T, value: &Value) -> bool {
+    //! true if value was a T and a different one
+    match T::deserialize(value) {
+        Ok(value) if *slot != value => {
+            *slot = value;
+            true
+        }
+        _ => false,
+    }
+}
+
+fn replace_agent(snapshot: &mut Snapshot, value: &Value) -> bool {
+    match Agent::deserialize(value) {
+        Ok(agent) if snapshot.agent.as_ref() != Some(&agent) => {
+            snapshot.agent = Some(agent);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn replace_ship(snapshot: &mut Snapshot, value: &Value) -> bool {
+    match Ship::deserialize(value) {
+        Ok(ship) if snapshot.ships.get(&ship.symbol) != Some(&ship) => {
+            snapshot.ships.insert(ship.symbol.clone(), ship);
+            true
+        }
+        _ => false,
+    }
+}
+
+pub struct AgentState {
+    sender: watch::Sender<Snapshot>,
+}
+
+impl Default for AgentState {
+    fn default() -> Self { Self::new() }
+}
+
+impl AgentState {
+    pub fn new() -> Self {
+        Self {
+            sender: watch::channel(Snapshot::default()).0,
+        }
+    }
+
+    pub fn snapshot(&self) -> Snapshot { self.sender.borrow().clone() }
+
+    pub fn agent(&self) -> Option<Agent> { self.sender.borrow().agent.clone() }
+
+    pub fn credits(&self) -> Option<i64> { self.sender.borrow().credits() }
+
+    pub fn ship(&self, symbol: &ShipSymbol) -> Option<Ship> {
+        self.sender.borrow().ships.get(symbol).cloned()
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<Snapshot> {
+        //! the receiver only wakes up when something actually changed
+        self.sender.subscribe()
+    }
+
+    pub fn set_agent(&self, agent: &Agent) {
+        self.sender.send_if_modified(|snapshot| {
+            let changed = snapshot.agent.as_ref() != Some(agent);
+            snapshot.agent = Some(agent.clone());
+            changed
+        });
+    }
+
+    pub fn set_ship(&self, ship: &Ship) {
+        self.sender.send_if_modified(|snapshot| {
+            let changed = snapshot.ships.get(&ship.symbol) != Some(ship);
+            snapshot.ships.insert(ship.symbol.clone(), ship.clone());
+            changed
+        });
+    }
+
+    pub(crate) fn observe(&self, path: &str, body: Option<&Value>, response: &str) {
+        //! folds one successful response into the state. path is the
+        //! request's, relative to the base url
+        let data = match serde_json::from_str::<Value>(response) {
+            Ok(mut value) => value["data"].take(),
+            Err(_) => return,
+        };
+        let path = path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        self.sender.send_if_modified(|snapshot| {
+            // the endpoints that answer with the thing itself
+            match segments.as_slice() {
+                ["my", "agent"] => return replace_agent(snapshot, &data),
+                ["my", "ships"] if data.is_array() => {
+                    let mut changed = false;
+                    for ship in data.as_array().unwrap() {
+                        changed |= replace_ship(snapshot, ship);
+                    }
+                    return changed;
+                }
+                ["my", "ships", _] => return replace_ship(snapshot, &data),
+                ["my", "ships", ship, part @ ("nav" | "cargo")] => {
+                    return match snapshot.ships.get_mut(*ship) {
+                        Some(ship) if *part == "nav" => replace(&mut ship.nav, &data),
+                        Some(ship) => replace(&mut ship.cargo, &data),
+                        None => false,
+                    };
+                }
+                _ => {}
+            }
+
+            // and the rest, which say what they carry by name
+            let mut changed = replace_agent(snapshot, &data["agent"]);
+            changed |= replace_ship(snapshot, &data["ship"]);
+            let ship_symbol = match segments.as_slice() {
+                ["my", "ships", ship, ..] => Some(*ship),
+                ["my", "contracts", _, "deliver"] => {
+                    body.and_then(|body| body["shipSymbol"].as_str())
+                }
+                _ => None,
+            };
+            if let Some(ship) = ship_symbol.and_then(|ship| snapshot.ships.get_mut(ship)) {
+                changed |= replace(&mut ship.cargo, &data["cargo"]);
+                changed |= replace(&mut ship.nav, &data["nav"]);
+                changed |= replace(&mut ship.fuel, &data["fuel"]);
+            }
+            changed
+        });
+    }
+}
@@ -0,0 +1,733 @@
+use super::fixtures::example_ship;
+use super::symbols::symbol;
+use crate::api::schema::{Ship, ShipFrameSymbols, ShipRole, Survey, SurveySize};
+use crate::behavior::{Builtin, SurveyPool};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+fn ship(role: ShipRole, frame: ShipFrameSymbols) -> Ship {
+    let mut ship: Ship = serde_json::from_value(example_ship()).unwrap();
+    ship.registration.role = role;
+    ship.frame.symbol = frame;
+    ship
+}
+
+fn survey(signature: &str, waypoint: &str, size: SurveySize) -> Survey {
+    let string = std::fs::read_to_string("schema-examples/Survey.json").unwrap();
+    let mut survey: Value = serde_json::from_str(&string).unwrap();
+    survey["signature"] = json!(signature);
+    survey["symbol"] = json!(waypoint);
+    survey["size"] = json!(size);
+    serde_json::from_value(survey).unwrap()
+}
+
+fn at(time: &str) -> DateTime<Utc> {
+    //! a time on the day the example survey expires
+    format!("2019-08-24T{time}:00Z").parse().unwrap()
+}
+
+#[test]
+fn test_builtins_by_role_then_frame() {
+    let pick = |role, frame| Builtin::for_ship(&ship(role, frame));
+    assert_eq!(
+        pick(ShipRole::Excavator, ShipFrameSymbols::FrameProbe),
+        Some(Builtin::Miner)
+    );
+    assert_eq!(
+        pick(ShipRole::Satellite, ShipFrameSymbols::FrameMiner),
+        Some(Builtin::MarketScout)
+    );
+    assert_eq!(
+        pick(ShipRole::Command, ShipFrameSymbols::FrameFrigate),
+        Some(Builtin::ContractRunner)
+    );
+    // roles that don't say fall back to the frame
+    assert_eq!(
+        pick(ShipRole::Patrol, ShipFrameSymbols::FrameLightFreighter),
+        Some(Builtin::Hauler)
+    );
+    assert_eq!(
+        pick(ShipRole::Explorer, ShipFrameSymbols::FrameProbe),
+        Some(Builtin::Explorer)
+    );
+    assert_eq!(
+        pick(ShipRole::Patrol, ShipFrameSymbols::FrameExplorer),
+        Some(Builtin::Explorer)
+    );
+    assert_eq!(pick(ShipRole::Patrol, ShipFrameSymbols::FrameFighter), None);
+}
+
+#[test]
+fn test_survey_pool_hands_out_the_largest() {
+    let pool = SurveyPool::new();
+    pool.add([
+        survey("A", "X1-A-FIELD", SurveySize::Moderate),
+        survey("B", "X1-A-FIELD", SurveySize::Large),
+        survey("C", "X1-A-FIELD", SurveySize::Small),
+        survey("D", "X1-A-OTHER", SurveySize::Large),
+    ]);
+    let field = symbol("X1-A-FIELD");
+    assert_eq!(pool.best(&field, at("14:00")).unwrap().signature, "B");
+    assert!(pool.remove("B"));
+    assert!(!pool.remove("B"));
+    assert_eq!(pool.best(&field, at("14:00")).unwrap().signature, "A");
+    assert_eq!(pool.best(&symbol("X1-A-NOWHERE"), at("14:00")), None);
+
+    // only so many per waypoint, and the oldest go first
+    pool.add((0..30).map(|n| survey(&n.to_string(), "X1-A-FIELD", SurveySize::Small)));
+    assert_eq!(pool.len(), 21);
+    assert_eq!(pool.best(&field, at("14:00")).unwrap().signature, "29");
+}
+
+#[test]
+fn test_survey_pool_skips_expired_surveys() {
+    let expiring = |signature, size, time| {
+        let mut survey = survey(signature, "X1-A-FIELD", size);
+        survey.expiration = at(time);
+        survey
+    };
+    let pool = SurveyPool::new();
+    pool.add([
+        expiring("A", SurveySize::Large, "14:10"),
+        expiring("B", SurveySize::Moderate, "16:00"),
+        expiring("C", SurveySize::Moderate, "15:00"),
+    ]);
+    let field = symbol("X1-A-FIELD");
+    assert_eq!(pool.best(&field, at("14:00")).unwrap().signature, "A");
+    // the same size goes to whichever lasts longest, not whichever came last
+    assert_eq!(pool.best(&field, at("14:30")).unwrap().signature, "B");
+    assert_eq!(pool.best(&field, at("16:00")), None);
+}
+
+#[cfg(feature = "mock")]
+mod simulated {
+    use super::super::symbols::symbol;
+    use crate::api::results::get_error_code;
+    use crate::api::schema::{
+        MarketTransactionTypes, ShipNavStatus, ShipRole, ShipSymbol, ShipType, TradeSymbol,
+        WaypointType,
+    };
+    use crate::api::transport::{BoxFuture, Logged};
+    use crate::api::{Error, SpaceTraderApi};
+    use crate::behavior::{
+        default_behavior, ContractRunner, Explorer, Hauler, MarketScout, Miner, MiningCycle,
+        ShipBehavior, Step, SurveyPool,
+    };
+    use crate::cache::{Cache, CacheKind};
+    use crate::fleet::{Behavior, Fleet, ShipContext};
+    use crate::galaxy::Galaxy;
+    use crate::history::PriceHistory;
+    use crate::mock::{self, MockServer};
+    use crate::sim::Simulator;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    fn start(sim: &Arc<Simulator>) -> Arc<SpaceTraderApi> { Arc::new(client(sim)) }
+
+    fn client(sim: &Arc<Simulator>) -> SpaceTraderApi {
+        //! with the simulator's clock following tokio's
+        sim.set_time_scale(1.0);
+        let history = PriceHistory::open_in_memory().unwrap();
+        for market in ["X1-SIM-HQ", "X1-SIM-MOON", "X1-SIM-STATION"] {
+            history
+                .record(&sim.market(&symbol(market)).unwrap())
+                .unwrap();
+        }
+        SpaceTraderApi::new("token".to_owned())
+            .with_transport(mock::in_memory(sim.clone()))
+            .with_price_history(history)
+    }
+
+    fn context(api: &Arc<SpaceTraderApi>, ship: &str) -> ShipContext {
+        ShipContext {
+            symbol: symbol(ship),
+            api: api.clone(),
+        }
+    }
+
+    #[derive(Default)]
+    struct Scripted {
+        hooks: Mutex<Vec<&'static str>>,
+    }
+
+    impl Scripted {
+        fn hook(&self, name: &'static str) { self.hooks.lock().unwrap().push(name); }
+    }
+
+    impl ShipBehavior for Scripted {
+        fn on_start<'a>(&'a self, _: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>> {
+            self.hook("start");
+            Box::pin(async { Ok(Step::NavigateTo(symbol("X1-SIM-ASTEROIDS"))) })
+        }
+
+        fn on_arrival<'a>(&'a self, ship: &'a ShipContext) -> BoxFuture<'a, Result<Step, Error>> {
+            self.hook("arrival");
+            Box::pin(async move {
+                ship.api.extract_resources(&ship.symbol, None).await?;
+                Ok(Step::Cooldown)
+            })
+        }
+
+        fn on_cooldown_ready<'a>(
+            &'a self,
+            _: &'a ShipContext,
+        ) -> BoxFuture<'a, Result<Step, Error>> {
+            self.hook("cooldown");
+            Box::pin(async { Err("out of ideas".into()) })
+        }
+
+        fn on_error<'a>(
+            &'a self,
+            _: &'a ShipContext,
+            error: Error,
+        ) -> BoxFuture<'a, Result<Step, Error>> {
+            self.hook("error");
+            assert_eq!(error.to_string(), "out of ideas");
+            Box::pin(async { Ok(Step::Done) })
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_hooks_follow_the_steps() {
+        let sim = Arc::new(Simulator::new(1));
+        let api = start(&sim);
+        let scripted = Scripted::default();
+        let started = Instant::now();
+        scripted.run(&context(&api, "SIMULATOR-1")).await.unwrap();
+        assert_eq!(
+            *scripted.hooks.lock().unwrap(),
+            ["start", "arrival", "cooldown", "error"]
+        );
+        // 40 seconds there, then the extraction cooldown
+        assert_eq!(started.elapsed(), Duration::from_secs(40 + 70));
+        let ship = sim.ship(&symbol("SIMULATOR-1")).unwrap();
+        assert!(matches!(ship.nav.status, ShipNavStatus::InOrbit));
+        assert!(ship.cargo.units > 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_default_fleet_mines_and_surveys() {
+        let sim = Arc::new(Simulator::new(3));
+        let mut surveyor = sim.make_ship(
+            &symbol("SIMULATOR-3"),
+            ShipRole::Surveyor,
+            &symbol("X1-SIM-HQ"),
+        );
+        surveyor.mounts = serde_json::from_value(json!([{
+            "symbol": "MOUNT_SURVEYOR_I",
+            "name": "Surveyor I",
+            "strength": 1,
+            "requirements": {},
+        }]))
+        .unwrap();
+        sim.add_ship(surveyor);
+        let api = start(&sim);
+        let fleet = Fleet::with_client(api.clone());
+        let surveys = Arc::new(SurveyPool::new());
+        let picked = surveys.clone();
+        let galaxy = Arc::new(Galaxy::open_in_memory().unwrap());
+        let started = fleet
+            .start(move |ship| default_behavior(ship, &picked, &galaxy))
+            .await
+            .unwrap();
+        assert_eq!(started, 3);
+
+        tokio::time::sleep(Duration::from_secs(2 * 60 * 60)).await;
+        // the miner has been back and forth selling ore
+        let sold: u32 = ["X1-SIM-HQ", "X1-SIM-MOON"]
+            .iter()
+            .flat_map(|market| sim.market(&symbol(market)).unwrap().transactions)
+            .filter(|transaction| {
+                transaction.ship_symbol == "SIMULATOR-1"
+                    && matches!(transaction.kind, MarketTransactionTypes::Sell)
+            })
+            .map(|transaction| transaction.units)
+            .sum();
+        assert!(sold >= 40, "{sold}");
+        assert!(!surveys.is_empty());
+        // and the probe has had a look at every market
+        assert!(
+            api.price_history()
+                .unwrap()
+                .history(&symbol("X1-SIM-STATION"), TradeSymbol::Fuel, None)
+                .unwrap()
+                .len()
+                > 1
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_miner_reports_each_cycle() {
+        let sim = Arc::new(Simulator::new(1));
+        let api = start(&sim);
+        let cycles = Arc::new(Mutex::new(Vec::new()));
+        let logged = cycles.clone();
+        // quartz sand sells for less than that anywhere
+        let miner = Miner::new(Arc::new(SurveyPool::new()))
+            .with_min_sell_price(30)
+            .with_logger(move |cycle| logged.lock().unwrap().push(cycle.clone()));
+        let mut ship = sim.ship(&symbol("SIMULATOR-1")).unwrap();
+        ship.crew.current = 4;
+        ship.crew.wages = 30;
+        sim.add_ship(ship);
+        // the moon buys it, and with the probe parked there its prices show
+        let mut probe = sim.ship(&symbol("SIMULATOR-2")).unwrap();
+        probe.nav.waypoint_symbol = symbol("X1-SIM-MOON");
+        sim.add_ship(probe);
+        let fleet = Fleet::with_client(api.clone());
+        fleet.add(&symbol("SIMULATOR-1"), Arc::new(miner));
+
+        tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        let cycles: Vec<MiningCycle> = cycles.lock().unwrap().clone();
+        assert!(cycles.len() > 1, "{cycles:?}");
+        for cycle in &cycles {
+            assert_eq!(cycle.ship_symbol, "SIMULATOR-1");
+            assert!(cycle.extractions > 0);
+            let jettisoned: u32 = cycle.jettisoned.values().sum();
+            assert!(cycle.units() > jettisoned, "{cycle}");
+            assert!(cycle.revenue > 0 && cycle.fuel_cost > 0, "{cycle}");
+            // 120 credits an hour
+            let crew_cost = (cycle.duration.as_secs_f64() / 30.0).round() as i64;
+            assert_eq!(cycle.crew_cost, crew_cost, "{cycle}");
+            assert_eq!(
+                cycle.profit(),
+                cycle.revenue - cycle.fuel_cost - cycle.crew_cost
+            );
+        }
+        assert!(cycles
+            .iter()
+            .any(|cycle| cycle.jettisoned.contains_key(&TradeSymbol::QuartzSand)));
+        let sold_quartz = sim
+            .market(&symbol("X1-SIM-MOON"))
+            .unwrap()
+            .transactions
+            .iter()
+            .any(|transaction| transaction.trade_symbol == TradeSymbol::QuartzSand);
+        assert!(!sold_quartz);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_miner_keeps_goods_it_has_no_prices_for() {
+        let sim = Arc::new(Simulator::new(1));
+        sim.set_time_scale(1.0);
+        // a history that hasn't seen any markets yet
+        let api = Arc::new(
+            SpaceTraderApi::new("token".to_owned())
+                .with_transport(mock::in_memory(sim.clone()))
+                .with_price_history(PriceHistory::open_in_memory().unwrap()),
+        );
+        let cycles = Arc::new(Mutex::new(Vec::new()));
+        let logged = cycles.clone();
+        let miner = Miner::new(Arc::new(SurveyPool::new()))
+            .with_min_sell_price(30)
+            .with_logger(move |cycle| logged.lock().unwrap().push(cycle.clone()));
+        let fleet = Fleet::with_client(api.clone());
+        fleet.add(&symbol("SIMULATOR-1"), Arc::new(miner));
+
+        tokio::time::sleep(Duration::from_secs(30 * 60)).await;
+        let cycles: Vec<MiningCycle> = cycles.lock().unwrap().clone();
+        assert!(!cycles.is_empty());
+        // everything out there sells somewhere, so nothing goes overboard
+        for cycle in &cycles {
+            assert!(cycle.jettisoned.is_empty(), "{cycle}");
+            assert!(cycle.revenue > 0, "{cycle}");
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_miner_gives_up_when_the_market_wont_buy_after_all() {
+        let sim = Arc::new(Simulator::new(1));
+        let mut ship = sim.ship(&symbol("SIMULATOR-1")).unwrap();
+        ship.cargo.units = ship.cargo.capacity;
+        ship.cargo.inventory = serde_json::from_value(json!([{
+            "symbol": "QUARTZ_SAND",
+            "name": "Quartz sand",
+            "description": "",
+            "units": ship.cargo.capacity,
+        }]))
+        .unwrap();
+        sim.add_ship(ship);
+        // an out of date copy of the market right here that says it buys quartz sand
+        let cache = Cache::open_in_memory("2023-05-20").unwrap();
+        let mut stale = sim.market(&symbol("X1-SIM-MOON")).unwrap();
+        stale.symbol = symbol("X1-SIM-HQ");
+        cache.put(CacheKind::Market, "X1-SIM-HQ", &stale).unwrap();
+        let api = Arc::new(client(&sim).with_cache(cache));
+        let miner = Miner::new(Arc::new(SurveyPool::new()));
+
+        let error = miner
+            .on_start(&context(&api, "SIMULATOR-1"))
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("X1-SIM-HQ wouldn't buy"),
+            "{error}"
+        );
+        let ship = sim.ship(&symbol("SIMULATOR-1")).unwrap();
+        assert_eq!(ship.nav.waypoint_symbol, "X1-SIM-HQ");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scout_tours_markets_and_shipyards() {
+        let sim = Arc::new(Simulator::new(1));
+        let api = start(&sim);
+        let fleet = Fleet::with_client(api.clone());
+        fleet.add(&symbol("SIMULATOR-2"), Arc::new(MarketScout::new()));
+
+        tokio::time::sleep(Duration::from_secs(30 * 60)).await;
+        let history = api.price_history().unwrap();
+        let probe = history
+            .best_ship_price(&symbol("X1-SIM"), ShipType::ShipProbe)
+            .unwrap()
+            .unwrap();
+        assert_eq!(probe.waypoint_symbol, "X1-SIM-STATION");
+        for market in ["X1-SIM-HQ", "X1-SIM-MOON", "X1-SIM-STATION"] {
+            let fuel = history
+                .history(&symbol(market), TradeSymbol::Fuel, None)
+                .unwrap();
+            assert!(fuel.len() > 1, "{market}");
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_parked_scout_polls_its_market() {
+        let sim = Arc::new(Simulator::new(1));
+        let api = start(&sim);
+        let fleet = Fleet::with_client(api.clone());
+        let scout = MarketScout::new()
+            .with_park_at(symbol("X1-SIM-MOON"))
+            .with_revisit(Duration::from_secs(5 * 60));
+        fleet.add(&symbol("SIMULATOR-2"), Arc::new(scout));
+
+        tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        let probe = sim.ship(&symbol("SIMULATOR-2")).unwrap();
+        assert_eq!(probe.nav.waypoint_symbol, "X1-SIM-MOON");
+        let history = api.price_history().unwrap();
+        let polled = |market| {
+            history
+                .history(&symbol(market), TradeSymbol::Fuel, None)
+                .unwrap()
+                .len()
+        };
+        // once from start(), then every five minutes
+        assert!(polled("X1-SIM-MOON") >= 12, "{}", polled("X1-SIM-MOON"));
+        assert_eq!(polled("X1-SIM-HQ"), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_hauler_trades_electronics() {
+        let sim = Arc::new(Simulator::new(1));
+        let hauler: ShipSymbol = symbol("SIMULATOR-3");
+        sim.add_ship(sim.make_ship(&hauler, ShipRole::Hauler, &symbol("X1-SIM-HQ")));
+        let api = start(&sim);
+        // buying needs the balance
+        api.my_agent().await.unwrap();
+        let fleet = Fleet::with_client(api.clone());
+        fleet.add(&hauler, Arc::new(Hauler::new()));
+
+        tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        // bought at the headquarters and sold elsewhere for more
+        let electronics: Vec<_> = ["X1-SIM-HQ", "X1-SIM-MOON", "X1-SIM-STATION"]
+            .iter()
+            .flat_map(|market| sim.market(&symbol(market)).unwrap().transactions)
+            .filter(|transaction| {
+                transaction.ship_symbol == hauler
+                    && transaction.trade_symbol == TradeSymbol::Electronics
+            })
+            .collect();
+        let total = |kind: fn(&MarketTransactionTypes) -> bool| -> i64 {
+            electronics
+                .iter()
+                .filter(|transaction| kind(&transaction.kind))
+                .map(|transaction| transaction.total_price as i64)
+                .sum()
+        };
+        let spent = total(|kind| matches!(kind, MarketTransactionTypes::Purchase));
+        let earned = total(|kind| matches!(kind, MarketTransactionTypes::Sell));
+        assert!(spent > 0);
+        assert!(earned > spent, "{earned} <= {spent}");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_hauler_buys_nothing_without_a_balance() {
+        let sim = Arc::new(Simulator::new(1));
+        let hauler: ShipSymbol = symbol("SIMULATOR-3");
+        sim.add_ship(sim.make_ship(&hauler, ShipRole::Hauler, &symbol("X1-SIM-HQ")));
+        let api = start(&sim);
+        let fleet = Fleet::with_client(api.clone());
+        fleet.add(&hauler, Arc::new(Hauler::new()));
+
+        tokio::time::sleep(Duration::from_secs(10 * 60)).await;
+        assert_eq!(api.agent_state().credits(), None);
+        assert_eq!(sim.ship(&hauler).unwrap().cargo.units, 0);
+        assert_eq!(sim.agent().credits, 100_000);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_contract_runner_fulfills_the_contract() {
+        let sim = Arc::new(Simulator::new(1));
+        let runner: ShipSymbol = symbol("SIMULATOR-3");
+        sim.add_ship(sim.make_ship(&runner, ShipRole::Command, &symbol("X1-SIM-HQ")));
+        let api = start(&sim);
+        let fleet = Fleet::with_client(api.clone());
+        let clock = sim.clone();
+        let contracts = ContractRunner::new().with_clock(move || clock.now());
+        fleet.add(&runner, Arc::new(contracts));
+
+        tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        let contract = sim.contract("sim-contract-1").unwrap();
+        assert!(contract.fulfilled);
+        // the iron ore came from the moon, where it's cheapest (and the
+        // contracts negotiated after it may have wanted more)
+        let bought: u32 = sim
+            .market(&symbol("X1-SIM-MOON"))
+            .unwrap()
+            .transactions
+            .iter()
+            .filter(|transaction| transaction.trade_symbol == TradeSymbol::IronOre)
+            .map(|transaction| transaction.units)
+            .sum();
+        assert!(bought >= 60, "{bought}");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_contract_runner_clears_a_full_hold() {
+        let sim = Arc::new(Simulator::new(1));
+        let runner: ShipSymbol = symbol("SIMULATOR-3");
+        let mut ship = sim.make_ship(&runner, ShipRole::Command, &symbol("X1-SIM-MOON"));
+        // the moon buys electronics but not copper
+        ship.cargo = serde_json::from_value(json!({
+            "capacity": 40,
+            "units": 40,
+            "inventory": [
+                { "symbol": "ELECTRONICS", "name": "", "description": "", "units": 10 },
+                { "symbol": "COPPER_ORE", "name": "", "description": "", "units": 30 },
+            ]
+        }))
+        .unwrap();
+        sim.add_ship(ship);
+        let api = start(&sim);
+        let fleet = Fleet::with_client(api.clone());
+        let clock = sim.clone();
+        let contracts = ContractRunner::new().with_clock(move || clock.now());
+        fleet.add(&runner, Arc::new(contracts));
+
+        tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        assert!(sim.contract("sim-contract-1").unwrap().fulfilled);
+        let sold: u32 = sim
+            .market(&symbol("X1-SIM-MOON"))
+            .unwrap()
+            .transactions
+            .iter()
+            .filter(|transaction| {
+                transaction.trade_symbol == TradeSymbol::Electronics
+                    && matches!(transaction.kind, MarketTransactionTypes::Sell)
+            })
+            .map(|transaction| transaction.units)
+            .sum();
+        assert_eq!(sold, 10);
+        let ship = sim.ship(&runner).unwrap();
+        assert!(ship
+            .cargo
+            .inventory
+            .iter()
+            .all(|item| item.symbol != TradeSymbol::CopperOre));
+    }
+
+    #[tokio::test]
+    async fn test_contract_runner_only_waits_when_it_cant_get_a_contract() {
+        let sim = Arc::new(Simulator::new(1));
+        let deadline = sim.now() - chrono::Duration::hours(1);
+        replace_contract(&sim, |contract| {
+            contract["terms"]["deadline"] = json!(deadline);
+        });
+        let server = MockServer::start_with(sim.clone()).await.unwrap();
+        let api = Arc::new(SpaceTraderApi::new("token".to_owned()).with_base_url(&server.url()));
+        let ship = context(&api, "SIMULATOR-1");
+        let runner = ContractRunner::new().with_clock(move || sim.now());
+        let negotiate = "/my/ships/SIMULATOR-1/negotiate/contract";
+
+        // it already has one
+        server.push_error(negotiate, 4511);
+        assert!(matches!(runner.on_start(&ship).await, Ok(Step::Wait(_))));
+        // but anything else is a real problem
+        server.push_error(negotiate, 4214);
+        let error = runner.on_start(&ship).await.unwrap_err();
+        assert_eq!(get_error_code(&error), Some(4214));
+    }
+
+    fn replace_contract(sim: &Simulator, change: impl FnOnce(&mut serde_json::Value)) {
+        //! the default universe's contract, changed and accepted
+        let contract = sim.contract("sim-contract-1").unwrap();
+        let mut contract = serde_json::to_value(contract).unwrap();
+        contract["accepted"] = json!(true);
+        change(&mut contract);
+        sim.add_contract(serde_json::from_value(contract).unwrap());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_contract_runner_mines_what_no_market_sells() {
+        let sim = Arc::new(Simulator::new(1));
+        let asteroids = symbol("X1-SIM-ASTEROIDS");
+        sim.set_deposits(
+            &asteroids,
+            vec![TradeSymbol::IronOre, TradeSymbol::AluminumOre],
+        );
+        replace_contract(&sim, |contract| {
+            contract["terms"]["deliver"][0]["tradeSymbol"] = json!("ALUMINUM_ORE");
+        });
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let logged = requests.clone();
+        let api = client(&sim).layer(|transport| {
+            Logged::with_logger(transport, move |line| {
+                logged.lock().unwrap().push(line.to_owned())
+            })
+        });
+        let fleet = Fleet::with_client(Arc::new(api));
+        let clock = sim.clone();
+        let contracts = ContractRunner::new().with_clock(move || clock.now());
+        fleet.add(&symbol("SIMULATOR-1"), Arc::new(contracts));
+
+        tokio::time::sleep(Duration::from_secs(2 * 60 * 60)).await;
+        assert!(sim.contract("sim-contract-1").unwrap().fulfilled);
+        let requests = requests.lock().unwrap();
+        let sent = |path: &str| requests.iter().filter(|line| line.contains(path)).count();
+        // 60 units in a 40 unit hold
+        assert_eq!(sent("sim-contract-1/deliver"), 2);
+        assert!(sent("/jettison") > 0);
+        // then new ones from the faction, accepted straight away
+        assert!(sent("/negotiate/contract") > 0);
+        assert!(sim.contract("sim-contract-2").unwrap().accepted);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_contract_runner_leaves_expired_contracts() {
+        let sim = Arc::new(Simulator::new(1));
+        let deadline = sim.now() - chrono::Duration::hours(1);
+        replace_contract(&sim, |contract| {
+            contract["terms"]["deadline"] = json!(deadline);
+        });
+        let api = start(&sim);
+        let fleet = Fleet::with_client(api.clone());
+        let clock = sim.clone();
+        let contracts = ContractRunner::new().with_clock(move || clock.now());
+        fleet.add(&symbol("SIMULATOR-1"), Arc::new(contracts));
+
+        tokio::time::sleep(Duration::from_secs(10 * 60)).await;
+        let expired = sim.contract("sim-contract-1").unwrap();
+        assert_eq!(expired.terms.deliver[0].units_fulfilled, 0);
+        assert!(sim.contract("sim-contract-2").unwrap().accepted);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_contract_runner_turns_down_what_it_cant_finish_in_time() {
+        let sim = Arc::new(Simulator::new(1));
+        // still on offer, but due in ten minutes
+        let deadline = sim.now() + chrono::Duration::minutes(10);
+        replace_contract(&sim, |contract| {
+            contract["accepted"] = json!(false);
+            contract["terms"]["deadline"] = json!(deadline);
+        });
+        let api = start(&sim);
+        let evaluations = Arc::new(Mutex::new(Vec::new()));
+        let logged = evaluations.clone();
+        let clock = sim.clone();
+        let runner = ContractRunner::new()
+            .with_clock(move || clock.now())
+            .with_logger(move |evaluation| logged.lock().unwrap().push(evaluation.to_string()));
+
+        let step = runner.on_start(&context(&api, "SIMULATOR-1")).await;
+        assert!(matches!(step, Ok(Step::Wait(_))));
+        assert!(!sim.contract("sim-contract-1").unwrap().accepted);
+        let evaluations = evaluations.lock().unwrap();
+        assert_eq!(evaluations.len(), 1);
+        assert!(evaluations[0].contains("NO GO"), "{}", evaluations[0]);
+        assert!(evaluations[0].contains("deadline is"), "{}", evaluations[0]);
+    }
+
+    fn add_system(sim: &Simulator, gate: &str, connected: &[&str], others: &[&str]) {
+        //! a system with a jump gate and some uncharted waypoints
+        sim.add_waypoint(&symbol(gate), WaypointType::JumpGate, 10, 10);
+        let connected = connected.iter().map(|system| symbol(system)).collect();
+        sim.add_jump_gate(&symbol(gate), connected);
+        for (n, waypoint) in others.iter().enumerate() {
+            sim.add_waypoint(&symbol(waypoint), WaypointType::Planet, 40 * n as i32, 30);
+            sim.set_uncharted(&symbol(waypoint));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_explorer_charts_its_way_through_the_gates() {
+        let sim = Arc::new(Simulator::new(1));
+        add_system(&sim, "X1-SIM-GATE", &["X1-FAR"], &[]);
+        sim.set_uncharted(&symbol("X1-SIM-MOON"));
+        add_system(
+            &sim,
+            "X1-FAR-GATE",
+            &["X1-SIM", "X1-EDGE"],
+            &["X1-FAR-ROCK"],
+        );
+        sim.set_uncharted(&symbol("X1-FAR-GATE"));
+        add_system(
+            &sim,
+            "X1-EDGE-GATE",
+            &["X1-FAR"],
+            &["X1-EDGE-ICE", "X1-EDGE-DUST"],
+        );
+        let api = start(&sim);
+        let galaxy = Arc::new(Galaxy::open_in_memory().unwrap());
+        let explorer = Explorer::new(galaxy.clone());
+
+        // it finishes once there's nothing left it can reach
+        explorer.run(&context(&api, "SIMULATOR-2")).await.unwrap();
+        for waypoint in [
+            "X1-SIM-MOON",
+            "X1-FAR-GATE",
+            "X1-FAR-ROCK",
+            "X1-EDGE-ICE",
+            "X1-EDGE-DUST",
+        ] {
+            let waypoint = sim.waypoint(&symbol(waypoint)).unwrap();
+            assert!(waypoint.chart.is_some(), "{}", waypoint.symbol);
+        }
+        assert!(galaxy.uncharted_systems().unwrap().is_empty());
+        assert_eq!(galaxy.systems().unwrap().len(), 3);
+        assert_eq!(
+            galaxy
+                .waypoints_in_system(&symbol("X1-EDGE"))
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(galaxy.jump_gates().unwrap().len(), 3);
+        let probe = sim.ship(&symbol("SIMULATOR-2")).unwrap();
+        assert_eq!(probe.nav.system_symbol, "X1-EDGE");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_explorer_takes_charted_for_an_answer() {
+        let sim = Arc::new(Simulator::new(1));
+        let moon = symbol("X1-SIM-MOON");
+        sim.set_uncharted(&moon);
+        let api = Arc::new(client(&sim).with_cache(Cache::open_in_memory("2023-05-20").unwrap()));
+        api.list_waypoints(&symbol("X1-SIM")).await.unwrap();
+
+        // charted behind the cache's back, so the explorer still thinks it isn't
+        let other = start(&sim);
+        let miner = context(&other, "SIMULATOR-1");
+        miner.navigate(&moon).await.unwrap();
+        other.create_chart(&miner.symbol).await.unwrap();
+
+        let galaxy = Arc::new(Galaxy::open_in_memory().unwrap());
+        let explorer = Explorer::new(galaxy.clone());
+        explorer.run(&context(&api, "SIMULATOR-2")).await.unwrap();
+        let probe = sim.ship(&symbol("SIMULATOR-2")).unwrap();
+        assert_eq!(probe.nav.waypoint_symbol, moon);
+        assert!(galaxy
+            .uncharted_in_system(&symbol("X1-SIM"))
+            .unwrap()
+            .is_empty());
+    }
+}
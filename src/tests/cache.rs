@@ -0,0 +1,69 @@
+use crate::api::schema;
+use crate::cache::{Cache, CacheKind};
+use chrono::{Duration, Utc};
+
+fn waypoint() -> schema::Waypoint {
+    let string = std::fs::read_to_string("schema-examples/Waypoint.json").unwrap();
+    serde_json::from_str(&string).unwrap()
+}
+
+#[test]
+fn test_cache_round_trip() {
+    let cache = Cache::open_in_memory("2023-05-20").unwrap();
+    cache
+        .put(CacheKind::Waypoint, "X1-DF55-20250Z", &waypoint())
+        .unwrap();
+    let cached = cache
+        .get::<schema::Waypoint>(CacheKind::Waypoint, "X1-DF55-20250Z")
+        .unwrap();
+    assert_eq!(cached.unwrap().symbol, waypoint().symbol);
+    assert!(cache
+        .get::<schema::Waypoint>(CacheKind::Market, "X1-DF55-20250Z")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_cache_ttl_expiry() {
+    let mut cache = Cache::open_in_memory("2023-05-20").unwrap();
+    let an_hour_ago = Utc::now() - Duration::hours(1);
+    cache
+        .put_at(
+            CacheKind::Market,
+            "X1-DF55-20250Z",
+            &waypoint(),
+            an_hour_ago,
+        )
+        .unwrap();
+    assert!(cache
+        .get::<schema::Waypoint>(CacheKind::Market, "X1-DF55-20250Z")
+        .unwrap()
+        .is_none());
+    assert!(cache
+        .get_entry::<schema::Waypoint>(CacheKind::Market, "X1-DF55-20250Z")
+        .unwrap()
+        .is_some());
+
+    cache.set_ttl(CacheKind::Market, None);
+    assert!(cache
+        .get::<schema::Waypoint>(CacheKind::Market, "X1-DF55-20250Z")
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn test_cache_ignores_other_resets() {
+    let path = std::env::temp_dir().join(format!("space-trate-cache-{}.db", std::process::id()));
+    let old = Cache::open(&path, "2023-05-06").unwrap();
+    old.put(CacheKind::System, "X1-DF55", &"old").unwrap();
+    drop(old);
+
+    let new = Cache::open(&path, "2023-05-20").unwrap();
+    assert!(new
+        .get::<String>(CacheKind::System, "X1-DF55")
+        .unwrap()
+        .is_none());
+    assert_eq!(new.prune_old_resets().unwrap(), 1);
+    drop(new);
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,88 @@
+use super::fixtures::example_ship;
+use crate::api::schema::{self, Deposits, TradeSymbol};
+use crate::capabilities::{Refinery, ShipCapabilities};
+use serde_json::json;
+
+fn ship(modules: serde_json::Value, mounts: serde_json::Value) -> schema::Ship {
+    let mut ship = example_ship();
+    ship["modules"] = modules;
+    ship["mounts"] = mounts;
+    serde_json::from_value(ship).unwrap()
+}
+
+fn module(symbol: &str, capacity: u32, range: u32) -> serde_json::Value {
+    json!({
+        "symbol": symbol,
+        "capacity": capacity,
+        "range": range,
+        "name": symbol,
+        "requirements": {}
+    })
+}
+
+fn mount(symbol: &str, strength: u32, deposits: &[&str]) -> serde_json::Value {
+    json!({
+        "symbol": symbol,
+        "name": symbol,
+        "strength": strength,
+        "deposits": deposits,
+        "requirements": {}
+    })
+}
+
+#[test]
+fn test_capabilities_add_up_modules_and_mounts() {
+    let ship = ship(
+        json!([
+            module("MODULE_CARGO_HOLD_I", 30, 0),
+            module("MODULE_CARGO_HOLD_I", 30, 0),
+            module("MODULE_CREW_QUARTERS_I", 40, 0),
+            module("MODULE_ORE_REFINERY_I", 0, 0),
+            module("MODULE_FUEL_REFINERY_I", 0, 0),
+            module("MODULE_WARP_DRIVE_I", 0, 2000),
+        ]),
+        json!([
+            mount("MOUNT_MINING_LASER_I", 10, &["IRON_ORE", "COPPER_ORE"]),
+            mount("MOUNT_MINING_LASER_II", 25, &["IRON_ORE", "GOLD_ORE"]),
+            mount("MOUNT_SURVEYOR_I", 1, &["IRON_ORE"]),
+            mount("MOUNT_SENSOR_ARRAY_II", 4, &[]),
+        ]),
+    );
+    let capabilities = ShipCapabilities::of(&ship);
+    assert!(capabilities.can_mine && capabilities.can_survey && capabilities.can_warp);
+    assert!(!capabilities.can_siphon && !capabilities.can_jump);
+    assert!(capabilities.can_refine());
+    assert_eq!(capabilities.refineries, vec![Refinery::Ore, Refinery::Fuel]);
+    assert_eq!(capabilities.cargo_capacity, 60);
+    assert_eq!(capabilities.module_capacity, 100);
+    assert_eq!(capabilities.warp_range, 2000);
+    assert_eq!(capabilities.mining_strength, 35);
+    assert_eq!(capabilities.survey_strength, 1);
+    assert_eq!(capabilities.surveyors, 1);
+    assert_eq!(capabilities.scan_strength, 4);
+    assert_eq!(
+        capabilities.deposits.keys().cloned().collect::<Vec<_>>(),
+        vec![Deposits::IronOre, Deposits::CopperOre, Deposits::GoldOre]
+    );
+    assert_eq!(capabilities.mining_strength_for(TradeSymbol::IronOre), 35);
+    assert_eq!(capabilities.mining_strength_for(TradeSymbol::GoldOre), 25);
+    assert_eq!(capabilities.mining_strength_for(TradeSymbol::SilverOre), 0);
+}
+
+#[test]
+fn test_bare_ship_can_do_nothing() {
+    let ship = ship(json!([]), json!([]));
+    assert_eq!(ShipCapabilities::of(&ship), ShipCapabilities::default());
+    assert!(!ShipCapabilities::of(&ship).can_refine());
+}
+
+#[test]
+fn test_laser_without_a_strength_counts_as_one() {
+    let mut laser = mount("MOUNT_MINING_LASER_I", 0, &["IRON_ORE"]);
+    laser.as_object_mut().unwrap().remove("strength");
+    let ship = ship(json!([]), json!([laser.clone(), laser]));
+    let capabilities = ShipCapabilities::of(&ship);
+    assert!(capabilities.can_mine);
+    assert_eq!(capabilities.mining_strength, 2);
+    assert_eq!(capabilities.mining_strength_for(TradeSymbol::IronOre), 2);
+}
@@ -0,0 +1,1394 @@
+// generated by `cargo xtask conformance` from spec/SpaceTraders.json.
+// don't edit it by hand; change the spec or xtask/src/conformance.rs instead
+// the spec is a hand-made copy, not the one in SpaceTradersAPI/api-docs, so these only check
+// our types against our own reading of the docs. spec/README.md says how to vendor it
+
+use super::fidelity::{config, perturbed, survives_round_trip};
+use crate::api::{responses, schema};
+use proptest::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+const KNOWN_DRIFT: &str = include_str!("../../spec/known-drift.txt");
+
+fn drift(path: &str, expected: &Value, actual: &Value, found: &mut Vec<String>) {
+    // a null and a missing field are the same thing as far as we care
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, value) in expected {
+                let path = format!("{path}.{key}");
+                match actual.get(key) {
+                    Some(actual) => drift(&path, value, actual, found),
+                    None if !value.is_null() => found.push(format!("missing {path}")),
+                    None => {}
+                }
+            }
+            for (key, value) in actual {
+                if !expected.contains_key(key) && !value.is_null() {
+                    found.push(format!("extra {path}.{key}"));
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (expected, actual) in expected.iter().zip(actual) {
+                drift(&format!("{path}[]"), expected, actual, found);
+            }
+        }
+        (Value::Number(e), Value::Number(a)) if e.as_f64() == a.as_f64() => {}
+        (expected, actual) if expected == actual => {}
+        _ => found.push(format!("changed {path}")),
+    }
+}
+
+fn round_trip<T: Serialize + DeserializeOwned>(name: &str, example: &str) {
+    let expected: Value = serde_json::from_str(example).unwrap();
+    let mut found = Vec::new();
+    match serde_json::from_value::<T>(expected.clone()) {
+        Ok(decoded) => drift("", &expected, &serde_json::to_value(&decoded).unwrap(), &mut found),
+        // without the list of every variant serde would have taken
+        Err(e) => found.push(format!("doesn't decode: {}", e.to_string().split(", expected one of").next().unwrap())),
+    }
+    found.dedup();
+    let known: Vec<&str> = KNOWN_DRIFT
+        .lines()
+        .filter_map(|line| line.strip_prefix(name)?.strip_prefix(": "))
+        .collect();
+    let report: Vec<String> = found.iter().map(|drift| format!("{name}: {drift}")).collect();
+    assert_eq!(
+        found,
+        known,
+        "{name} doesn't match the spec. fix the type, or list what's expected in spec/known-drift.txt:\n{}",
+        report.join("\n")
+    );
+}
+
+fn names<T>(values: &[&str], open: bool)
+where T: Serialize + DeserializeOwned + Display + FromStr<Err = schema::ParseEnumError> + PartialEq + Debug {
+    // Display and FromStr have to agree with serde on every name
+    for value in values {
+        let decoded: T = serde_json::from_value(Value::from(*value)).unwrap();
+        assert_eq!(decoded.to_string(), *value);
+        assert_eq!(value.parse::<T>().unwrap(), decoded);
+    }
+    // the open ones keep a name they don't know, the rest refuse it
+    let unknown = "NOT_A_VARIANT".parse::<T>();
+    match open {
+        true => {
+            let unknown = unknown.unwrap();
+            assert_eq!(unknown.to_string(), "NOT_A_VARIANT");
+            assert_eq!(serde_json::to_value(&unknown).unwrap(), Value::from("NOT_A_VARIANT"));
+        }
+        false => assert!(unknown.is_err()),
+    }
+}
+
+#[test]
+fn test_agent_conforms() {
+    round_trip::<schema::Agent>("Agent", include_str!("../../spec/examples/Agent.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_agent_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Agent.json"))) {
+        survives_round_trip::<schema::Agent>(&json)?;
+    }
+}
+
+#[test]
+fn test_chart_conforms() {
+    round_trip::<schema::Chart>("Chart", include_str!("../../spec/examples/Chart.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_chart_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Chart.json"))) {
+        survives_round_trip::<schema::Chart>(&json)?;
+    }
+}
+
+#[test]
+fn test_connected_system_conforms() {
+    round_trip::<schema::ConnectedSystem>("ConnectedSystem", include_str!("../../spec/examples/ConnectedSystem.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_connected_system_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ConnectedSystem.json"))) {
+        survives_round_trip::<schema::ConnectedSystem>(&json)?;
+    }
+}
+
+#[test]
+fn test_contract_conforms() {
+    round_trip::<schema::Contract>("Contract", include_str!("../../spec/examples/Contract.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_contract_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Contract.json"))) {
+        survives_round_trip::<schema::Contract>(&json)?;
+    }
+}
+
+#[test]
+fn test_contract_deliver_good_conforms() {
+    round_trip::<schema::ContractDeliverGood>("ContractDeliverGood", include_str!("../../spec/examples/ContractDeliverGood.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_contract_deliver_good_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ContractDeliverGood.json"))) {
+        survives_round_trip::<schema::ContractDeliverGood>(&json)?;
+    }
+}
+
+#[test]
+fn test_contract_payment_conforms() {
+    round_trip::<schema::ContractPayment>("ContractPayment", include_str!("../../spec/examples/ContractPayment.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_contract_payment_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ContractPayment.json"))) {
+        survives_round_trip::<schema::ContractPayment>(&json)?;
+    }
+}
+
+#[test]
+fn test_contract_terms_conforms() {
+    round_trip::<schema::ContractTerms>("ContractTerms", include_str!("../../spec/examples/ContractTerms.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_contract_terms_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ContractTerms.json"))) {
+        survives_round_trip::<schema::ContractTerms>(&json)?;
+    }
+}
+
+#[test]
+fn test_cooldown_conforms() {
+    round_trip::<schema::Cooldown>("Cooldown", include_str!("../../spec/examples/Cooldown.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_cooldown_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Cooldown.json"))) {
+        survives_round_trip::<schema::Cooldown>(&json)?;
+    }
+}
+
+#[test]
+fn test_extraction_conforms() {
+    round_trip::<schema::Extraction>("Extraction", include_str!("../../spec/examples/Extraction.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_extraction_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Extraction.json"))) {
+        survives_round_trip::<schema::Extraction>(&json)?;
+    }
+}
+
+#[test]
+fn test_extraction_yield_conforms() {
+    round_trip::<schema::ExtractionYield>("ExtractionYield", include_str!("../../spec/examples/ExtractionYield.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_extraction_yield_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ExtractionYield.json"))) {
+        survives_round_trip::<schema::ExtractionYield>(&json)?;
+    }
+}
+
+#[test]
+fn test_faction_conforms() {
+    round_trip::<schema::Faction>("Faction", include_str!("../../spec/examples/Faction.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_faction_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Faction.json"))) {
+        survives_round_trip::<schema::Faction>(&json)?;
+    }
+}
+
+#[test]
+fn test_faction_trait_conforms() {
+    round_trip::<schema::FactionTrait>("FactionTrait", include_str!("../../spec/examples/FactionTrait.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_faction_trait_survives_round_trip(json in perturbed(include_str!("../../spec/examples/FactionTrait.json"))) {
+        survives_round_trip::<schema::FactionTrait>(&json)?;
+    }
+}
+
+#[test]
+fn test_jump_gate_conforms() {
+    round_trip::<schema::JumpGate>("JumpGate", include_str!("../../spec/examples/JumpGate.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_jump_gate_survives_round_trip(json in perturbed(include_str!("../../spec/examples/JumpGate.json"))) {
+        survives_round_trip::<schema::JumpGate>(&json)?;
+    }
+}
+
+#[test]
+fn test_market_conforms() {
+    round_trip::<schema::Market>("Market", include_str!("../../spec/examples/Market.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_market_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Market.json"))) {
+        survives_round_trip::<schema::Market>(&json)?;
+    }
+}
+
+#[test]
+fn test_market_trade_good_conforms() {
+    round_trip::<schema::MarketTradeGood>("MarketTradeGood", include_str!("../../spec/examples/MarketTradeGood.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_market_trade_good_survives_round_trip(json in perturbed(include_str!("../../spec/examples/MarketTradeGood.json"))) {
+        survives_round_trip::<schema::MarketTradeGood>(&json)?;
+    }
+}
+
+#[test]
+fn test_market_transaction_conforms() {
+    round_trip::<schema::MarketTransaction>("MarketTransaction", include_str!("../../spec/examples/MarketTransaction.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_market_transaction_survives_round_trip(json in perturbed(include_str!("../../spec/examples/MarketTransaction.json"))) {
+        survives_round_trip::<schema::MarketTransaction>(&json)?;
+    }
+}
+
+#[test]
+fn test_meta_conforms() {
+    round_trip::<schema::Meta>("Meta", include_str!("../../spec/examples/Meta.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_meta_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Meta.json"))) {
+        survives_round_trip::<schema::Meta>(&json)?;
+    }
+}
+
+#[test]
+fn test_scanned_ship_conforms() {
+    round_trip::<schema::ScannedShip>("ScannedShip", include_str!("../../spec/examples/ScannedShip.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_scanned_ship_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ScannedShip.json"))) {
+        survives_round_trip::<schema::ScannedShip>(&json)?;
+    }
+}
+
+#[test]
+fn test_scanned_system_conforms() {
+    round_trip::<schema::ScannedSystem>("ScannedSystem", include_str!("../../spec/examples/ScannedSystem.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_scanned_system_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ScannedSystem.json"))) {
+        survives_round_trip::<schema::ScannedSystem>(&json)?;
+    }
+}
+
+#[test]
+fn test_scanned_waypoint_conforms() {
+    round_trip::<schema::ScannedWaypoint>("ScannedWaypoint", include_str!("../../spec/examples/ScannedWaypoint.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_scanned_waypoint_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ScannedWaypoint.json"))) {
+        survives_round_trip::<schema::ScannedWaypoint>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_conforms() {
+    round_trip::<schema::Ship>("Ship", include_str!("../../spec/examples/Ship.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Ship.json"))) {
+        survives_round_trip::<schema::Ship>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_cargo_conforms() {
+    round_trip::<schema::ShipCargo>("ShipCargo", include_str!("../../spec/examples/ShipCargo.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_cargo_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipCargo.json"))) {
+        survives_round_trip::<schema::ShipCargo>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_cargo_item_conforms() {
+    round_trip::<schema::ShipCargoItem>("ShipCargoItem", include_str!("../../spec/examples/ShipCargoItem.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_cargo_item_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipCargoItem.json"))) {
+        survives_round_trip::<schema::ShipCargoItem>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_condition_conforms() {
+    round_trip::<schema::ShipCondition>("ShipCondition", include_str!("../../spec/examples/ShipCondition.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_condition_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipCondition.json"))) {
+        survives_round_trip::<schema::ShipCondition>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_crew_conforms() {
+    round_trip::<schema::ShipCrew>("ShipCrew", include_str!("../../spec/examples/ShipCrew.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_crew_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipCrew.json"))) {
+        survives_round_trip::<schema::ShipCrew>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_engine_conforms() {
+    round_trip::<schema::ShipEngine>("ShipEngine", include_str!("../../spec/examples/ShipEngine.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_engine_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipEngine.json"))) {
+        survives_round_trip::<schema::ShipEngine>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_frame_conforms() {
+    round_trip::<schema::ShipFrame>("ShipFrame", include_str!("../../spec/examples/ShipFrame.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_frame_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipFrame.json"))) {
+        survives_round_trip::<schema::ShipFrame>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_fuel_conforms() {
+    round_trip::<schema::ShipFuel>("ShipFuel", include_str!("../../spec/examples/ShipFuel.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_fuel_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipFuel.json"))) {
+        survives_round_trip::<schema::ShipFuel>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_module_conforms() {
+    round_trip::<schema::ShipModule>("ShipModule", include_str!("../../spec/examples/ShipModule.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_module_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipModule.json"))) {
+        survives_round_trip::<schema::ShipModule>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_mount_conforms() {
+    round_trip::<schema::ShipMount>("ShipMount", include_str!("../../spec/examples/ShipMount.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_mount_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipMount.json"))) {
+        survives_round_trip::<schema::ShipMount>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_nav_conforms() {
+    round_trip::<schema::ShipNav>("ShipNav", include_str!("../../spec/examples/ShipNav.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_nav_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipNav.json"))) {
+        survives_round_trip::<schema::ShipNav>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_nav_flight_mode_conforms() {
+    round_trip::<schema::ShipNavFlightMode>("ShipNavFlightMode", include_str!("../../spec/examples/ShipNavFlightMode.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_nav_flight_mode_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipNavFlightMode.json"))) {
+        survives_round_trip::<schema::ShipNavFlightMode>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_nav_flight_mode_names() {
+    names::<schema::ShipNavFlightMode>(&["DRIFT", "STEALTH", "CRUISE", "BURN"], false);
+}
+
+#[test]
+fn test_ship_nav_route_conforms() {
+    round_trip::<schema::ShipNavRoute>("ShipNavRoute", include_str!("../../spec/examples/ShipNavRoute.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_nav_route_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipNavRoute.json"))) {
+        survives_round_trip::<schema::ShipNavRoute>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_nav_route_waypoint_conforms() {
+    round_trip::<schema::ShipNavRouteWaypoint>("ShipNavRouteWaypoint", include_str!("../../spec/examples/ShipNavRouteWaypoint.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_nav_route_waypoint_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipNavRouteWaypoint.json"))) {
+        survives_round_trip::<schema::ShipNavRouteWaypoint>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_nav_status_conforms() {
+    round_trip::<schema::ShipNavStatus>("ShipNavStatus", include_str!("../../spec/examples/ShipNavStatus.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_nav_status_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipNavStatus.json"))) {
+        survives_round_trip::<schema::ShipNavStatus>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_nav_status_names() {
+    names::<schema::ShipNavStatus>(&["IN_TRANSIT", "IN_ORBIT", "DOCKED"], false);
+}
+
+#[test]
+fn test_ship_reactor_conforms() {
+    round_trip::<schema::ShipReactor>("ShipReactor", include_str!("../../spec/examples/ShipReactor.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_reactor_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipReactor.json"))) {
+        survives_round_trip::<schema::ShipReactor>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_registration_conforms() {
+    round_trip::<schema::ShipRegistration>("ShipRegistration", include_str!("../../spec/examples/ShipRegistration.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_registration_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipRegistration.json"))) {
+        survives_round_trip::<schema::ShipRegistration>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_requirements_conforms() {
+    round_trip::<schema::ShipRequirements>("ShipRequirements", include_str!("../../spec/examples/ShipRequirements.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_requirements_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipRequirements.json"))) {
+        survives_round_trip::<schema::ShipRequirements>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_role_conforms() {
+    round_trip::<schema::ShipRole>("ShipRole", include_str!("../../spec/examples/ShipRole.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_role_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipRole.json"))) {
+        survives_round_trip::<schema::ShipRole>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_role_names() {
+    names::<schema::ShipRole>(&["FABRICATOR", "HARVESTER", "HAULER", "INTERCEPTOR", "EXCAVATOR", "TRANSPORT", "REPAIR", "SURVEYOR", "COMMAND", "CARRIER", "PATROL", "SATELLITE", "EXPLORER", "REFINERY"], false);
+}
+
+#[test]
+fn test_ship_type_conforms() {
+    round_trip::<schema::ShipType>("ShipType", include_str!("../../spec/examples/ShipType.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_type_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipType.json"))) {
+        survives_round_trip::<schema::ShipType>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_type_names() {
+    names::<schema::ShipType>(&["SHIP_PROBE", "SHIP_MINING_DRONE", "SHIP_INTERCEPTOR", "SHIP_LIGHT_HAULER", "SHIP_COMMAND_FRIGATE", "SHIP_EXPLORER", "SHIP_HEAVY_FREIGHTER", "SHIP_LIGHT_SHUTTLE", "SHIP_ORE_HOUND", "SHIP_REFINING_FREIGHTER"], true);
+}
+
+#[test]
+fn test_shipyard_conforms() {
+    round_trip::<schema::Shipyard>("Shipyard", include_str!("../../spec/examples/Shipyard.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_shipyard_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Shipyard.json"))) {
+        survives_round_trip::<schema::Shipyard>(&json)?;
+    }
+}
+
+#[test]
+fn test_shipyard_ship_conforms() {
+    round_trip::<schema::ShipyardShip>("ShipyardShip", include_str!("../../spec/examples/ShipyardShip.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_shipyard_ship_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipyardShip.json"))) {
+        survives_round_trip::<schema::ShipyardShip>(&json)?;
+    }
+}
+
+#[test]
+fn test_shipyard_transaction_conforms() {
+    round_trip::<schema::ShipyardTransaction>("ShipyardTransaction", include_str!("../../spec/examples/ShipyardTransaction.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_shipyard_transaction_survives_round_trip(json in perturbed(include_str!("../../spec/examples/ShipyardTransaction.json"))) {
+        survives_round_trip::<schema::ShipyardTransaction>(&json)?;
+    }
+}
+
+#[test]
+fn test_survey_conforms() {
+    round_trip::<schema::Survey>("Survey", include_str!("../../spec/examples/Survey.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_survey_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Survey.json"))) {
+        survives_round_trip::<schema::Survey>(&json)?;
+    }
+}
+
+#[test]
+fn test_survey_deposit_conforms() {
+    round_trip::<schema::SurveyDeposit>("SurveyDeposit", include_str!("../../spec/examples/SurveyDeposit.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_survey_deposit_survives_round_trip(json in perturbed(include_str!("../../spec/examples/SurveyDeposit.json"))) {
+        survives_round_trip::<schema::SurveyDeposit>(&json)?;
+    }
+}
+
+#[test]
+fn test_system_conforms() {
+    round_trip::<schema::System>("System", include_str!("../../spec/examples/System.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_system_survives_round_trip(json in perturbed(include_str!("../../spec/examples/System.json"))) {
+        survives_round_trip::<schema::System>(&json)?;
+    }
+}
+
+#[test]
+fn test_system_faction_conforms() {
+    round_trip::<schema::SystemFaction>("SystemFaction", include_str!("../../spec/examples/SystemFaction.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_system_faction_survives_round_trip(json in perturbed(include_str!("../../spec/examples/SystemFaction.json"))) {
+        survives_round_trip::<schema::SystemFaction>(&json)?;
+    }
+}
+
+#[test]
+fn test_system_type_conforms() {
+    round_trip::<schema::SystemType>("SystemType", include_str!("../../spec/examples/SystemType.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_system_type_survives_round_trip(json in perturbed(include_str!("../../spec/examples/SystemType.json"))) {
+        survives_round_trip::<schema::SystemType>(&json)?;
+    }
+}
+
+#[test]
+fn test_system_type_names() {
+    names::<schema::SystemType>(&["NEUTRON_STAR", "RED_STAR", "ORANGE_STAR", "BLUE_STAR", "YOUNG_STAR", "WHITE_DWARF", "BLACK_HOLE", "HYPERGIANT", "NEBULA", "UNSTABLE"], false);
+}
+
+#[test]
+fn test_system_waypoint_conforms() {
+    round_trip::<schema::SystemWaypoint>("SystemWaypoint", include_str!("../../spec/examples/SystemWaypoint.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_system_waypoint_survives_round_trip(json in perturbed(include_str!("../../spec/examples/SystemWaypoint.json"))) {
+        survives_round_trip::<schema::SystemWaypoint>(&json)?;
+    }
+}
+
+#[test]
+fn test_trade_good_conforms() {
+    round_trip::<schema::TradeGood>("TradeGood", include_str!("../../spec/examples/TradeGood.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_trade_good_survives_round_trip(json in perturbed(include_str!("../../spec/examples/TradeGood.json"))) {
+        survives_round_trip::<schema::TradeGood>(&json)?;
+    }
+}
+
+#[test]
+fn test_trade_symbol_conforms() {
+    round_trip::<schema::TradeSymbol>("TradeSymbol", include_str!("../../spec/examples/TradeSymbol.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_trade_symbol_survives_round_trip(json in perturbed(include_str!("../../spec/examples/TradeSymbol.json"))) {
+        survives_round_trip::<schema::TradeSymbol>(&json)?;
+    }
+}
+
+#[test]
+fn test_trade_symbol_names() {
+    names::<schema::TradeSymbol>(&["PRECIOUS_STONES", "QUARTZ_SAND", "SILICON_CRYSTALS", "AMMONIA_ICE", "LIQUID_HYDROGEN", "LIQUID_NITROGEN", "ICE_WATER", "EXOTIC_MATTER", "ADVANCED_CIRCUITRY", "GRAVITON_EMITTERS", "IRON", "IRON_ORE", "COPPER", "COPPER_ORE", "ALUMINUM", "ALUMINUM_ORE", "SILVER", "SILVER_ORE", "GOLD", "GOLD_ORE", "PLATINUM", "PLATINUM_ORE", "DIAMONDS", "URANITE", "URANITE_ORE", "MERITIUM", "MERITIUM_ORE", "HYDROCARBON", "ANTIMATTER", "FERTILIZERS", "FABRICS", "FOOD", "JEWELRY", "MACHINERY", "FIREARMS", "ASSAULT_RIFLES", "MILITARY_EQUIPMENT", "EXPLOSIVES", "LAB_INSTRUMENTS", "AMMUNITION", "ELECTRONICS", "SHIP_PLATING", "EQUIPMENT", "FUEL", "MEDICINE", "DRUGS", "CLOTHING", "MICROPROCESSORS", "PLASTICS", "POLYNUCLEOTIDES", "BIOCOMPOSITES", "NANOBOTS", "AI_MAINFRAMES", "QUANTUM_DRIVES", "ROBOTIC_DRONES", "CYBER_IMPLANTS", "GENE_THERAPEUTICS", "NEURAL_CHIPS", "MOOD_REGULATORS", "VIRAL_AGENTS", "MICRO_FUSION_GENERATORS", "SUPERGRAINS", "LASER_RIFLES", "HOLOGRAPHICS", "SHIP_SALVAGE", "RELIC_TECH", "NOVEL_LIFEFORMS", "BOTANICAL_SPECIMENS", "CULTURAL_ARTIFACTS", "REACTOR_SOLAR_I", "REACTOR_FUSION_I", "REACTOR_FISSION_I", "REACTOR_CHEMICAL_I", "REACTOR_ANTIMATTER_I", "ENGINE_IMPULSE_DRIVE_I", "ENGINE_ION_DRIVE_I", "ENGINE_ION_DRIVE_II", "ENGINE_HYPER_DRIVE_I", "MODULE_MINERAL_PROCESSOR_I", "MODULE_CARGO_HOLD_I", "MODULE_CREW_QUARTERS_I", "MODULE_ENVOY_QUARTERS_I", "MODULE_PASSENGER_CABIN_I", "MODULE_MICRO_REFINERY_I", "MODULE_ORE_REFINERY_I", "MODULE_FUEL_REFINERY_I", "MODULE_SCIENCE_LAB_I", "MODULE_JUMP_DRIVE_I", "MODULE_JUMP_DRIVE_II", "MODULE_JUMP_DRIVE_III", "MODULE_WARP_DRIVE_I", "MODULE_WARP_DRIVE_II", "MODULE_WARP_DRIVE_III", "MODULE_SHIELD_GENERATOR_I", "MODULE_SHIELD_GENERATOR_II", "MOUNT_GAS_SIPHON_I", "MOUNT_GAS_SIPHON_II", "MOUNT_GAS_SIPHON_III", "MOUNT_SURVEYOR_I", "MOUNT_SURVEYOR_II", "MOUNT_SURVEYOR_III", "MOUNT_SENSOR_ARRAY_I", "MOUNT_SENSOR_ARRAY_II", "MOUNT_SENSOR_ARRAY_III", "MOUNT_MINING_LASER_I", "MOUNT_MINING_LASER_II", "MOUNT_MINING_LASER_III", "MOUNT_LASER_CANNON_I", "MOUNT_MISSILE_LAUNCHER_I", "MOUNT_TURRET_I"], true);
+}
+
+#[test]
+fn test_waypoint_conforms() {
+    round_trip::<schema::Waypoint>("Waypoint", include_str!("../../spec/examples/Waypoint.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_waypoint_survives_round_trip(json in perturbed(include_str!("../../spec/examples/Waypoint.json"))) {
+        survives_round_trip::<schema::Waypoint>(&json)?;
+    }
+}
+
+#[test]
+fn test_waypoint_faction_conforms() {
+    round_trip::<schema::WaypointFaction>("WaypointFaction", include_str!("../../spec/examples/WaypointFaction.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_waypoint_faction_survives_round_trip(json in perturbed(include_str!("../../spec/examples/WaypointFaction.json"))) {
+        survives_round_trip::<schema::WaypointFaction>(&json)?;
+    }
+}
+
+#[test]
+fn test_waypoint_orbital_conforms() {
+    round_trip::<schema::WaypointOrbital>("WaypointOrbital", include_str!("../../spec/examples/WaypointOrbital.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_waypoint_orbital_survives_round_trip(json in perturbed(include_str!("../../spec/examples/WaypointOrbital.json"))) {
+        survives_round_trip::<schema::WaypointOrbital>(&json)?;
+    }
+}
+
+#[test]
+fn test_waypoint_trait_conforms() {
+    round_trip::<schema::WaypointTrait>("WaypointTrait", include_str!("../../spec/examples/WaypointTrait.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_waypoint_trait_survives_round_trip(json in perturbed(include_str!("../../spec/examples/WaypointTrait.json"))) {
+        survives_round_trip::<schema::WaypointTrait>(&json)?;
+    }
+}
+
+#[test]
+fn test_waypoint_type_conforms() {
+    round_trip::<schema::WaypointType>("WaypointType", include_str!("../../spec/examples/WaypointType.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_waypoint_type_survives_round_trip(json in perturbed(include_str!("../../spec/examples/WaypointType.json"))) {
+        survives_round_trip::<schema::WaypointType>(&json)?;
+    }
+}
+
+#[test]
+fn test_waypoint_type_names() {
+    names::<schema::WaypointType>(&["PLANET", "GAS_GIANT", "MOON", "ORBITAL_STATION", "JUMP_GATE", "ASTEROID_FIELD", "NEBULA", "DEBRIS_FIELD", "GRAVITY_WELL"], true);
+}
+
+#[test]
+fn test_get_status_response_conforms() {
+    round_trip::<responses::GetStatus>("GetStatus", include_str!("../../spec/examples/responses/GetStatus.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_status_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetStatus.json"))) {
+        survives_round_trip::<responses::GetStatus>(&json)?;
+    }
+}
+
+#[test]
+fn test_register_response_conforms() {
+    round_trip::<responses::Register>("Register", include_str!("../../spec/examples/responses/Register.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_register_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/Register.json"))) {
+        survives_round_trip::<responses::Register>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_my_agent_response_conforms() {
+    round_trip::<responses::agents::MyAgentDetails>("GetMyAgent", include_str!("../../spec/examples/responses/GetMyAgent.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_my_agent_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetMyAgent.json"))) {
+        survives_round_trip::<responses::agents::MyAgentDetails>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_systems_response_conforms() {
+    round_trip::<responses::systems::ListSystems>("GetSystems", include_str!("../../spec/examples/responses/GetSystems.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_systems_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetSystems.json"))) {
+        survives_round_trip::<responses::systems::ListSystems>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_system_response_conforms() {
+    round_trip::<responses::systems::GetSystem>("GetSystem", include_str!("../../spec/examples/responses/GetSystem.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_system_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetSystem.json"))) {
+        survives_round_trip::<responses::systems::GetSystem>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_system_waypoints_response_conforms() {
+    round_trip::<responses::systems::ListWaypoints>("GetSystemWaypoints", include_str!("../../spec/examples/responses/GetSystemWaypoints.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_system_waypoints_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetSystemWaypoints.json"))) {
+        survives_round_trip::<responses::systems::ListWaypoints>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_waypoint_response_conforms() {
+    round_trip::<responses::systems::GetWaypoint>("GetWaypoint", include_str!("../../spec/examples/responses/GetWaypoint.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_waypoint_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetWaypoint.json"))) {
+        survives_round_trip::<responses::systems::GetWaypoint>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_market_response_conforms() {
+    round_trip::<responses::systems::GetMarket>("GetMarket", include_str!("../../spec/examples/responses/GetMarket.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_market_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetMarket.json"))) {
+        survives_round_trip::<responses::systems::GetMarket>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_shipyard_response_conforms() {
+    round_trip::<responses::systems::GetShipyard>("GetShipyard", include_str!("../../spec/examples/responses/GetShipyard.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_shipyard_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetShipyard.json"))) {
+        survives_round_trip::<responses::systems::GetShipyard>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_jump_gate_response_conforms() {
+    round_trip::<responses::systems::GetJumpGate>("GetJumpGate", include_str!("../../spec/examples/responses/GetJumpGate.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_jump_gate_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetJumpGate.json"))) {
+        survives_round_trip::<responses::systems::GetJumpGate>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_contracts_response_conforms() {
+    round_trip::<responses::contracts::ListContracts>("GetContracts", include_str!("../../spec/examples/responses/GetContracts.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_contracts_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetContracts.json"))) {
+        survives_round_trip::<responses::contracts::ListContracts>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_contract_response_conforms() {
+    round_trip::<responses::contracts::GetContract>("GetContract", include_str!("../../spec/examples/responses/GetContract.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_contract_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetContract.json"))) {
+        survives_round_trip::<responses::contracts::GetContract>(&json)?;
+    }
+}
+
+#[test]
+fn test_accept_contract_response_conforms() {
+    round_trip::<responses::contracts::AcceptContract>("AcceptContract", include_str!("../../spec/examples/responses/AcceptContract.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_accept_contract_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/AcceptContract.json"))) {
+        survives_round_trip::<responses::contracts::AcceptContract>(&json)?;
+    }
+}
+
+#[test]
+fn test_deliver_contract_response_conforms() {
+    round_trip::<responses::contracts::DeliverContract>("DeliverContract", include_str!("../../spec/examples/responses/DeliverContract.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_deliver_contract_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/DeliverContract.json"))) {
+        survives_round_trip::<responses::contracts::DeliverContract>(&json)?;
+    }
+}
+
+#[test]
+fn test_fulfill_contract_response_conforms() {
+    round_trip::<responses::contracts::FulfillContract>("FulfillContract", include_str!("../../spec/examples/responses/FulfillContract.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_fulfill_contract_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/FulfillContract.json"))) {
+        survives_round_trip::<responses::contracts::FulfillContract>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_factions_response_conforms() {
+    round_trip::<responses::factions::ListFactions>("GetFactions", include_str!("../../spec/examples/responses/GetFactions.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_factions_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetFactions.json"))) {
+        survives_round_trip::<responses::factions::ListFactions>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_faction_response_conforms() {
+    round_trip::<responses::factions::GetFaction>("GetFaction", include_str!("../../spec/examples/responses/GetFaction.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_faction_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetFaction.json"))) {
+        survives_round_trip::<responses::factions::GetFaction>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_my_ships_response_conforms() {
+    round_trip::<responses::fleet::ListShips>("GetMyShips", include_str!("../../spec/examples/responses/GetMyShips.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_my_ships_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetMyShips.json"))) {
+        survives_round_trip::<responses::fleet::ListShips>(&json)?;
+    }
+}
+
+#[test]
+fn test_purchase_ship_response_conforms() {
+    round_trip::<responses::fleet::PurchaseShip>("PurchaseShip", include_str!("../../spec/examples/responses/PurchaseShip.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_purchase_ship_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/PurchaseShip.json"))) {
+        survives_round_trip::<responses::fleet::PurchaseShip>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_my_ship_response_conforms() {
+    round_trip::<responses::fleet::GetShip>("GetMyShip", include_str!("../../spec/examples/responses/GetMyShip.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_my_ship_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetMyShip.json"))) {
+        survives_round_trip::<responses::fleet::GetShip>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_my_ship_cargo_response_conforms() {
+    round_trip::<responses::fleet::GetShipCargo>("GetMyShipCargo", include_str!("../../spec/examples/responses/GetMyShipCargo.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_my_ship_cargo_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetMyShipCargo.json"))) {
+        survives_round_trip::<responses::fleet::GetShipCargo>(&json)?;
+    }
+}
+
+#[test]
+fn test_orbit_ship_response_conforms() {
+    round_trip::<responses::fleet::OrbitShip>("OrbitShip", include_str!("../../spec/examples/responses/OrbitShip.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_orbit_ship_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/OrbitShip.json"))) {
+        survives_round_trip::<responses::fleet::OrbitShip>(&json)?;
+    }
+}
+
+#[test]
+fn test_ship_refine_response_conforms() {
+    round_trip::<responses::fleet::ShipRefine>("ShipRefine", include_str!("../../spec/examples/responses/ShipRefine.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_ship_refine_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/ShipRefine.json"))) {
+        survives_round_trip::<responses::fleet::ShipRefine>(&json)?;
+    }
+}
+
+#[test]
+fn test_create_chart_response_conforms() {
+    round_trip::<responses::fleet::CreateChart>("CreateChart", include_str!("../../spec/examples/responses/CreateChart.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_create_chart_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/CreateChart.json"))) {
+        survives_round_trip::<responses::fleet::CreateChart>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_ship_cooldown_response_conforms() {
+    round_trip::<responses::fleet::GetShipCooldown>("GetShipCooldown", include_str!("../../spec/examples/responses/GetShipCooldown.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_ship_cooldown_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetShipCooldown.json"))) {
+        survives_round_trip::<responses::fleet::GetShipCooldown>(&json)?;
+    }
+}
+
+#[test]
+fn test_dock_ship_response_conforms() {
+    round_trip::<responses::fleet::DockShip>("DockShip", include_str!("../../spec/examples/responses/DockShip.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_dock_ship_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/DockShip.json"))) {
+        survives_round_trip::<responses::fleet::DockShip>(&json)?;
+    }
+}
+
+#[test]
+fn test_create_survey_response_conforms() {
+    round_trip::<responses::fleet::CreateSurvey>("CreateSurvey", include_str!("../../spec/examples/responses/CreateSurvey.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_create_survey_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/CreateSurvey.json"))) {
+        survives_round_trip::<responses::fleet::CreateSurvey>(&json)?;
+    }
+}
+
+#[test]
+fn test_extract_resources_response_conforms() {
+    round_trip::<responses::fleet::ExtractResources>("ExtractResources", include_str!("../../spec/examples/responses/ExtractResources.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_extract_resources_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/ExtractResources.json"))) {
+        survives_round_trip::<responses::fleet::ExtractResources>(&json)?;
+    }
+}
+
+#[test]
+fn test_jettison_response_conforms() {
+    round_trip::<responses::fleet::JettisonCargo>("Jettison", include_str!("../../spec/examples/responses/Jettison.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_jettison_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/Jettison.json"))) {
+        survives_round_trip::<responses::fleet::JettisonCargo>(&json)?;
+    }
+}
+
+#[test]
+fn test_jump_ship_response_conforms() {
+    round_trip::<responses::fleet::JumpShip>("JumpShip", include_str!("../../spec/examples/responses/JumpShip.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_jump_ship_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/JumpShip.json"))) {
+        survives_round_trip::<responses::fleet::JumpShip>(&json)?;
+    }
+}
+
+#[test]
+fn test_navigate_ship_response_conforms() {
+    round_trip::<responses::fleet::NavigateShip>("NavigateShip", include_str!("../../spec/examples/responses/NavigateShip.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_navigate_ship_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/NavigateShip.json"))) {
+        survives_round_trip::<responses::fleet::NavigateShip>(&json)?;
+    }
+}
+
+#[test]
+fn test_patch_ship_nav_response_conforms() {
+    round_trip::<responses::fleet::PatchShipNav>("PatchShipNav", include_str!("../../spec/examples/responses/PatchShipNav.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_patch_ship_nav_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/PatchShipNav.json"))) {
+        survives_round_trip::<responses::fleet::PatchShipNav>(&json)?;
+    }
+}
+
+#[test]
+fn test_get_ship_nav_response_conforms() {
+    round_trip::<responses::fleet::GetShipNav>("GetShipNav", include_str!("../../spec/examples/responses/GetShipNav.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_get_ship_nav_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/GetShipNav.json"))) {
+        survives_round_trip::<responses::fleet::GetShipNav>(&json)?;
+    }
+}
+
+#[test]
+fn test_warp_ship_response_conforms() {
+    round_trip::<responses::fleet::WarpShip>("WarpShip", include_str!("../../spec/examples/responses/WarpShip.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_warp_ship_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/WarpShip.json"))) {
+        survives_round_trip::<responses::fleet::WarpShip>(&json)?;
+    }
+}
+
+#[test]
+fn test_sell_cargo_response_conforms() {
+    round_trip::<responses::fleet::SellCargo>("SellCargo", include_str!("../../spec/examples/responses/SellCargo.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_sell_cargo_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/SellCargo.json"))) {
+        survives_round_trip::<responses::fleet::SellCargo>(&json)?;
+    }
+}
+
+#[test]
+fn test_create_ship_system_scan_response_conforms() {
+    round_trip::<responses::fleet::ScanSystems>("CreateShipSystemScan", include_str!("../../spec/examples/responses/CreateShipSystemScan.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_create_ship_system_scan_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/CreateShipSystemScan.json"))) {
+        survives_round_trip::<responses::fleet::ScanSystems>(&json)?;
+    }
+}
+
+#[test]
+fn test_create_ship_waypoint_scan_response_conforms() {
+    round_trip::<responses::fleet::ScanWaypoints>("CreateShipWaypointScan", include_str!("../../spec/examples/responses/CreateShipWaypointScan.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_create_ship_waypoint_scan_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/CreateShipWaypointScan.json"))) {
+        survives_round_trip::<responses::fleet::ScanWaypoints>(&json)?;
+    }
+}
+
+#[test]
+fn test_create_ship_ship_scan_response_conforms() {
+    round_trip::<responses::fleet::ScanShips>("CreateShipShipScan", include_str!("../../spec/examples/responses/CreateShipShipScan.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_create_ship_ship_scan_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/CreateShipShipScan.json"))) {
+        survives_round_trip::<responses::fleet::ScanShips>(&json)?;
+    }
+}
+
+#[test]
+fn test_refuel_ship_response_conforms() {
+    round_trip::<responses::fleet::RefuelShip>("RefuelShip", include_str!("../../spec/examples/responses/RefuelShip.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_refuel_ship_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/RefuelShip.json"))) {
+        survives_round_trip::<responses::fleet::RefuelShip>(&json)?;
+    }
+}
+
+#[test]
+fn test_purchase_cargo_response_conforms() {
+    round_trip::<responses::fleet::PurchaseCargo>("PurchaseCargo", include_str!("../../spec/examples/responses/PurchaseCargo.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_purchase_cargo_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/PurchaseCargo.json"))) {
+        survives_round_trip::<responses::fleet::PurchaseCargo>(&json)?;
+    }
+}
+
+#[test]
+fn test_transfer_cargo_response_conforms() {
+    round_trip::<responses::fleet::TransferCargo>("TransferCargo", include_str!("../../spec/examples/responses/TransferCargo.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_transfer_cargo_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/TransferCargo.json"))) {
+        survives_round_trip::<responses::fleet::TransferCargo>(&json)?;
+    }
+}
+
+#[test]
+fn test_negotiate_contract_response_conforms() {
+    round_trip::<responses::fleet::NegotiateContract>("NegotiateContract", include_str!("../../spec/examples/responses/NegotiateContract.json"));
+}
+
+proptest! {
+    #![proptest_config(config())]
+    #[test]
+    fn test_negotiate_contract_response_survives_round_trip(json in perturbed(include_str!("../../spec/examples/responses/NegotiateContract.json"))) {
+        survives_round_trip::<responses::fleet::NegotiateContract>(&json)?;
+    }
+}
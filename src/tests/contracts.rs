@@ -0,0 +1,217 @@
+use super::fixtures;
+use crate::api::schema;
+use crate::contracts::{Acquisition, ContractEvaluator};
+use chrono::{Duration, Utc};
+use serde_json::json;
+
+fn ship() -> schema::Ship {
+    let mut ship = fixtures::ship("HAULER-1");
+    ship.cargo.capacity = 30;
+    ship.engine.speed = 30;
+    ship
+}
+
+fn contract(units: i32, on_fulfilled: i32, deadline: Duration) -> schema::Contract {
+    serde_json::from_value(json!({
+        "id": "contract-1",
+        "factionSymbol": "COSMIC",
+        "type": "PROCUREMENT",
+        "terms": {
+            "deadline": (Utc::now() + deadline).to_rfc3339(),
+            "payment": { "onAccepted": 1000, "onFulfilled": on_fulfilled },
+            "deliver": [{
+                "tradeSymbol": "IRON_ORE",
+                "destinationSymbol": "X1-DF55-HQ",
+                "unitsRequired": units,
+                "unitsFulfilled": 0
+            }]
+        },
+        "accepted": false,
+        "fulfilled": false,
+        "expiration": (Utc::now() + deadline).to_rfc3339()
+    }))
+    .unwrap()
+}
+
+fn market(symbol: &str, purchase_price: u32) -> schema::Market {
+    fixtures::market(symbol, "IRON_ORE", purchase_price, purchase_price - 5)
+}
+
+fn system(waypoints: serde_json::Value) -> schema::System {
+    serde_json::from_value(json!({
+        "symbol": "X1-DF55",
+        "sectorSymbol": "X1",
+        "type": "RED_STAR",
+        "x": 0,
+        "y": 0,
+        "waypoints": waypoints,
+        "factions": []
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_profitable_contract_is_a_go() {
+    let fleet = vec![ship()];
+    let mut evaluator = ContractEvaluator::new(
+        &system(json!([
+            { "symbol": "X1-DF55-HQ", "type": "PLANET", "x": 0, "y": 0 },
+            { "symbol": "X1-DF55-CHEAP", "type": "MOON", "x": 30, "y": 40 },
+            { "symbol": "X1-DF55-PRICEY", "type": "MOON", "x": 3, "y": 4 }
+        ])),
+        &fleet,
+    );
+    evaluator.add_market(&market("X1-DF55-CHEAP", 20));
+    evaluator.add_market(&market("X1-DF55-PRICEY", 60));
+
+    let evaluation = evaluator.evaluate(&contract(60, 5000, Duration::days(2)));
+    assert!(evaluation.go, "{}", evaluation);
+    assert_eq!(evaluation.ship_symbol.as_ref().unwrap(), "HAULER-1");
+    assert_eq!(evaluation.acquisition_cost, 1200);
+    assert_eq!(evaluation.net_profit, 4800);
+    assert!(evaluation.reasons[0].starts_with("IRON_ORE x60 for X1-DF55-HQ"));
+    match &evaluation.plans[0].acquisition {
+        Acquisition::Buy {
+            waypoint_symbol, ..
+        } => assert_eq!(waypoint_symbol, "X1-DF55-CHEAP"),
+        other => panic!("expected to buy, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_contract_rejected_with_reasons() {
+    let fleet = vec![ship()];
+    let mut evaluator = ContractEvaluator::new(
+        &system(json!([
+            { "symbol": "X1-DF55-HQ", "type": "PLANET", "x": 0, "y": 0 },
+            { "symbol": "X1-DF55-FAR", "type": "MOON", "x": 900, "y": 0 }
+        ])),
+        &fleet,
+    );
+    evaluator.add_market(&market("X1-DF55-FAR", 100));
+
+    // costs more than it pays, and takes far longer than the deadline allows
+    let evaluation = evaluator.evaluate(&contract(300, 1000, Duration::minutes(90)));
+    assert!(!evaluation.go);
+    assert!(evaluation.net_profit < 0);
+    assert!(evaluation.reasons.iter().any(|r| r.contains("deadline")));
+    assert!(evaluation.reasons.iter().any(|r| r.contains("loss")));
+    assert!(evaluation
+        .to_string()
+        .starts_with("contract contract-1: NO GO"));
+
+    let no_market = ContractEvaluator::new(
+        &system(json!([{ "symbol": "X1-DF55-HQ", "type": "PLANET", "x": 0, "y": 0 }])),
+        &fleet,
+    );
+    let evaluation = no_market.evaluate(&contract(10, 5000, Duration::days(1)));
+    assert!(!evaluation.go);
+    assert!(evaluation.ship_symbol.is_none());
+}
+
+#[test]
+fn test_accepted_contract_counts_only_whats_still_to_be_paid() {
+    let fleet = vec![ship()];
+    let mut evaluator = ContractEvaluator::new(
+        &system(json!([
+            { "symbol": "X1-DF55-HQ", "type": "PLANET", "x": 0, "y": 0 },
+            { "symbol": "X1-DF55-CHEAP", "type": "MOON", "x": 30, "y": 40 }
+        ])),
+        &fleet,
+    );
+    evaluator.add_market(&market("X1-DF55-CHEAP", 20));
+    evaluator.set_credits(500);
+
+    // before accepting, the 1000 up front pays for the 1200 of iron
+    let mut contract = contract(60, 5000, Duration::days(2));
+    let evaluation = evaluator.evaluate(&contract);
+    assert!(evaluation.go, "{}", evaluation);
+    assert_eq!(evaluation.payment, 6000);
+    assert_eq!(evaluation.net_profit, 4800);
+
+    // once it's accepted that's already been paid
+    contract.accepted = true;
+    let evaluation = evaluator.evaluate(&contract);
+    assert!(!evaluation.go);
+    assert_eq!(evaluation.payment, 5000);
+    assert_eq!(evaluation.net_profit, 3800);
+    assert!(evaluation
+        .reasons
+        .iter()
+        .any(|r| r == "sourcing costs 1200 but only 500 credits are available"));
+}
+
+#[test]
+fn test_goods_share_the_time_to_the_deadline() {
+    let mut hauler = ship();
+    hauler.nav.route.destination.x = 0;
+    hauler.nav.route.destination.y = 0;
+    let fleet = vec![hauler];
+    let mut evaluator = ContractEvaluator::new(
+        &system(json!([
+            { "symbol": "X1-DF55-HQ", "type": "PLANET", "x": 0, "y": 0 },
+            { "symbol": "X1-DF55-CHEAP", "type": "MOON", "x": 3000, "y": 0 },
+            { "symbol": "X1-DF55-PRICEY", "type": "MOON", "x": 3, "y": 4 }
+        ])),
+        &fleet,
+    );
+    evaluator.safety_margin = Duration::minutes(10);
+    evaluator.add_market(&market("X1-DF55-CHEAP", 20));
+    evaluator.add_market(&market("X1-DF55-PRICEY", 60));
+
+    // there's time to fetch one load from the cheap market, but not two
+    let mut contract = contract(30, 5000, Duration::minutes(75));
+    let iron = contract.terms.deliver[0].clone();
+    contract.terms.deliver.push(iron);
+    let evaluation = evaluator.evaluate(&contract);
+    assert!(evaluation.go, "{}", evaluation);
+    let sources: Vec<_> = evaluation
+        .plans
+        .iter()
+        .map(|plan| match &plan.acquisition {
+            Acquisition::Buy {
+                waypoint_symbol, ..
+            } => waypoint_symbol.as_str(),
+            other => panic!("expected to buy, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(sources, ["X1-DF55-CHEAP", "X1-DF55-PRICEY"]);
+    assert!(evaluation.estimated_time + evaluator.safety_margin <= evaluation.time_left);
+}
+
+#[test]
+fn test_crew_wages_count_against_the_contract() {
+    // a big crew on high wages, so time costs more than iron does
+    let mut hauler = ship();
+    hauler.crew.current = 100;
+    hauler.crew.wages = 2000;
+    let fleet = vec![hauler];
+    let mut evaluator = ContractEvaluator::new(
+        &system(json!([
+            { "symbol": "X1-DF55-HQ", "type": "PLANET", "x": 0, "y": 0 },
+            { "symbol": "X1-DF55-CHEAP", "type": "MOON", "x": 30, "y": 40 },
+            { "symbol": "X1-DF55-PRICEY", "type": "MOON", "x": 3, "y": 4 }
+        ])),
+        &fleet,
+    );
+    evaluator.add_market(&market("X1-DF55-CHEAP", 20));
+    evaluator.add_market(&market("X1-DF55-PRICEY", 60));
+
+    let evaluation = evaluator.evaluate(&contract(60, 5000, Duration::days(2)));
+    match &evaluation.plans[0].acquisition {
+        Acquisition::Buy {
+            waypoint_symbol, ..
+        } => assert_eq!(waypoint_symbol, "X1-DF55-PRICEY"),
+        other => panic!("expected to buy, got {:?}", other),
+    }
+    assert_eq!(evaluation.acquisition_cost, 3600);
+    assert!(evaluation.crew_cost > 0);
+    assert_eq!(
+        evaluation.net_profit,
+        evaluation.payment - evaluation.acquisition_cost - evaluation.crew_cost
+    );
+    assert!(evaluation
+        .reasons
+        .iter()
+        .any(|r| r.contains("and the crew's wages")));
+}
@@ -0,0 +1,67 @@
+use super::fixtures;
+use super::symbols::symbol;
+use crate::api::schema;
+use crate::crew::{wage_cost, CrewAccounts, CrewWarning};
+use std::time::Duration;
+
+fn ship(name: &str, current: i32, required: i32, morale: u8, wages: u32) -> schema::Ship {
+    let mut ship = fixtures::ship(name);
+    ship.crew.current = current;
+    ship.crew.required = required;
+    ship.crew.capacity = 80;
+    ship.crew.morale = morale;
+    ship.crew.wages = wages;
+    ship
+}
+
+#[test]
+fn test_fleet_wages_over_a_period() {
+    let fleet = [
+        ship("MINER-1", 10, 8, 90, 15),
+        ship("HAULER-1", 4, 4, 70, 20),
+        ship("PROBE-1", 0, 0, 0, 0),
+    ];
+    let mut accounts = CrewAccounts::new();
+    accounts.add_fleet(&fleet);
+    assert_eq!(accounts.wages_per_hour(), 150 + 80);
+    assert_eq!(accounts.wages(Duration::from_secs(24 * 60 * 60)), 230 * 24);
+    let by_ship = accounts.wages_by_ship(Duration::from_secs(30 * 60));
+    assert_eq!(by_ship[&symbol::<schema::ShipSymbol>("MINER-1")], 75);
+    assert_eq!(wage_cost(&fleet[0].crew, Duration::from_secs(90)), 4);
+    assert!(accounts.warnings().is_empty());
+
+    // a newer copy of a ship replaces the old one
+    accounts.add_ship(&ship("HAULER-1", 2, 4, 70, 20));
+    assert_eq!(accounts.wages_per_hour(), 150 + 40);
+    assert_eq!(accounts.crew(&symbol("HAULER-1")).unwrap().current, 2);
+}
+
+#[test]
+fn test_warns_about_short_crews_and_low_morale() {
+    let mut accounts = CrewAccounts::new().with_low_morale(60);
+    accounts.add_fleet(&[
+        ship("MINER-1", 6, 8, 90, 15),
+        ship("HAULER-1", 4, 4, 35, 20),
+        ship("PROBE-1", 0, 0, 0, 0),
+    ]);
+    let warnings = accounts.warnings();
+    assert_eq!(
+        warnings,
+        vec![
+            CrewWarning::LowMorale {
+                ship_symbol: symbol("HAULER-1"),
+                morale: 35
+            },
+            CrewWarning::Understaffed {
+                ship_symbol: symbol("MINER-1"),
+                current: 6,
+                required: 8
+            },
+        ]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "HAULER-1's crew morale is down to 35"
+    );
+    assert_eq!(warnings[1].to_string(), "MINER-1 has 6 crew but needs 8");
+}
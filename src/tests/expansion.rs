@@ -0,0 +1,240 @@
+use super::fixtures;
+use super::symbols::symbol;
+use crate::api::schema;
+use crate::behavior::MiningCycle;
+use crate::expansion::{ExpansionPlanner, Job};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+fn listing(kind: &str, purchase_price: u32, cargo: u32, laser: u32) -> schema::ShipyardShip {
+    let ship = fixtures::example_ship();
+    let mut engine = ship["engine"].clone();
+    engine["speed"] = json!(30);
+    let modules = match cargo {
+        0 => json!([]),
+        _ => json!([{
+            "symbol": "MODULE_CARGO_HOLD_I",
+            "name": "Cargo Hold",
+            "capacity": cargo,
+            "requirements": {}
+        }]),
+    };
+    let mounts = match laser {
+        0 => json!([]),
+        _ => json!([{
+            "symbol": "MOUNT_MINING_LASER_I",
+            "name": "Mining Laser I",
+            "strength": laser,
+            "requirements": {}
+        }]),
+    };
+    serde_json::from_value(json!({
+        "type": kind,
+        "name": kind,
+        "description": "",
+        "purchasePrice": purchase_price,
+        "frame": ship["frame"],
+        "reactor": ship["reactor"],
+        "engine": engine,
+        "modules": modules,
+        "mounts": mounts
+    }))
+    .unwrap()
+}
+
+fn shipyard(ships: Vec<schema::ShipyardShip>) -> schema::Shipyard {
+    serde_json::from_value(json!({
+        "symbol": "X1-DF55-YARD",
+        "shipTypes": [],
+        "transactions": [],
+        "ships": ships
+    }))
+    .unwrap()
+}
+
+fn market(symbol: &str, purchase_price: u32, sell_price: u32) -> schema::Market {
+    fixtures::market(symbol, "IRON", purchase_price, sell_price)
+}
+
+fn planner() -> ExpansionPlanner {
+    let system = serde_json::from_value(json!({
+        "symbol": "X1-DF55",
+        "sectorSymbol": "X1",
+        "type": "RED_STAR",
+        "x": 0,
+        "y": 0,
+        "waypoints": [],
+        "factions": []
+    }))
+    .unwrap();
+    let mut planner = ExpansionPlanner::new(&system);
+    planner.add_waypoint(&symbol("X1-DF55-A"), 0, 0);
+    planner.add_waypoint(&symbol("X1-DF55-B"), 40, 0);
+    planner.add_asteroid_field(&symbol("X1-DF55-FIELD"), 30, 40);
+    // iron sells for a little more at B than it costs at A
+    planner.add_market(&market("X1-DF55-A", 100, 90));
+    planner.add_market(&market("X1-DF55-B", 115, 105));
+    planner.add_shipyard(&shipyard(vec![
+        listing("SHIP_PROBE", 10_000, 0, 0),
+        listing("SHIP_MINING_DRONE", 30_000, 15, 10),
+        listing("SHIP_LIGHT_HAULER", 60_000, 80, 0),
+    ]));
+    planner
+}
+
+#[test]
+fn test_candidates_ranked_by_payback() {
+    let mut planner = planner();
+    let plan = planner.plan();
+    let kinds: Vec<_> = plan
+        .iter()
+        .map(|candidate| candidate.ship_type.clone())
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            schema::ShipType::ShipLightHauler,
+            schema::ShipType::ShipMiningDrone,
+            schema::ShipType::ShipProbe
+        ]
+    );
+    assert_eq!(plan[0].job, Some(Job::Hauling));
+    assert_eq!(plan[1].job, Some(Job::Mining));
+    assert_eq!(plan[2].job, None);
+    assert_eq!(plan[2].payback, None);
+    assert!(plan[0].go && plan[1].go && !plan[2].go);
+    assert_eq!(plan[0].waypoint_symbol, "X1-DF55-YARD");
+
+    // with too little for the hauler, the drone's next best
+    planner.set_credits(50_000);
+    let recommended = planner.recommend().unwrap();
+    assert_eq!(recommended.ship_type, schema::ShipType::ShipMiningDrone);
+    assert!(recommended.go);
+    let hauler = &planner.plan()[0];
+    assert!(!hauler.affordable && !hauler.go);
+    assert!(hauler.to_string().contains("NO GO"));
+
+    planner.reserve = 25_000;
+    assert!(planner.recommend().is_none());
+}
+
+#[test]
+fn test_recorded_cycles_value_what_a_miner_mines() {
+    let mut planner = planner();
+    let guessed = planner.plan()[1].income_per_hour;
+
+    // our miners only made 5 a unit, a fifth of the guess
+    planner.add_mining_cycle(&MiningCycle {
+        ship_symbol: symbol("MINER-1"),
+        extractions: 3,
+        extracted: BTreeMap::from([(schema::TradeSymbol::IronOre, 20)]),
+        jettisoned: BTreeMap::new(),
+        revenue: 110,
+        fuel_cost: 10,
+        crew_cost: 0,
+        duration: std::time::Duration::from_secs(300),
+    });
+    let drone = planner
+        .plan()
+        .into_iter()
+        .find(|candidate| candidate.ship_type == schema::ShipType::ShipMiningDrone)
+        .unwrap();
+    // which makes it worth more hauling than mining
+    assert_eq!(drone.job, Some(Job::Hauling));
+    assert!(drone.income_per_hour < guessed);
+    assert!(drone.income_per_hour > guessed / 5.0);
+    assert!(drone.to_string().contains("made 5.0 a unit"));
+}
+
+#[test]
+fn test_crew_wages_come_out_of_income() {
+    let mut planner = planner();
+    let income = |planner: &ExpansionPlanner, kind| {
+        planner
+            .plan()
+            .into_iter()
+            .find(|candidate| candidate.ship_type == kind)
+            .unwrap()
+            .income_per_hour
+    };
+    let drone = schema::ShipType::ShipMiningDrone;
+    let hauler = schema::ShipType::ShipLightHauler;
+    let (mining, hauling) = (
+        income(&planner, drone.clone()),
+        income(&planner, hauler.clone()),
+    );
+
+    // a crew of four at 50 an hour each
+    let ships = [
+        listing("SHIP_MINING_DRONE", 30_000, 15, 10),
+        listing("SHIP_LIGHT_HAULER", 60_000, 80, 0),
+    ];
+    let ships = ships
+        .into_iter()
+        .map(|mut ship| {
+            ship.frame.requirements.crew = Some(3);
+            ship.engine.requirements.crew = Some(1);
+            ship
+        })
+        .collect();
+    planner.add_shipyard(&shipyard(ships));
+    planner.crew_wages = 50;
+    assert!((mining - 200.0 - income(&planner, drone.clone())).abs() < 1.0);
+    // give or take the wages being rounded to the credit each trip
+    assert!((hauling - 200.0 - income(&planner, hauler)).abs() < 10.0);
+    let plan = planner.plan();
+    let drone = plan.iter().find(|candidate| candidate.ship_type == drone);
+    assert!(drone.unwrap().to_string().contains("after 200 in wages"));
+}
+
+#[cfg(feature = "mock")]
+mod simulated {
+    use super::symbol;
+    use crate::api::schema::ShipRole;
+    use crate::api::SpaceTraderApi;
+    use crate::expansion::ExpansionPlanner;
+    use crate::mock;
+    use crate::sim::Simulator;
+    use chrono::Duration;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_expand_buys_the_recommended_ship() {
+        let sim = Arc::new(Simulator::new(1));
+        let api =
+            SpaceTraderApi::new("token".to_owned()).with_transport(mock::in_memory(sim.clone()));
+        let station = symbol("X1-SIM-STATION");
+        let system = api.get_system(&symbol("X1-SIM")).await.unwrap();
+        let mut planner = ExpansionPlanner::new(&system);
+        for market in ["X1-SIM-HQ", "X1-SIM-MOON", "X1-SIM-STATION"] {
+            planner.add_market(&sim.market(&symbol(market)).unwrap());
+        }
+        planner.max_payback = Duration::days(7);
+
+        sim.add_ship(sim.make_ship(&symbol("SIMULATOR-3"), ShipRole::Satellite, &station));
+        planner.add_shipyard(&api.get_shipyard(&station).await.unwrap());
+        // set_credits is only for planning; buying needs the real balance
+        planner.set_credits(1_000_000);
+        assert!(planner.expand(&api).await.is_err());
+        assert!(sim.ship(&symbol("SIMULATOR-4")).is_none());
+
+        let credits = api.my_agent().await.unwrap().credits;
+        planner.reserve = credits - 40_000;
+        assert!(planner.expand(&api).await.unwrap().is_none());
+
+        // the same shipyard again replaces it, and one seen from afar
+        // doesn't wipe out what's listed
+        planner.reserve = 0;
+        planner.add_shipyard(&api.get_shipyard(&station).await.unwrap());
+        let mut afar = api.get_shipyard(&station).await.unwrap();
+        afar.ships.clear();
+        planner.add_shipyard(&afar);
+        assert_eq!(planner.plan().len(), 2);
+        let bought = planner.expand(&api).await.unwrap().unwrap();
+        assert_eq!(bought.ship.cargo.capacity, 15);
+        assert_eq!(bought.agent.credits, credits - 45_000);
+        let ship = sim.ship(&bought.ship.symbol).unwrap();
+        assert_eq!(ship.nav.waypoint_symbol, station);
+        assert_eq!(ship.mounts.len(), 1);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::api::schema;
+use chrono::{DateTime, FixedOffset, SecondsFormat, TimeZone};
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+fn timestamp() -> impl Strategy<Value = String> {
+    // 2000 to 2100, in any offset, at any precision the API might use
+    let formats = [
+        SecondsFormat::Secs,
+        SecondsFormat::Millis,
+        SecondsFormat::Micros,
+        SecondsFormat::Nanos,
+    ];
+    (
+        946_684_800i64..4_102_444_800,
+        0u32..1_000_000_000,
+        -12i32..=14,
+        proptest::sample::select(formats.to_vec()),
+    )
+        .prop_map(|(seconds, nanos, hours, format)| {
+            let offset = FixedOffset::east_opt(hours * 3600).unwrap();
+            let date = offset.timestamp_opt(seconds, nanos).unwrap();
+            date.to_rfc3339_opts(format, hours == 0)
+        })
+}
+
+fn is_enum_value(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn vary(value: Value) -> BoxedStrategy<Value> {
+    //! something shaped like value that the same type should accept:
+    //! other dates, numbers and text, and lists of other lengths.
+    //! anything that looks like an enum value is left alone
+    match value {
+        Value::String(s) if DateTime::parse_from_rfc3339(&s).is_ok() => {
+            timestamp().prop_map(Value::String).boxed()
+        }
+        Value::String(s) if is_enum_value(&s) => Just(Value::String(s)).boxed(),
+        Value::String(_) => proptest::collection::vec(any::<char>(), 0..16)
+            .prop_map(|chars| Value::String(chars.into_iter().collect()))
+            .boxed(),
+        // the examples hold each number's minimum, and nothing we decode into is smaller than a u8
+        Value::Number(n) if n.is_u64() => {
+            let n = n.as_u64().unwrap();
+            (n..=n + 100).prop_map(Value::from).boxed()
+        }
+        Value::Number(n) if n.is_f64() => (0.0..1000.0f64).prop_map(Value::from).boxed(),
+        Value::Array(items) => match items.into_iter().next() {
+            Some(item) => proptest::collection::vec(vary(item), 0..4)
+                .prop_map(Value::Array)
+                .boxed(),
+            None => Just(json!([])).boxed(),
+        },
+        Value::Object(fields) => {
+            let (keys, values): (Vec<String>, Vec<BoxedStrategy<Value>>) = fields
+                .into_iter()
+                .map(|(key, value)| (key, vary(value)))
+                .unzip();
+            values
+                .prop_map(move |values| Value::Object(keys.iter().cloned().zip(values).collect()))
+                .boxed()
+        }
+        value => Just(value).boxed(),
+    }
+}
+
+pub fn config() -> ProptestConfig {
+    // a hundred or so of these run per `cargo test`, and the bigger types are slow to generate
+    ProptestConfig::with_cases(64)
+}
+
+pub fn perturbed(example: &str) -> BoxedStrategy<Value> {
+    vary(serde_json::from_str(example).unwrap())
+}
+
+fn compare(path: &str, expected: &Value, actual: &Value) -> Result<(), String> {
+    //! whether actual says the same as expected, under the same names.
+    //! dates only have to be the same instant, written in UTC
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, value) in expected {
+                match actual.get(key) {
+                    Some(actual) => compare(&format!("{path}.{key}"), value, actual)?,
+                    None if value.is_null() => {}
+                    None => return Err(format!("{path}.{key} went missing")),
+                }
+            }
+            match actual
+                .iter()
+                .find(|(key, value)| !expected.contains_key(*key) && !value.is_null())
+            {
+                Some((key, _)) => Err(format!("{path}.{key} appeared from nowhere")),
+                None => Ok(()),
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (expected, actual) in expected.iter().zip(actual) {
+                compare(&format!("{path}[]"), expected, actual)?;
+            }
+            Ok(())
+        }
+        (Value::String(expected), Value::String(actual)) if expected != actual => {
+            match (
+                DateTime::parse_from_rfc3339(expected),
+                DateTime::parse_from_rfc3339(actual),
+            ) {
+                (Ok(e), Ok(a)) if e == a && actual.ends_with('Z') => Ok(()),
+                _ => Err(format!("{path} was {expected:?}, came back {actual:?}")),
+            }
+        }
+        (Value::Number(e), Value::Number(a)) if e.as_f64() == a.as_f64() => Ok(()),
+        (expected, actual) if expected == actual => Ok(()),
+        _ => Err(format!("{path} was {expected}, came back {actual}")),
+    }
+}
+
+pub fn survives_round_trip<T: Serialize + DeserializeOwned>(
+    json: &Value,
+) -> Result<(), TestCaseError> {
+    //! decode, encode, decode again: the encoding has to say what the
+    //! original did, and decoding it has to give the same thing back
+    let decoded: T = serde_json::from_value(json.clone())
+        .map_err(|e| TestCaseError::fail(format!("doesn't decode: {e}")))?;
+    let encoded = serde_json::to_value(&decoded).unwrap();
+    compare("", json, &encoded).map_err(TestCaseError::fail)?;
+    let again: T = serde_json::from_value(encoded.clone())
+        .map_err(|e| TestCaseError::fail(format!("doesn't decode its own output: {e}")))?;
+    prop_assert_eq!(serde_json::to_value(&again).unwrap(), encoded);
+    Ok(())
+}
+
+macro_rules! round_trip_test {
+    ($name:ident, $struct_type:ty, $file_path:expr) => {
+        proptest! {
+            #![proptest_config(config())]
+            #[test]
+            fn $name(json in perturbed(include_str!($file_path))) {
+                survives_round_trip::<$struct_type>(&json)?;
+            }
+        }
+    };
+}
+
+round_trip_test!(
+    test_contract_survives_round_trip,
+    schema::Contract,
+    "../../schema-examples/Contract.json"
+);
+round_trip_test!(
+    test_faction_survives_round_trip,
+    schema::Faction,
+    "../../schema-examples/Faction.json"
+);
+round_trip_test!(
+    test_jump_gate_survives_round_trip,
+    schema::JumpGate,
+    "../../schema-examples/JumpGate.json"
+);
+round_trip_test!(
+    test_market_survives_round_trip,
+    schema::Market,
+    "../../schema-examples/Market.json"
+);
+round_trip_test!(
+    test_scanned_waypoint_survives_round_trip,
+    schema::ScannedWaypoint,
+    "../../schema-examples/ScannedWaypoint.json"
+);
+round_trip_test!(
+    test_ship_survives_round_trip,
+    schema::Ship,
+    "../../schema-examples/Ship.json"
+);
+round_trip_test!(
+    test_ship_nav_survives_round_trip,
+    schema::ShipNav,
+    "../../schema-examples/ShipNav.json"
+);
+round_trip_test!(
+    test_shipyard_survives_round_trip,
+    schema::Shipyard,
+    "../../schema-examples/Shipyard.json"
+);
+round_trip_test!(
+    test_survey_survives_round_trip,
+    schema::Survey,
+    "../../schema-examples/Survey.json"
+);
+round_trip_test!(
+    test_system_survives_round_trip,
+    schema::System,
+    "../../schema-examples/System.json"
+);
+round_trip_test!(
+    test_waypoint_survives_round_trip,
+    schema::Waypoint,
+    "../../schema-examples/Waypoint.json"
+);
+round_trip_test!(
+    test_waypoint_trait_survives_round_trip,
+    schema::WaypointTrait,
+    "../../schema-examples/WaypointTrait.json"
+);
+
+#[test]
+fn test_dates_come_back_in_utc() {
+    let cooldown: schema::Cooldown = serde_json::from_value(json!({
+        "shipSymbol": "SHIP-1",
+        "totalSeconds": 70,
+        "remainingSeconds": 0,
+        "expiration": "2023-05-20T14:15:22.5+02:00",
+    }))
+    .unwrap();
+    let encoded = serde_json::to_value(&cooldown).unwrap();
+    assert_eq!(encoded["expiration"], "2023-05-20T12:15:22.500Z");
+}
+
+#[test]
+fn test_clones_and_names() {
+    let ship: schema::Ship =
+        serde_json::from_str(include_str!("../../schema-examples/Ship.json")).unwrap();
+    let mut snapshot = ship.clone();
+    assert_eq!(snapshot, ship);
+    snapshot.nav.flight_mode = schema::ShipNavFlightMode::Burn;
+    assert_ne!(snapshot, ship);
+
+    let mut units = std::collections::HashMap::new();
+    units.insert(schema::TradeSymbol::IronOre, 10);
+    assert_eq!(
+        units[&"IRON_ORE".parse::<schema::TradeSymbol>().unwrap()],
+        10
+    );
+    assert_eq!(
+        schema::WaypointType::AsteroidField.to_string(),
+        "ASTEROID_FIELD"
+    );
+    let error = "SPRINT".parse::<schema::ShipNavFlightMode>().unwrap_err();
+    assert_eq!(error.to_string(), "\"SPRINT\" isn't a ShipNavFlightMode");
+
+    // SupplyLevel keeps its own order, and like serde never fails to parse
+    assert_eq!("SCARCE".parse(), Ok(schema::SupplyLevel::Scarce));
+    assert_eq!(
+        "GLUT".parse(),
+        Ok(schema::SupplyLevel::Unknown("GLUT".to_owned()))
+    );
+    assert!(schema::SupplyLevel::Abundant > schema::SupplyLevel::Scarce);
+}
+
+#[test]
+fn test_new_names_dont_fail_the_response() {
+    // the game adds goods, traits and ship parts faster than the spec
+    let mut example = super::fixtures::example_ship();
+    example["cargo"]["inventory"] = json!([{
+        "symbol": "FLUX_CAPACITORS",
+        "name": "Flux capacitors",
+        "description": "",
+        "units": 3
+    }]);
+    example["mounts"][0]["symbol"] = json!("MOUNT_TRACTOR_BEAM_I");
+    let ship: schema::Ship = serde_json::from_value(example.clone()).unwrap();
+    assert_eq!(
+        ship.cargo.inventory[0].symbol,
+        schema::TradeSymbol::Unknown("FLUX_CAPACITORS".to_owned())
+    );
+    assert_eq!(ship.mounts[0].symbol.to_string(), "MOUNT_TRACTOR_BEAM_I");
+    assert_eq!(
+        serde_json::to_value(&ship).unwrap()["cargo"],
+        example["cargo"]
+    );
+
+    assert_eq!(
+        "ASTEROIDS".parse(),
+        Ok(schema::WaypointType::Unknown("ASTEROIDS".to_owned()))
+    );
+    assert_eq!(
+        "IRON_ORE".parse::<schema::TradeSymbol>(),
+        Ok(schema::TradeSymbol::IronOre)
+    );
+}
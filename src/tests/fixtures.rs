@@ -0,0 +1,36 @@
+use super::symbols::symbol;
+use crate::api::schema::{Market, Ship};
+use serde_json::{json, Value};
+
+/// the example ship as it comes, for tests that patch the json before
+/// parsing it
+pub fn example_ship() -> Value {
+    let string = std::fs::read_to_string("schema-examples/Ship.json").unwrap();
+    serde_json::from_str(&string).unwrap()
+}
+
+/// the example ship, called name
+pub fn ship(name: &str) -> Ship {
+    let mut ship: Ship = serde_json::from_value(example_ship()).unwrap();
+    ship.symbol = symbol(name);
+    ship
+}
+
+/// a market that trades only good, 100 at a time
+pub fn market(symbol: &str, good: &str, purchase_price: u32, sell_price: u32) -> Market {
+    serde_json::from_value(json!({
+        "symbol": symbol,
+        "exports": [],
+        "imports": [],
+        "exchange": [],
+        "transactions": [],
+        "tradeGoods": [{
+            "symbol": good,
+            "tradeVolume": 100,
+            "supply": "MODERATE",
+            "purchasePrice": purchase_price,
+            "sellPrice": sell_price
+        }]
+    }))
+    .unwrap()
+}
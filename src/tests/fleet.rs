@@ -0,0 +1,178 @@
+use super::fixtures::example_ship;
+use super::symbols::symbol;
+use crate::api::transport::{BoxFuture, InMemoryTransport, Response};
+use crate::api::{Error, SpaceTraderApi};
+use crate::fleet::{Behavior, Fleet, ShipContext, ShipControl, ShipStatus};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+fn fleet() -> Fleet {
+    //! two ships, ME-1 and ME-2, and nothing else
+    let ship = example_ship();
+    let ships: Vec<Value> = ["ME-1", "ME-2"]
+        .iter()
+        .map(|symbol| {
+            let mut ship = ship.clone();
+            ship["symbol"] = json!(symbol);
+            ship
+        })
+        .collect();
+    let body = json!({ "data": ships, "meta": { "total": 2, "page": 1, "limit": 20 } });
+    let transport = InMemoryTransport::new(move |_| Response {
+        status: 200,
+        headers: Default::default(),
+        body: body.to_string(),
+    });
+    let api = SpaceTraderApi::new("token".to_owned()).with_transport(transport);
+    Fleet::with_client(Arc::new(api)).with_backoff(Duration::from_secs(1), Duration::from_secs(8))
+}
+
+async fn wait_for(control: &ShipControl, done: impl Fn(&ShipStatus) -> bool) {
+    let mut status = control.watch_status();
+    while !done(&status.borrow_and_update()) {
+        status.changed().await.unwrap();
+    }
+}
+
+/// fails the first few times it's run, or panics if asked to
+#[derive(Default)]
+struct Flaky {
+    runs: AtomicU32,
+    failures: u32,
+    panics: bool,
+}
+
+impl Behavior for Flaky {
+    fn run<'a>(&'a self, _: &'a ShipContext) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let run = self.runs.fetch_add(1, Ordering::SeqCst) + 1;
+            match run <= self.failures {
+                true if self.panics => panic!("run {run}"),
+                true => Err(format!("run {run}").into()),
+                false => Ok(()),
+            }
+        })
+    }
+}
+
+/// ticks once a second, forever
+#[derive(Default)]
+struct Ticker {
+    ticks: AtomicU32,
+}
+
+impl Behavior for Ticker {
+    fn run<'a>(&'a self, _: &'a ShipContext) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            loop {
+                self.ticks.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        })
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_start_supervises_every_ship() {
+    let fleet = fleet();
+    let behavior = Arc::new(Flaky::default());
+    let picked = behavior.clone();
+    assert_eq!(fleet.start(move |_| picked.clone()).await.unwrap(), 2);
+    assert_eq!(fleet.ships(), ["ME-1", "ME-2"]);
+    // already running, so nothing new to start
+    assert_eq!(fleet.start(|_| unreachable!()).await.unwrap(), 0);
+    // and the shared client knows about them
+    assert_eq!(fleet.api().agent_state().snapshot().ships.len(), 2);
+
+    for ship in fleet.ships() {
+        let control = fleet.control(&ship).unwrap();
+        wait_for(&control, |status| *status == ShipStatus::Finished).await;
+    }
+    assert_eq!(behavior.runs.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_failures_restart_with_backoff() {
+    let fleet = fleet();
+    let behavior = Arc::new(Flaky {
+        failures: 5,
+        ..Default::default()
+    });
+    let started = Instant::now();
+    let control = fleet.add(&symbol("ME-1"), behavior.clone());
+    wait_for(&control, |status| {
+        matches!(status, ShipStatus::Failed { restarts: 2, .. })
+    })
+    .await;
+    assert_eq!(
+        control.status(),
+        ShipStatus::Failed {
+            error: "run 2".to_owned(),
+            restarts: 2,
+            retry_in: Duration::from_secs(2),
+        }
+    );
+
+    wait_for(&control, |status| *status == ShipStatus::Finished).await;
+    assert_eq!(behavior.runs.load(Ordering::SeqCst), 6);
+    // 1 + 2 + 4 + 8 + 8, since the backoff stops doubling at 8
+    assert_eq!(started.elapsed(), Duration::from_secs(23));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_panics_are_failures_too() {
+    let fleet = fleet();
+    let behavior = Arc::new(Flaky {
+        failures: 1,
+        panics: true,
+        ..Default::default()
+    });
+    let control = fleet.add(&symbol("ME-1"), behavior.clone());
+    wait_for(&control, |status| {
+        matches!(status, ShipStatus::Failed { .. })
+    })
+    .await;
+    match control.status() {
+        ShipStatus::Failed { error, .. } => assert_eq!(error, "panicked: run 1"),
+        status => panic!("{status:?}"),
+    }
+    wait_for(&control, |status| *status == ShipStatus::Finished).await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_pause_resume_and_reassign() {
+    let fleet = fleet();
+    let ticker = Arc::new(Ticker::default());
+    let control = fleet.add(&symbol("ME-1"), ticker.clone());
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    assert_eq!(ticker.ticks.load(Ordering::SeqCst), 3);
+
+    control.pause();
+    wait_for(&control, |status| *status == ShipStatus::Paused).await;
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    assert_eq!(ticker.ticks.load(Ordering::SeqCst), 3);
+
+    control.resume();
+    wait_for(&control, |status| *status == ShipStatus::Running).await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(ticker.ticks.load(Ordering::SeqCst), 4);
+
+    let flaky = Arc::new(Flaky::default());
+    control.reassign(flaky.clone());
+    wait_for(&control, |status| *status == ShipStatus::Finished).await;
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    assert_eq!(ticker.ticks.load(Ordering::SeqCst), 4);
+    assert_eq!(flaky.runs.load(Ordering::SeqCst), 1);
+
+    // a finished ship picks up whatever it's given next
+    control.reassign(ticker.clone());
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(ticker.ticks.load(Ordering::SeqCst), 5);
+
+    assert!(fleet.remove(&symbol("ME-1")));
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    assert_eq!(ticker.ticks.load(Ordering::SeqCst), 5);
+}
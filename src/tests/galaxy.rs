@@ -0,0 +1,102 @@
+use super::symbols::symbol;
+use crate::api::schema::{JumpGate, SystemSymbol, Waypoint, WaypointTraitSymbol};
+use crate::galaxy::Galaxy;
+use serde_json::json;
+
+fn waypoint(symbol: &str, uncharted: bool) -> Waypoint {
+    let traits = match uncharted {
+        true => json!([{ "symbol": "UNCHARTED", "name": "Uncharted", "description": "" }]),
+        false => json!([]),
+    };
+    let system = &symbol[..symbol.rfind('-').unwrap()];
+    serde_json::from_value(json!({
+        "symbol": symbol,
+        "type": "JUMP_GATE",
+        "systemSymbol": system,
+        "x": 0,
+        "y": 0,
+        "orbitals": [],
+        "traits": traits,
+    }))
+    .unwrap()
+}
+
+fn gate(connected: &[&str]) -> JumpGate {
+    let connected: Vec<_> = connected
+        .iter()
+        .map(|system| {
+            json!({
+                "symbol": system,
+                "sectorSymbol": "X1",
+                "type": "RED_STAR",
+                "x": 0,
+                "y": 0,
+                "distance": 0,
+            })
+        })
+        .collect();
+    serde_json::from_value(json!({ "jumpRange": 2000, "connectedSystems": connected })).unwrap()
+}
+
+#[test]
+fn test_charted_waypoints_stay_charted() {
+    let galaxy = Galaxy::open_in_memory().unwrap();
+    galaxy
+        .record_waypoints(&[waypoint("X1-A-GATE", true), waypoint("X1-A-MOON", true)])
+        .unwrap();
+    let system = symbol("X1-A");
+    assert_eq!(galaxy.uncharted_in_system(&system).unwrap().len(), 2);
+    assert_eq!(galaxy.uncharted_systems().unwrap(), vec![system.clone()]);
+
+    galaxy
+        .record_waypoint(&waypoint("X1-A-MOON", false))
+        .unwrap();
+    // an older copy from somewhere doesn't put it back
+    galaxy
+        .record_waypoint(&waypoint("X1-A-MOON", true))
+        .unwrap();
+    let uncharted = galaxy.uncharted_in_system(&system).unwrap();
+    assert_eq!(uncharted.len(), 1);
+    assert_eq!(uncharted[0].symbol, "X1-A-GATE");
+    let moon = galaxy.waypoint(&symbol("X1-A-MOON")).unwrap().unwrap();
+    assert!(!moon
+        .traits
+        .iter()
+        .any(|t| t.symbol == WaypointTraitSymbol::Uncharted));
+    assert_eq!(galaxy.waypoints_in_system(&system).unwrap().len(), 2);
+    assert_eq!(galaxy.systems().unwrap(), vec![system]);
+}
+
+#[test]
+fn test_jump_route_takes_the_fewest_jumps() {
+    let galaxy = Galaxy::open_in_memory().unwrap();
+    galaxy
+        .record_jump_gate(&symbol("X1-A-GATE"), &gate(&["X1-B", "X1-C"]))
+        .unwrap();
+    galaxy
+        .record_jump_gate(&symbol("X1-B-GATE"), &gate(&["X1-A", "X1-D"]))
+        .unwrap();
+    galaxy
+        .record_jump_gate(&symbol("X1-C-GATE"), &gate(&["X1-A", "X1-D", "X1-E"]))
+        .unwrap();
+    assert_eq!(galaxy.jump_gates().unwrap().len(), 3);
+    assert_eq!(
+        galaxy.jump_gate(&symbol("X1-B-GATE")).unwrap().unwrap(),
+        gate(&["X1-A", "X1-D"])
+    );
+
+    let from: SystemSymbol = symbol("X1-A");
+    let route = |to: &str| {
+        galaxy
+            .jump_route(&from, |system| system.as_str() == to)
+            .unwrap()
+            .map(|route| route.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+    };
+    assert_eq!(
+        route("X1-E"),
+        Some(vec!["X1-C".to_owned(), "X1-E".to_owned()])
+    );
+    assert_eq!(route("X1-D").unwrap().len(), 2);
+    assert_eq!(route("X1-A"), Some(vec![]));
+    assert_eq!(route("X1-Z"), None);
+}
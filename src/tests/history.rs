@@ -0,0 +1,189 @@
+use super::fixtures;
+use super::symbols::symbol;
+use crate::api::schema::{self, ShipType, SupplyLevel, TradeSymbol};
+use crate::history::PriceHistory;
+use chrono::{Duration, Utc};
+use serde_json::json;
+
+fn market(symbol: &str, purchase_price: u32, sell_price: u32) -> schema::Market {
+    fixtures::market(symbol, "IRON_ORE", purchase_price, sell_price)
+}
+
+fn shipyard(symbol: &str, prices: &[(ShipType, u32)]) -> schema::Shipyard {
+    let example = fixtures::example_ship();
+    let ships: Vec<_> = prices
+        .iter()
+        .map(|(kind, price)| {
+            json!({
+                "type": kind,
+                "name": kind.to_string(),
+                "description": "",
+                "purchasePrice": price,
+                "frame": example["frame"],
+                "reactor": example["reactor"],
+                "engine": example["engine"],
+                "modules": example["modules"],
+                "mounts": example["mounts"],
+            })
+        })
+        .collect();
+    serde_json::from_value(json!({
+        "symbol": symbol,
+        "shipTypes": prices.iter().map(|(kind, _)| json!({ "type": kind })).collect::<Vec<_>>(),
+        "ships": ships,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_latest_price_and_history() {
+    let history = PriceHistory::open_in_memory().unwrap();
+    let earlier = Utc::now() - Duration::hours(2);
+    history
+        .record_at(&market("X1-DF55-20250Z", 40, 35), earlier)
+        .unwrap();
+    history.record(&market("X1-DF55-20250Z", 44, 38)).unwrap();
+
+    let latest = history
+        .latest_price(&symbol("X1-DF55-20250Z"), TradeSymbol::IronOre)
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest.purchase_price, 44);
+    assert_eq!(
+        history
+            .latest_prices(&symbol("X1-DF55-20250Z"))
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let all = history
+        .history(&symbol("X1-DF55-20250Z"), TradeSymbol::IronOre, None)
+        .unwrap();
+    assert_eq!(
+        all.iter().map(|r| r.sell_price).collect::<Vec<_>>(),
+        vec![35, 38]
+    );
+    let recent = history.history(
+        &symbol("X1-DF55-20250Z"),
+        TradeSymbol::IronOre,
+        Some(Utc::now() - Duration::hours(1)),
+    );
+    assert_eq!(recent.unwrap().len(), 1);
+}
+
+#[test]
+fn test_best_buy_and_sell_in_system() {
+    let history = PriceHistory::open_in_memory().unwrap();
+    let earlier = Utc::now() - Duration::hours(2);
+    // the stale price at 17350C shouldn't win over its newer one
+    history
+        .record_at(&market("X1-DF55-17350C", 10, 90), earlier)
+        .unwrap();
+    history.record(&market("X1-DF55-17350C", 50, 45)).unwrap();
+    history.record(&market("X1-DF55-20250Z", 40, 35)).unwrap();
+    history.record(&market("X1-AB12-00001A", 5, 500)).unwrap();
+
+    let buy = history
+        .best_buy(&symbol("X1-DF55"), TradeSymbol::IronOre)
+        .unwrap()
+        .unwrap();
+    assert_eq!(buy.waypoint_symbol, "X1-DF55-20250Z");
+    let sell = history
+        .best_sell(&symbol("X1-DF55"), TradeSymbol::IronOre)
+        .unwrap()
+        .unwrap();
+    assert_eq!(sell.waypoint_symbol, "X1-DF55-17350C");
+    assert_eq!(sell.sell_price, 45);
+    assert_eq!(
+        history.latest_in_system(&symbol("X1-DF55")).unwrap().len(),
+        2
+    );
+}
+
+#[test]
+fn test_supply_levels() {
+    let latest = {
+        let history = PriceHistory::open_in_memory().unwrap();
+        history.record(&market("X1-DF55-20250Z", 40, 35)).unwrap();
+        history
+            .latest_prices(&symbol("X1-DF55-20250Z"))
+            .unwrap()
+            .remove(0)
+    };
+    assert_eq!(latest.trade_symbol, TradeSymbol::IronOre);
+    assert_eq!(latest.supply, SupplyLevel::Moderate);
+
+    assert!(SupplyLevel::Abundant > SupplyLevel::Moderate);
+    assert!(SupplyLevel::Scarce < SupplyLevel::Limited);
+    let unknown: SupplyLevel = serde_json::from_str("\"OVERFLOWING\"").unwrap();
+    assert_eq!(unknown, SupplyLevel::Unknown("OVERFLOWING".to_owned()));
+    assert!(unknown < SupplyLevel::Scarce);
+    // and goes back out as it came in
+    assert_eq!(serde_json::to_string(&unknown).unwrap(), "\"OVERFLOWING\"");
+    assert_eq!(unknown.to_string(), "OVERFLOWING");
+
+    // a level we don't know survives being stored and read back
+    let history = PriceHistory::open_in_memory().unwrap();
+    let mut market = market("X1-DF55-20250Z", 40, 35);
+    market.trade_goods[0].supply = unknown.clone();
+    history.record(&market).unwrap();
+    let latest = history
+        .latest_prices(&symbol("X1-DF55-20250Z"))
+        .unwrap()
+        .remove(0);
+    assert_eq!(latest.supply, unknown);
+}
+
+#[test]
+fn test_ship_prices() {
+    let history = PriceHistory::open_in_memory().unwrap();
+    let earlier = Utc::now() - Duration::hours(2);
+    history
+        .record_shipyard_at(
+            &shipyard("X1-DF55-A", &[(ShipType::ShipProbe, 20_000)]),
+            earlier,
+        )
+        .unwrap();
+    history
+        .record_shipyard(&shipyard(
+            "X1-DF55-A",
+            &[
+                (ShipType::ShipProbe, 24_000),
+                (ShipType::ShipMiningDrone, 40_000),
+            ],
+        ))
+        .unwrap();
+    history
+        .record_shipyard(&shipyard("X1-DF55-B", &[(ShipType::ShipProbe, 22_000)]))
+        .unwrap();
+    // shipyards list nothing without a ship there
+    assert_eq!(
+        history
+            .record_shipyard(&shipyard("X1-DF55-C", &[]))
+            .unwrap(),
+        0
+    );
+
+    let latest = history.ship_prices_in_system(&symbol("X1-DF55")).unwrap();
+    assert_eq!(
+        latest
+            .iter()
+            .map(|record| (record.waypoint_symbol.as_str(), record.purchase_price))
+            .collect::<Vec<_>>(),
+        [
+            ("X1-DF55-B", 22_000),
+            ("X1-DF55-A", 24_000),
+            ("X1-DF55-A", 40_000)
+        ]
+    );
+    let probe = history
+        .best_ship_price(&symbol("X1-DF55"), ShipType::ShipProbe)
+        .unwrap()
+        .unwrap();
+    assert_eq!(probe.waypoint_symbol, "X1-DF55-B");
+    assert!(history
+        .best_ship_price(&symbol("X1-DF55"), ShipType::ShipRefiningFreighter)
+        .unwrap()
+        .is_none());
+}
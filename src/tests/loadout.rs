@@ -0,0 +1,154 @@
+use crate::api::schema::{self, ShipModuleSymbols, ShipMountSymbols};
+use crate::loadout::{compare, Loadout, Resource};
+use serde_json::json;
+
+fn module(symbol: &str, power: i32, crew: i32, capacity: u32) -> serde_json::Value {
+    json!({
+        "symbol": symbol,
+        "capacity": capacity,
+        "name": symbol,
+        "requirements": { "power": power, "crew": crew, "slots": 1 }
+    })
+}
+
+fn mount(symbol: &str, power: i32, crew: i32) -> serde_json::Value {
+    json!({
+        "symbol": symbol,
+        "name": symbol,
+        "strength": 10,
+        "requirements": { "power": power, "crew": crew }
+    })
+}
+
+fn listing(
+    kind: &str,
+    purchase_price: u32,
+    power_output: u32,
+    modules: serde_json::Value,
+    mounts: serde_json::Value,
+) -> schema::ShipyardShip {
+    serde_json::from_value(json!({
+        "type": kind,
+        "name": kind,
+        "description": "",
+        "purchasePrice": purchase_price,
+        "frame": {
+            "symbol": "FRAME_MINER",
+            "name": "Miner",
+            "description": "",
+            "moduleSlots": 3,
+            "mountingPoints": 2,
+            "fuelCapacity": 400,
+            "requirements": { "power": 2, "crew": 1 }
+        },
+        "reactor": {
+            "symbol": "REACTOR_FISSION_I",
+            "name": "Fission Reactor",
+            "description": "",
+            "powerOutput": power_output,
+            "requirements": { "crew": 2 }
+        },
+        "engine": {
+            "symbol": "ENGINE_ION_DRIVE_I",
+            "name": "Ion Drive",
+            "description": "",
+            "speed": 30,
+            "requirements": { "power": 3, "crew": 1 }
+        },
+        "modules": modules,
+        "mounts": mounts
+    }))
+    .unwrap()
+}
+
+fn miner(power_output: u32) -> schema::ShipyardShip {
+    listing(
+        "SHIP_MINING_DRONE",
+        40_000,
+        power_output,
+        json!([
+            module("MODULE_CARGO_HOLD_I", 1, 0, 30),
+            module("MODULE_CREW_QUARTERS_I", 1, 2, 10),
+        ]),
+        json!([
+            mount("MOUNT_MINING_LASER_I", 3, 0),
+            mount("MOUNT_MINING_LASER_I", 3, 0)
+        ]),
+    )
+}
+
+#[test]
+fn test_loadout_within_limits_fits() {
+    let report = Loadout::of_listing(&miner(15)).check();
+    assert!(report.fits(), "{}", report);
+    // frame 2, engine 3, modules 2, lasers 6
+    assert_eq!(report.power, (13, 15));
+    assert_eq!(report.crew, (6, 10));
+    assert_eq!(report.module_slots, (2, 3));
+    assert_eq!(report.mounting_points, (2, 2));
+    assert_eq!(report.spare(Resource::Power), 2);
+    assert!(report.to_string().starts_with("FITS"));
+}
+
+#[test]
+fn test_each_violation_names_what_takes_it() {
+    let surveyor: schema::ShipMount =
+        serde_json::from_value(mount("MOUNT_SURVEYOR_I", 1, 0)).unwrap();
+    let report = Loadout::of_listing(&miner(10))
+        .with_mount(surveyor)
+        .without_module(ShipModuleSymbols::ModuleCrewQuartersI)
+        .check();
+    assert!(!report.fits());
+    let resources: Vec<_> = report.violations.iter().map(|v| v.resource).collect();
+    assert_eq!(
+        resources,
+        vec![Resource::Power, Resource::Crew, Resource::MountingPoints]
+    );
+    let power = &report.violations[0];
+    assert_eq!((power.needed, power.available), (13, 10));
+    assert_eq!(power.takers[0], ("MOUNT_MINING_LASER_I".to_owned(), 6));
+    assert_eq!(
+        power.to_string(),
+        "needs 13 power but only has 10 (MOUNT_MINING_LASER_I 6, ENGINE_ION_DRIVE_I 3, \
+         FRAME_MINER 2, MODULE_CARGO_HOLD_I 1, MOUNT_SURVEYOR_I 1)"
+    );
+    // without the quarters there's nowhere for the crew
+    assert_eq!(report.crew, (4, 0));
+    assert_eq!(report.spare(Resource::MountingPoints), -1);
+
+    // which a refit can sort out
+    let fixed = Loadout::of_listing(&miner(10))
+        .without_mount(ShipMountSymbols::MountMiningLaserI)
+        .check();
+    assert!(fixed.fits(), "{}", fixed);
+}
+
+#[test]
+fn test_compare_puts_what_fits_first() {
+    let ships = vec![
+        miner(10),
+        miner(20),
+        listing("SHIP_PROBE", 5_000, 5, json!([]), json!([])),
+    ];
+    let compared = compare(&ships);
+    let order: Vec<_> = compared
+        .iter()
+        .map(|(ship, report)| {
+            (
+                ship.kind.clone().unwrap(),
+                ship.purchase_price,
+                report.fits(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            (schema::ShipType::ShipMiningDrone, 40_000, true),
+            // no crew quarters, but the most room for a refit
+            (schema::ShipType::ShipProbe, 5_000, false),
+            (schema::ShipType::ShipMiningDrone, 40_000, false),
+        ]
+    );
+    assert_eq!(compared[0].1.power.1, 20);
+}
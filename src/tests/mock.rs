@@ -0,0 +1,102 @@
+use super::symbols::symbol;
+use crate::api::results::ApiError;
+use crate::api::SpaceTraderApi;
+use crate::cache::Cache;
+use crate::mock::MockServer;
+use std::time::{Duration, Instant};
+
+fn client(server: &MockServer) -> SpaceTraderApi {
+    SpaceTraderApi::new("token".to_owned()).with_base_url(&server.url())
+}
+
+#[tokio::test]
+async fn test_client_against_fixtures() {
+    let server = MockServer::start().await.unwrap();
+    let api = client(&server);
+
+    assert_eq!(api.status().await.unwrap().reset_date, "2023-05-20");
+    let system = api.get_system(&symbol("X1-DF55")).await.unwrap();
+    assert_eq!(system.symbol, "X1-DF55");
+    let waypoints = api.list_waypoints(&symbol("X1-DF55")).await.unwrap();
+    assert_eq!(waypoints[0].system_symbol, "X1-DF55");
+    let market = api.get_market(&symbol("X1-DF55-20250Z")).await.unwrap();
+    assert_eq!(market.symbol, "X1-DF55-20250Z");
+
+    assert_eq!(
+        server.requests(),
+        vec![
+            "GET /v2/",
+            "GET /v2/systems/X1-DF55",
+            "GET /v2/systems/X1-DF55/waypoints?page=1&limit=20",
+            "GET /v2/systems/X1-DF55/waypoints/X1-DF55-20250Z/market",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_client_reads_from_cache() {
+    let server = MockServer::start().await.unwrap();
+    let mut api = client(&server).with_cache(Cache::open_in_memory("2023-05-20").unwrap());
+
+    api.get_system(&symbol("X1-DF55")).await.unwrap();
+    api.get_system(&symbol("X1-DF55")).await.unwrap();
+    assert_eq!(server.requests().len(), 1);
+
+    api.set_force_refresh(true);
+    api.get_system(&symbol("X1-DF55")).await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_refresh_skips_the_cache() {
+    let server = MockServer::start().await.unwrap();
+    let api = client(&server).with_cache(Cache::open_in_memory("2023-05-20").unwrap());
+    let market = symbol("X1-DF55-20250Z");
+
+    api.get_market(&market).await.unwrap();
+    api.get_market(&market).await.unwrap();
+    assert_eq!(server.requests().len(), 1);
+    api.refresh_market(&market).await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+    api.refresh_shipyard(&market).await.unwrap();
+    // and what was refreshed is what the cache hands out from then on
+    api.get_shipyard(&market).await.unwrap();
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_scripted_errors() {
+    let server = MockServer::start().await.unwrap();
+    let api = client(&server);
+
+    server.push_error("/systems/X1-DF55", 4203);
+    server.push_rate_limit("*", 1.5);
+    let error = api.get_system(&symbol("X1-DF55")).await.unwrap_err();
+    let error = error.downcast_ref::<ApiError>().unwrap();
+    assert_eq!(error.code, 4203);
+    assert_eq!(error.name(), "navigateInsufficientFuelError");
+
+    let error = api.get_system(&symbol("X1-DF55")).await.unwrap_err();
+    assert_eq!(error.downcast_ref::<ApiError>().unwrap().code, 429);
+
+    // scripted responses are used up, so it's back to the fixtures
+    assert!(api.get_system(&symbol("X1-DF55")).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_scripted_delay_and_overrides() {
+    let server = MockServer::start().await.unwrap();
+    let api = client(&server);
+
+    server.push_delay("/systems/X1-DF55", Duration::from_millis(100));
+    let start = Instant::now();
+    api.get_system(&symbol("X1-DF55")).await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    let mut system = crate::mock::fixture("System");
+    system["symbol"] = "X1-OTHER".into();
+    server.set_data("/systems", serde_json::Value::Array(vec![system; 25]));
+    let systems = api.list_systems().await.unwrap();
+    assert_eq!(systems.len(), 25);
+    assert_eq!(systems[24].symbol, "X1-OTHER");
+}
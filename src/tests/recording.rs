@@ -0,0 +1,153 @@
+use crate::api::transport::{Request, Retrying, Transport};
+use crate::api::SpaceTraderApi;
+use crate::recording::{Recording, Replay};
+use chrono::Utc;
+use reqwest::Method;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+fn recording(path: &str, status: u16, headers: &[(&str, &str)], body: &str) -> Recording {
+    Recording {
+        method: "GET".to_owned(),
+        path: path.to_owned(),
+        request_body: None,
+        status,
+        headers: headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+        body: body.to_owned(),
+        started_at: Utc::now(),
+        duration_ms: 0,
+    }
+}
+
+fn request(path: &str) -> Request {
+    Request {
+        method: Method::GET,
+        base_url: String::new(),
+        path: path.to_owned(),
+        token: String::new(),
+        body: None,
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_replay_gives_back_exactly_what_was_recorded() {
+    let mut slow = recording(
+        "/my/ships",
+        502,
+        &[("content-type", "text/html"), ("x-request-id", "abc")],
+        "<html>bad gateway</html>",
+    );
+    slow.duration_ms = 1500;
+    let replay = Replay::new(vec![slow]);
+
+    let started = Instant::now();
+    let response = replay.send(request("/my/ships")).await.unwrap();
+    assert_eq!(started.elapsed(), Duration::from_millis(1500));
+    assert_eq!(response.status, 502);
+    assert_eq!(response.body, "<html>bad gateway</html>");
+    assert_eq!(
+        response.headers,
+        BTreeMap::from([
+            ("content-type".to_owned(), "text/html".to_owned()),
+            ("x-request-id".to_owned(), "abc".to_owned()),
+        ])
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_replayed_rate_limits_are_waited_out() {
+    let agent = json!({ "data": { "accountId": "1", "symbol": "ME", "headquarters": "X1-A-B", "credits": 10 } });
+    let replay = Arc::new(Replay::new(vec![
+        recording(
+            "/my/agent",
+            429,
+            &[("retry-after", "3")],
+            &json!({ "error": { "message": "slow down", "code": 429 } }).to_string(),
+        ),
+        recording("/my/agent", 200, &[], &agent.to_string()),
+    ]));
+    let api = SpaceTraderApi::new("token".to_owned())
+        .with_transport(Retrying::new(replay.clone(), 3).with_backoff(Duration::from_secs(60)));
+
+    let started = Instant::now();
+    assert_eq!(api.my_agent().await.unwrap().symbol, "ME");
+    assert_eq!(started.elapsed(), Duration::from_secs(3));
+    assert_eq!(replay.remaining(), 0);
+}
+
+#[cfg(feature = "mock")]
+mod simulated {
+    use super::super::symbols::symbol;
+    use crate::api::results::ApiError;
+    use crate::api::SpaceTraderApi;
+    use crate::mock::MockServer;
+    use crate::recording::{self, Recorder, Replay};
+    use std::sync::Arc;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("space-trate-{}-{}.jsonl", name, std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let path = temp_path("record");
+        let server = MockServer::start().await.unwrap();
+        let api = SpaceTraderApi::new("secret-token".to_owned())
+            .with_base_url(&server.url())
+            .with_recorder(Recorder::create(&path).unwrap());
+
+        server.push_error("/my/ships/SHIP-1/orbit", 4214);
+        let system = api.get_system(&symbol("X1-DF55")).await.unwrap();
+        let ships = api.list_ships().await.unwrap();
+        api.orbit_ship(&symbol("SHIP-1")).await.unwrap_err();
+        drop(server);
+
+        let recordings = recording::load(&path).unwrap();
+        assert_eq!(recordings.len(), 3);
+        assert_eq!(recordings[1].method, "GET");
+        assert_eq!(recordings[1].path, "/my/ships?page=1&limit=20");
+        assert_eq!(recordings[2].status, 400);
+        assert_eq!(recordings[2].headers["content-type"], "application/json");
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("secret-token"));
+
+        // the same calls against the replay get the same answers
+        let replay = Arc::new(Replay::open(&path).unwrap());
+        let api = SpaceTraderApi::new("token".to_owned()).with_transport(replay.clone());
+        assert_eq!(
+            api.get_system(&symbol("X1-DF55")).await.unwrap().symbol,
+            system.symbol
+        );
+        assert_eq!(api.list_ships().await.unwrap().len(), ships.len());
+        let error = api.orbit_ship(&symbol("SHIP-1")).await.unwrap_err();
+        assert_eq!(error.downcast_ref::<ApiError>().unwrap().code, 4214);
+        assert_eq!(replay.remaining(), 0);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_replay_off_script() {
+        let path = temp_path("off-script");
+        let server = MockServer::start().await.unwrap();
+        let api = SpaceTraderApi::new("token".to_owned())
+            .with_base_url(&server.url())
+            .with_recorder(Recorder::create(&path).unwrap());
+        api.get_system(&symbol("X1-DF55")).await.unwrap();
+
+        let api =
+            SpaceTraderApi::new("token".to_owned()).with_transport(Replay::open(&path).unwrap());
+        let error = api.get_system(&symbol("X1-OTHER")).await.unwrap_err();
+        let error = error.downcast_ref::<ApiError>().unwrap();
+        assert_eq!(error.code, 6000);
+        assert!(error.message.contains("/systems/X1-DF55"));
+        let error = api.get_system(&symbol("X1-DF55")).await.unwrap_err();
+        assert!(error.to_string().contains("nothing left"));
+        std::fs::remove_file(&path).ok();
+    }
+}
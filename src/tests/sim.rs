@@ -0,0 +1,349 @@
+use super::symbols::symbol;
+use crate::api::results::ApiError;
+use crate::api::schema::{
+    ShipNavStatus, ShipRole, ShipType, TradeSymbol, WaypointTraitSymbol, WaypointType,
+};
+use crate::api::SpaceTraderApi;
+use crate::mock::MockServer;
+use crate::sim::Simulator;
+use chrono::Duration;
+use serde_json::json;
+use std::sync::Arc;
+
+async fn start(sim: &Arc<Simulator>) -> (MockServer, SpaceTraderApi) {
+    let server = MockServer::start_with(sim.clone()).await.unwrap();
+    let api = SpaceTraderApi::new("token".to_owned()).with_base_url(&server.url());
+    (server, api)
+}
+
+fn code(error: crate::api::Error) -> i32 { error.downcast_ref::<ApiError>().unwrap().code }
+
+#[tokio::test]
+async fn test_navigation_uses_fuel_and_time() {
+    let sim = Arc::new(Simulator::new(1));
+    let (_server, api) = start(&sim).await;
+
+    let error = api
+        .navigate_ship(&symbol("SIMULATOR-1"), &symbol("X1-SIM-ASTEROIDS"))
+        .await
+        .unwrap_err();
+    assert_eq!(code(error), 4236);
+
+    api.orbit_ship(&symbol("SIMULATOR-1")).await.unwrap();
+    let navigated = api
+        .navigate_ship(&symbol("SIMULATOR-1"), &symbol("X1-SIM-ASTEROIDS"))
+        .await
+        .unwrap();
+    // 50 units away at cruise, with a speed of 30
+    assert_eq!(navigated.fuel.current, 350);
+    assert_eq!(
+        navigated.nav.route.arrival - sim.now(),
+        Duration::seconds(40)
+    );
+    assert!(matches!(navigated.nav.status, ShipNavStatus::InTransit));
+
+    let error = api
+        .extract_resources(&symbol("SIMULATOR-1"), None)
+        .await
+        .unwrap_err();
+    assert_eq!(code(error), 4214);
+
+    sim.advance(Duration::seconds(40));
+    let nav = api.get_ship_nav(&symbol("SIMULATOR-1")).await.unwrap();
+    assert!(matches!(nav.status, ShipNavStatus::InOrbit));
+    assert_eq!(nav.waypoint_symbol, "X1-SIM-ASTEROIDS");
+}
+
+#[tokio::test]
+async fn test_extraction_and_cooldown() {
+    let sim = Arc::new(Simulator::new(7));
+    let (_server, api) = start(&sim).await;
+    api.orbit_ship(&symbol("SIMULATOR-1")).await.unwrap();
+    api.navigate_ship(&symbol("SIMULATOR-1"), &symbol("X1-SIM-ASTEROIDS"))
+        .await
+        .unwrap();
+    sim.advance(Duration::minutes(1));
+
+    let extracted = api
+        .extract_resources(&symbol("SIMULATOR-1"), None)
+        .await
+        .unwrap();
+    let units = extracted.extraction.yield_amount.units;
+    assert!((3..=7).contains(&units));
+    assert_eq!(extracted.cargo.units, units as u32);
+    assert_eq!(extracted.cooldown.remaining_seconds, 70);
+
+    let error = api
+        .extract_resources(&symbol("SIMULATOR-1"), None)
+        .await
+        .unwrap_err();
+    assert_eq!(code(error), 4000);
+    sim.advance(Duration::seconds(70));
+    assert!(api
+        .get_ship_cooldown(&symbol("SIMULATOR-1"))
+        .await
+        .unwrap()
+        .is_none());
+    assert!(api
+        .extract_resources(&symbol("SIMULATOR-1"), None)
+        .await
+        .is_ok());
+
+    // the probe has no mining laser
+    api.orbit_ship(&symbol("SIMULATOR-2")).await.unwrap();
+    api.navigate_ship(&symbol("SIMULATOR-2"), &symbol("X1-SIM-ASTEROIDS"))
+        .await
+        .unwrap();
+    sim.advance(Duration::minutes(1));
+    let error = api
+        .extract_resources(&symbol("SIMULATOR-2"), None)
+        .await
+        .unwrap_err();
+    assert_eq!(code(error), 4227);
+}
+
+#[tokio::test]
+async fn test_shipyard_lists_ships_while_one_is_there() {
+    let sim = Arc::new(Simulator::new(1));
+    let (_server, api) = start(&sim).await;
+    let station = symbol("X1-SIM-STATION");
+
+    let shipyard = api.get_shipyard(&station).await.unwrap();
+    assert_eq!(shipyard.ship_types.len(), 2);
+    assert!(shipyard.ships.is_empty());
+    let waypoint = api.get_waypoint(&station).await.unwrap();
+    assert!(waypoint
+        .traits
+        .iter()
+        .any(|t| t.symbol == WaypointTraitSymbol::Shipyard));
+
+    api.orbit_ship(&symbol("SIMULATOR-1")).await.unwrap();
+    api.navigate_ship(&symbol("SIMULATOR-1"), &station)
+        .await
+        .unwrap();
+    sim.advance(Duration::minutes(10));
+    let shipyard = api.get_shipyard(&station).await.unwrap();
+    let probe = shipyard
+        .ships
+        .iter()
+        .find(|ship| ship.kind == Some(ShipType::ShipProbe))
+        .unwrap();
+    assert_eq!(probe.purchase_price, 20_000);
+}
+
+#[tokio::test]
+async fn test_surveys_narrow_extraction() {
+    let sim = Arc::new(Simulator::new(5));
+    let surveyor = symbol("SIMULATOR-3");
+    let mut ship = sim.make_ship(&surveyor, ShipRole::Surveyor, &symbol("X1-SIM-ASTEROIDS"));
+    ship.mounts = serde_json::from_value(json!([
+        { "symbol": "MOUNT_SURVEYOR_I", "name": "Surveyor I", "requirements": {} },
+        { "symbol": "MOUNT_MINING_LASER_I", "name": "Mining Laser I", "strength": 10, "requirements": {} },
+    ]))
+    .unwrap();
+    sim.add_ship(ship);
+    let (_server, api) = start(&sim).await;
+
+    let error = api.create_survey(&surveyor).await.unwrap_err();
+    assert_eq!(code(error), 4236);
+    api.orbit_ship(&surveyor).await.unwrap();
+    let surveyed = api.create_survey(&surveyor).await.unwrap();
+    assert_eq!(surveyed.surveys.len(), 1);
+    assert_eq!(surveyed.cooldown.remaining_seconds, 70);
+    let survey = &surveyed.surveys[0];
+    assert_eq!(survey.symbol, "X1-SIM-ASTEROIDS");
+
+    sim.advance(Duration::seconds(70));
+    let extracted = api
+        .extract_resources(&surveyor, Some(survey))
+        .await
+        .unwrap();
+    let found = extracted.extraction.yield_amount.symbol;
+    assert!(survey
+        .deposits
+        .iter()
+        .any(|deposit| deposit.symbol == found.to_string()));
+
+    // and once it's expired the server won't take it
+    sim.advance(Duration::minutes(15));
+    let error = api
+        .extract_resources(&surveyor, Some(survey))
+        .await
+        .unwrap_err();
+    assert_eq!(code(error), 4221);
+}
+
+#[tokio::test]
+async fn test_trading_moves_prices() {
+    let sim = Arc::new(Simulator::new(1));
+    let (_server, api) = start(&sim).await;
+
+    // prices only show up while a ship is there
+    let market = api.get_market(&symbol("X1-SIM-MOON")).await.unwrap();
+    assert!(market.trade_goods.is_empty());
+    let market = api.get_market(&symbol("X1-SIM-HQ")).await.unwrap();
+    let price = |symbol| {
+        let good = market
+            .trade_goods
+            .iter()
+            .find(|good| good.symbol == symbol)
+            .unwrap();
+        (good.purchase_price, good.sell_price)
+    };
+    let (electronics, _) = price(TradeSymbol::Electronics);
+
+    let bought = api
+        .purchase_cargo(&symbol("SIMULATOR-1"), TradeSymbol::Electronics, 10)
+        .await
+        .unwrap();
+    assert_eq!(bought.transaction.price_per_unit, electronics);
+    assert_eq!(bought.agent.credits, 100_000 - 10 * electronics as i64);
+    let error = api
+        .purchase_cargo(&symbol("SIMULATOR-1"), TradeSymbol::Electronics, 11)
+        .await
+        .unwrap_err();
+    assert_eq!(code(error), 4604);
+
+    let after = sim.market(&symbol("X1-SIM-HQ")).unwrap();
+    let good = after
+        .trade_goods
+        .iter()
+        .find(|good| good.symbol == TradeSymbol::Electronics)
+        .unwrap();
+    assert!(good.purchase_price > electronics);
+
+    // and drift back once we leave them alone
+    sim.advance(Duration::hours(12));
+    let later = sim.market(&symbol("X1-SIM-HQ")).unwrap();
+    let good = later
+        .trade_goods
+        .iter()
+        .find(|good| good.symbol == TradeSymbol::Electronics)
+        .unwrap();
+    assert_eq!(good.purchase_price, electronics);
+
+    let sold = api
+        .sell_cargo(&symbol("SIMULATOR-1"), TradeSymbol::Electronics, 5)
+        .await
+        .unwrap();
+    assert_eq!(sold.cargo.units, 5);
+    assert_eq!(sim.agent().credits, sold.agent.credits);
+}
+
+#[tokio::test]
+async fn test_contract_delivery() {
+    let sim = Arc::new(Simulator::new(3));
+    let (_server, api) = start(&sim).await;
+    let id = "sim-contract-1";
+
+    let error = api.fulfill_contract(id).await.unwrap_err();
+    assert_eq!(code(error), 4505);
+    let accepted = api.accept_contract(id).await.unwrap();
+    assert_eq!(accepted.agent.credits, 105_000);
+
+    // 40 cargo, so it takes two trips of 30
+    for trip in 0..2 {
+        api.orbit_ship(&symbol("SIMULATOR-1")).await.unwrap();
+        api.navigate_ship(&symbol("SIMULATOR-1"), &symbol("X1-SIM-ASTEROIDS"))
+            .await
+            .unwrap();
+        sim.advance(Duration::minutes(1));
+        let mut mined = 0;
+        while mined < 30 {
+            let extracted = api
+                .extract_resources(&symbol("SIMULATOR-1"), None)
+                .await
+                .unwrap();
+            for item in extracted.cargo.inventory {
+                if item.symbol == TradeSymbol::IronOre {
+                    mined = item.units;
+                } else {
+                    api.jettison_cargo(&symbol("SIMULATOR-1"), item.symbol, item.units)
+                        .await
+                        .unwrap();
+                }
+            }
+            sim.advance(Duration::seconds(70));
+        }
+
+        api.navigate_ship(&symbol("SIMULATOR-1"), &symbol("X1-SIM-HQ"))
+            .await
+            .unwrap();
+        sim.advance(Duration::minutes(1));
+        if trip == 0 {
+            let error = api
+                .deliver_contract(id, &symbol("SIMULATOR-1"), TradeSymbol::IronOre, 30)
+                .await
+                .unwrap_err();
+            assert_eq!(code(error), 4244);
+        }
+        api.dock_ship(&symbol("SIMULATOR-1")).await.unwrap();
+        if trip == 0 {
+            let delivered = api
+                .deliver_contract(id, &symbol("SIMULATOR-1"), TradeSymbol::IronOre, 30)
+                .await
+                .unwrap();
+            assert_eq!(delivered.contract.terms.deliver[0].units_fulfilled, 30);
+            let error = api.fulfill_contract(id).await.unwrap_err();
+            assert_eq!(code(error), 4502);
+        }
+    }
+
+    let delivered = api
+        .deliver_contract(id, &symbol("SIMULATOR-1"), TradeSymbol::IronOre, 30)
+        .await
+        .unwrap();
+    assert_eq!(delivered.contract.terms.deliver[0].units_fulfilled, 60);
+    let error = api
+        .negotiate_contract(&symbol("SIMULATOR-1"))
+        .await
+        .unwrap_err();
+    assert_eq!(code(error), 4511);
+    let fulfilled = api.fulfill_contract(id).await.unwrap();
+    assert!(fulfilled.contract.fulfilled);
+    assert_eq!(fulfilled.agent.credits, 125_000);
+
+    // with that one done, the faction has another
+    let negotiated = api
+        .negotiate_contract(&symbol("SIMULATOR-1"))
+        .await
+        .unwrap();
+    assert!(!negotiated.contract.accepted);
+    assert_eq!(api.list_contracts().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_charting_and_jumping() {
+    let sim = Arc::new(Simulator::new(1));
+    sim.add_waypoint(&symbol("X1-SIM-GATE"), WaypointType::JumpGate, 10, 10);
+    sim.add_waypoint(&symbol("X1-FAR-GATE"), WaypointType::JumpGate, 0, 0);
+    sim.add_jump_gate(&symbol("X1-SIM-GATE"), vec![symbol("X1-FAR")]);
+    sim.add_jump_gate(&symbol("X1-FAR-GATE"), vec![symbol("X1-SIM")]);
+    sim.set_uncharted(&symbol("X1-FAR-GATE"));
+    let (_server, api) = start(&sim).await;
+    let probe = symbol("SIMULATOR-2");
+
+    let gate = api.get_jump_gate(&symbol("X1-SIM-GATE")).await.unwrap();
+    assert_eq!(gate.connected_systems[0].symbol, "X1-FAR");
+    api.orbit_ship(&probe).await.unwrap();
+    let error = api.jump_ship(&probe, &symbol("X1-FAR")).await.unwrap_err();
+    assert_eq!(code(error), 4211);
+    api.navigate_ship(&probe, &symbol("X1-SIM-GATE"))
+        .await
+        .unwrap();
+    sim.advance(Duration::minutes(1));
+    let error = api.create_chart(&probe).await.unwrap_err();
+    assert_eq!(code(error), 4230);
+
+    let jumped = api.jump_ship(&probe, &symbol("X1-FAR")).await.unwrap();
+    assert_eq!(jumped.nav.waypoint_symbol, "X1-FAR-GATE");
+    assert_eq!(jumped.cooldown.remaining_seconds, 60);
+    let error = api.jump_ship(&probe, &symbol("X1-SIM")).await.unwrap_err();
+    assert_eq!(code(error), 4000);
+
+    let charted = api.create_chart(&probe).await.unwrap();
+    assert_eq!(charted.chart.submitted_by.as_deref(), Some("SIMULATOR"));
+    assert!(charted.waypoint.traits.is_empty());
+    let error = api.create_chart(&probe).await.unwrap_err();
+    assert_eq!(code(error), 4230);
+}
@@ -0,0 +1,107 @@
+use super::fixtures::example_ship;
+use super::symbols::symbol;
+use crate::api::schema::{ShipNavStatus, TradeSymbol};
+use crate::state::AgentState;
+use serde_json::{json, Value};
+
+fn ship() -> Value {
+    let mut ship = example_ship();
+    ship["symbol"] = json!("ME-1");
+    ship
+}
+
+fn data(value: Value) -> String { json!({ "data": value }).to_string() }
+
+#[test]
+fn test_whole_and_partial_updates() {
+    let state = AgentState::new();
+    let mut agent =
+        json!({ "accountId": "1", "symbol": "ME", "headquarters": "X1-A-B", "credits": 10 });
+    state.observe("/my/agent", None, &data(agent.clone()));
+    assert_eq!(state.credits(), Some(10));
+
+    // nav for a ship we've never seen whole has nowhere to go
+    let mut nav = ship()["nav"].clone();
+    state.observe("/my/ships/ME-1/orbit", None, &data(json!({ "nav": nav })));
+    assert!(state.snapshot().ships.is_empty());
+
+    state.observe("/my/ships?page=1&limit=20", None, &data(json!([ship()])));
+    assert!(state.ship(&symbol("ME-1")).is_some());
+
+    nav["status"] = json!("IN_ORBIT");
+    agent["credits"] = json!(5);
+    let sold = json!({ "agent": agent, "nav": nav, "cargo": { "capacity": 10, "units": 0, "inventory": [] } });
+    state.observe("/my/ships/ME-1/sell", None, &data(sold));
+    let ship = state.ship(&symbol("ME-1")).unwrap();
+    assert!(matches!(ship.nav.status, ShipNavStatus::InOrbit));
+    assert_eq!(ship.cargo.capacity, 10);
+    assert_eq!(state.credits(), Some(5));
+
+    // deliveries name the ship in the request instead of the path
+    let cargo = json!({ "capacity": 10, "units": 1, "inventory": [
+        { "symbol": "IRON_ORE", "name": "", "description": "", "units": 1 },
+    ] });
+    let body = json!({ "shipSymbol": "ME-1", "tradeSymbol": "IRON_ORE", "units": 1 });
+    let delivered = json!({ "contract": {}, "cargo": cargo });
+    state.observe("/my/contracts/c1/deliver", Some(&body), &data(delivered));
+    let ship = state.ship(&symbol("ME-1")).unwrap();
+    assert_eq!(ship.cargo.inventory[0].symbol, TradeSymbol::IronOre);
+
+    // errors and things that aren't ours leave it alone
+    let before = state.snapshot();
+    let error = json!({ "error": { "message": "no", "code": 4000 } }).to_string();
+    state.observe("/my/ships/ME-1/nav", None, &error);
+    state.observe("/my/ships/ME-1/nav", None, &data(json!({ "not": "a nav" })));
+    assert_eq!(state.snapshot(), before);
+}
+
+#[tokio::test]
+async fn test_subscribers_only_see_changes() {
+    let state = AgentState::new();
+    let mut changes = state.subscribe();
+    let agent =
+        json!({ "accountId": "1", "symbol": "ME", "headquarters": "X1-A-B", "credits": 10 });
+    state.observe("/my/agent", None, &data(agent.clone()));
+    assert!(changes.has_changed().unwrap());
+    assert_eq!(changes.borrow_and_update().credits(), Some(10));
+
+    state.observe("/my/agent", None, &data(agent));
+    assert!(!changes.has_changed().unwrap());
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn test_client_keeps_state_up_to_date() {
+    use crate::api::SpaceTraderApi;
+    use crate::mock::MockServer;
+    use crate::sim::Simulator;
+    use std::sync::Arc;
+
+    let sim = Arc::new(Simulator::new(1));
+    let server = MockServer::start_with(sim.clone()).await.unwrap();
+    let api = SpaceTraderApi::new("token".to_owned()).with_base_url(&server.url());
+    let state = api.agent_state().clone();
+    let mut changes = state.subscribe();
+
+    api.my_agent().await.unwrap();
+    api.list_ships().await.unwrap();
+    assert_eq!(state.credits(), Some(100_000));
+    assert_eq!(state.snapshot().ships.len(), 2);
+    changes.borrow_and_update();
+
+    let miner = symbol("SIMULATOR-1");
+    api.purchase_cargo(&miner, TradeSymbol::Electronics, 10)
+        .await
+        .unwrap();
+    assert!(changes.has_changed().unwrap());
+    assert_eq!(Some(sim.agent().credits), state.credits());
+    assert_eq!(state.ship(&miner).unwrap().cargo.units, 10);
+
+    api.orbit_ship(&miner).await.unwrap();
+    api.navigate_ship(&miner, &symbol("X1-SIM-ASTEROIDS"))
+        .await
+        .unwrap();
+    let ship = state.ship(&miner).unwrap();
+    assert_eq!(ship, sim.ship(&miner).unwrap());
+    assert!(matches!(ship.nav.status, ShipNavStatus::InTransit));
+}
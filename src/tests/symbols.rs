@@ -0,0 +1,70 @@
+use crate::api::schema::{FactionSymbol, SectorSymbol, ShipSymbol, SystemSymbol, WaypointSymbol};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+/// whichever symbol the call site wants, from a string that had better be one
+pub fn symbol<T>(symbol: &str) -> T
+where
+    T: FromStr,
+    T::Err: Debug, {
+    symbol.parse().unwrap()
+}
+
+#[test]
+fn test_parsing_symbols() {
+    assert!("X1".parse::<SectorSymbol>().is_ok());
+    assert!("X1-DF55".parse::<SystemSymbol>().is_ok());
+    assert!("X1-DF55-20250Z".parse::<WaypointSymbol>().is_ok());
+    assert!("MY_AGENT-1".parse::<ShipSymbol>().is_ok());
+    assert!("SOME-AGENT-1A".parse::<ShipSymbol>().is_ok());
+    assert!("COSMIC".parse::<FactionSymbol>().is_ok());
+
+    assert!("X1-DF55".parse::<SectorSymbol>().is_err());
+    assert!("X1-DF55-20250Z".parse::<SystemSymbol>().is_err());
+    assert!("X1-DF55".parse::<WaypointSymbol>().is_err());
+    assert!("X1--20250Z".parse::<WaypointSymbol>().is_err());
+    assert!("X1-DF55-20250Z-".parse::<WaypointSymbol>().is_err());
+    assert!("X1 DF55 20250Z".parse::<WaypointSymbol>().is_err());
+    assert!("AGENT".parse::<ShipSymbol>().is_err());
+    assert!("-1".parse::<ShipSymbol>().is_err());
+    assert!("".parse::<FactionSymbol>().is_err());
+
+    let error = "X1-DF55".parse::<WaypointSymbol>().unwrap_err();
+    assert_eq!(error.to_string(), "\"X1-DF55\" isn't a WaypointSymbol");
+}
+
+#[test]
+fn test_waypoints_know_their_system_and_sector() {
+    let waypoint: WaypointSymbol = symbol("X1-DF55-20250Z");
+    assert_eq!(waypoint.system(), symbol::<SystemSymbol>("X1-DF55"));
+    assert_eq!(waypoint.sector(), symbol::<SectorSymbol>("X1"));
+    assert_eq!(waypoint.system().sector(), waypoint.sector());
+}
+
+#[test]
+fn test_symbols_serialize_as_strings() {
+    let waypoint: WaypointSymbol = symbol("X1-DF55-20250Z");
+    assert_eq!(
+        serde_json::to_value(&waypoint).unwrap(),
+        serde_json::json!("X1-DF55-20250Z")
+    );
+    assert_eq!(
+        serde_json::from_str::<WaypointSymbol>("\"X1-DF55-20250Z\"").unwrap(),
+        waypoint
+    );
+    assert_eq!(waypoint.to_string(), "X1-DF55-20250Z");
+    assert_eq!(waypoint, "X1-DF55-20250Z");
+
+    // whatever the server calls something is its name, even if parse() wouldn't have it
+    let odd: WaypointSymbol = serde_json::from_str("\"string\"").unwrap();
+    assert_eq!(odd.system(), "string");
+}
+
+#[test]
+fn test_maps_keyed_by_symbol_take_str() {
+    let mut positions: HashMap<WaypointSymbol, (i32, i32)> = HashMap::new();
+    positions.insert(symbol("X1-DF55-20250Z"), (3, 4));
+    assert_eq!(positions.get("X1-DF55-20250Z"), Some(&(3, 4)));
+    assert_eq!(positions.get("X1-DF55-A1"), None);
+}
@@ -0,0 +1,85 @@
+use super::fixtures;
+use super::symbols::symbol;
+use crate::api::schema;
+use crate::trading::{RouteFinder, RouteRanking, TradeShip};
+
+fn market(symbol: &str, purchase_price: u32, sell_price: u32) -> schema::Market {
+    //! iron, only 10 at a time
+    let mut market = fixtures::market(symbol, "IRON", purchase_price, sell_price);
+    market.trade_goods[0].trade_volume = 10;
+    market
+}
+
+fn finder() -> RouteFinder {
+    let mut finder = RouteFinder::default();
+    finder.add_waypoint(&symbol("X1-DF55-A"), 0, 0);
+    finder.add_waypoint(&symbol("X1-DF55-NEAR"), 10, 0);
+    finder.add_waypoint(&symbol("X1-DF55-FAR"), 400, 0);
+    // iron sells for a bit more close by, and a lot more far away
+    finder.add_market(&market("X1-DF55-A", 100, 90));
+    finder.add_market(&market("X1-DF55-NEAR", 130, 120));
+    finder.add_market(&market("X1-DF55-FAR", 210, 200));
+    finder
+}
+
+fn ship() -> TradeShip {
+    TradeShip {
+        cargo_capacity: 60,
+        engine_speed: 30,
+        flight_mode: schema::ShipNavFlightMode::Cruise,
+        max_transactions: None,
+        wages_per_hour: 0,
+    }
+}
+
+#[test]
+fn test_routes_ranked_by_unit_and_time() {
+    let finder = finder();
+
+    let by_unit = finder.routes(&ship(), RouteRanking::ProfitPerUnit);
+    assert_eq!(by_unit.len(), 3);
+    assert_eq!(
+        (by_unit[0].buy_at.as_str(), by_unit[0].sell_at.as_str()),
+        ("X1-DF55-A", "X1-DF55-FAR")
+    );
+    assert_eq!(by_unit[0].profit_per_unit, 100);
+    assert_eq!(by_unit[0].profit_per_trip, 6000);
+
+    let by_time = finder.routes(&ship(), RouteRanking::ProfitPerSecond);
+    assert_eq!(
+        (by_time[0].buy_at.as_str(), by_time[0].sell_at.as_str()),
+        ("X1-DF55-A", "X1-DF55-NEAR")
+    );
+}
+
+#[test]
+fn test_routes_respect_trade_volume() {
+    let mut ship = ship();
+    let routes = finder().routes(&ship, RouteRanking::ProfitPerTrip);
+    assert_eq!(routes[0].units_per_trip, 60);
+    assert_eq!(routes[0].transactions_per_trip, 6);
+
+    ship.max_transactions = Some(2);
+    let routes = finder().routes(&ship, RouteRanking::ProfitPerTrip);
+    assert_eq!(routes[0].units_per_trip, 20);
+    assert_eq!(routes[0].transactions_per_trip, 2);
+}
+
+#[test]
+fn test_crew_wages_come_out_of_each_trip() {
+    let mut ship = ship();
+    let free = finder().routes(&ship, RouteRanking::ProfitPerUnit);
+    ship.wages_per_hour = 600;
+    let paid = finder().routes(&ship, RouteRanking::ProfitPerUnit);
+    assert_eq!(paid.len(), free.len());
+    for (free, paid) in free.iter().zip(&paid) {
+        assert!(paid.crew_cost > 0);
+        assert_eq!(paid.profit_per_trip, free.profit_per_trip - paid.crew_cost);
+        assert!(paid.profit_per_second < free.profit_per_second);
+    }
+
+    // enough to eat up what the short haul makes
+    ship.wages_per_hour = 200_000;
+    let routes = finder().routes(&ship, RouteRanking::ProfitPerSecond);
+    assert!(routes.iter().all(|route| route.sell_at != "X1-DF55-NEAR"));
+}
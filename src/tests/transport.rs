@@ -0,0 +1,171 @@
+use crate::api::transport::{
+    InMemoryTransport, Logged, RateLimited, Request, Response, Retrying, Transport,
+};
+use crate::api::SpaceTraderApi;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+fn ok_response() -> Response {
+    let body = json!({ "data": { "accountId": "1", "symbol": "ME", "headquarters": "X1-A-B", "credits": 10 } });
+    Response {
+        status: 200,
+        body: body.to_string(),
+        ..Default::default()
+    }
+}
+
+fn counting(responses: Vec<Response>) -> (InMemoryTransport, Arc<AtomicUsize>) {
+    //! answers with each of responses in turn, then ok_response forever
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = count.clone();
+    let transport = InMemoryTransport::new(move |_| {
+        let n = counter.fetch_add(1, Ordering::SeqCst);
+        responses.get(n).cloned().unwrap_or_else(ok_response)
+    });
+    (transport, count)
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retries_rate_limits_and_server_errors() {
+    let rate_limited = Response {
+        status: 429,
+        headers: [("retry-after".to_owned(), "3".to_owned())].into(),
+        body: json!({ "error": { "message": "slow down", "code": 429 } }).to_string(),
+    };
+    let server_error = Response {
+        status: 502,
+        ..Default::default()
+    };
+    let (transport, count) = counting(vec![rate_limited, server_error]);
+    let api = SpaceTraderApi::new("token".to_owned())
+        .with_transport(transport)
+        .layer(|t| Retrying::new(t, 3).with_backoff(Duration::from_secs(1)));
+
+    let start = Instant::now();
+    assert_eq!(api.my_agent().await.unwrap().symbol, "ME");
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+    // 3s from retry-after, then the doubled backoff
+    assert_eq!(start.elapsed().as_secs(), 5);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retries_give_up() {
+    let server_error = Response {
+        status: 500,
+        body: json!({ "error": { "message": "oops", "code": 500 } }).to_string(),
+        ..Default::default()
+    };
+    let (transport, count) = counting(vec![server_error; 5]);
+    let api = SpaceTraderApi::new("token".to_owned())
+        .with_transport(transport)
+        .layer(|t| Retrying::new(t, 2));
+    assert!(api.my_agent().await.is_err());
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+}
+
+fn request(method: reqwest::Method) -> Request {
+    Request {
+        method,
+        base_url: "https://example.com".to_owned(),
+        path: "/my/ships/SHIP-1/purchase".to_owned(),
+        token: "token".to_owned(),
+        body: None,
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_writes_only_retried_when_asked() {
+    let server_error = Response {
+        status: 502,
+        ..Default::default()
+    };
+    // the purchase may well have gone through, so it's not sent again
+    let (transport, count) = counting(vec![server_error.clone()]);
+    let retrying = Retrying::new(transport, 3);
+    let response = retrying.send(request(reqwest::Method::POST)).await.unwrap();
+    assert_eq!(response.status, 502);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    let (transport, count) = counting(vec![server_error]);
+    let retrying = Retrying::new(transport, 3).with_retry_writes(true);
+    let response = retrying.send(request(reqwest::Method::POST)).await.unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+
+    // a rate limited one never reached the server, so that's safe
+    let rate_limited = Response {
+        status: 429,
+        ..Default::default()
+    };
+    let (transport, count) = counting(vec![rate_limited]);
+    let retrying = Retrying::new(transport, 3);
+    let response = retrying.send(request(reqwest::Method::POST)).await.unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_after_that_makes_no_sense() {
+    let retry_after = |value: &str| Response {
+        status: 429,
+        headers: [("retry-after".to_owned(), value.to_owned())].into(),
+        ..Default::default()
+    };
+    let (transport, count) = counting(vec![
+        retry_after("inf"),
+        retry_after("NaN"),
+        retry_after("1e300"),
+    ]);
+    let retrying = Retrying::new(transport, 4).with_backoff(Duration::from_secs(1));
+    let start = Instant::now();
+    let response = retrying.send(request(reqwest::Method::GET)).await.unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(count.load(Ordering::SeqCst), 4);
+    // an hour at most for inf and 1e300, and the 2s backoff for NaN
+    assert_eq!(start.elapsed().as_secs(), 2 * 60 * 60 + 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limit_and_logging() {
+    let (transport, count) = counting(vec![]);
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let log = lines.clone();
+    let api = SpaceTraderApi::new("token".to_owned())
+        .with_transport(transport)
+        .layer(|t| RateLimited::new(t, 2.0, 2))
+        .layer(|t| Logged::with_logger(t, move |line| log.lock().unwrap().push(line.to_owned())));
+
+    let start = Instant::now();
+    for _ in 0..4 {
+        api.my_agent().await.unwrap();
+    }
+    assert_eq!(count.load(Ordering::SeqCst), 4);
+    // two go out straight away, then one every half second
+    assert_eq!(start.elapsed().as_millis(), 1000);
+
+    let lines = lines.lock().unwrap();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("GET /my/agent -> 200"));
+}
+
+#[cfg(feature = "mock")]
+#[tokio::test]
+async fn test_in_memory_backend() {
+    use super::symbols::symbol;
+    use crate::sim::Simulator;
+
+    let sim = Arc::new(Simulator::new(1));
+    let api = SpaceTraderApi::new("token".to_owned()).with_transport(crate::mock::in_memory(sim));
+    assert_eq!(api.status().await.unwrap().reset_date, "2023-05-20");
+    assert_eq!(api.list_ships().await.unwrap().len(), 2);
+    api.orbit_ship(&symbol("SIMULATOR-1")).await.unwrap();
+    // nothing on cooldown comes back as a 204
+    assert!(api
+        .get_ship_cooldown(&symbol("SIMULATOR-1"))
+        .await
+        .unwrap()
+        .is_none());
+}
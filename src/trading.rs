@@ -0,0 +1,169 @@
+/*
+The trading module looks for goods that can be bought at one market
+in a system and sold at another for more.
+
+Routes are built from known prices, either straight from fetched
+Markets or from the price history. Markets only show prices while
+one of our ships is there, so a market we've never visited can't
+show up in any route. Every trip is charged the crew's wages for the
+time it takes, so a long haul has to earn more to be worth it.
+*/
+
+use crate::api::schema;
+use crate::crew;
+use crate::history::PriceRecord;
+use crate::navigation;
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+pub struct TradeShip {
+    pub cargo_capacity: u32,
+    pub engine_speed: u32,
+    pub flight_mode: schema::ShipNavFlightMode,
+    /// stop after this many transactions per market per trip; prices move
+    /// against us with every trade_volume units, so more isn't always better
+    pub max_transactions: Option<u32>,
+    /// what the crew are paid an hour, charged against every trip
+    pub wages_per_hour: i64,
+}
+
+impl TradeShip {
+    pub fn from_ship(ship: &schema::Ship) -> Self {
+        Self {
+            cargo_capacity: ship.cargo.capacity,
+            engine_speed: ship.engine.speed,
+            flight_mode: ship.nav.flight_mode,
+            max_transactions: None,
+            wages_per_hour: crew::wages_per_hour(&ship.crew),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RouteRanking {
+    ProfitPerUnit,
+    ProfitPerTrip,
+    ProfitPerSecond,
+}
+
+#[derive(Debug)]
+pub struct TradeRoute {
+    pub trade_symbol: schema::TradeSymbol,
+    pub buy_at: schema::WaypointSymbol,
+    pub sell_at: schema::WaypointSymbol,
+    pub purchase_price: u32,
+    pub sell_price: u32,
+    pub profit_per_unit: i64,
+    /// how many units one trip moves, after trade_volume limits
+    pub units_per_trip: u32,
+    /// how many buy (and sell) transactions a trip takes
+    pub transactions_per_trip: u32,
+    /// the crew's wages for the round trip
+    pub crew_cost: i64,
+    /// after the crew's wages
+    pub profit_per_trip: i64,
+    /// buy market -> sell market -> back again
+    pub round_trip: Duration,
+    pub profit_per_second: f64,
+}
+
+#[derive(Default)]
+pub struct RouteFinder {
+    positions: HashMap<schema::WaypointSymbol, (i32, i32)>,
+    prices: Vec<PriceRecord>,
+}
+
+impl RouteFinder {
+    pub fn new(system: &schema::System) -> Self {
+        let mut finder = Self::default();
+        for waypoint in &system.waypoints {
+            finder
+                .positions
+                .insert(waypoint.symbol.clone(), (waypoint.x, waypoint.y));
+        }
+        finder
+    }
+
+    pub fn add_waypoint(&mut self, symbol: &schema::WaypointSymbol, x: i32, y: i32) {
+        self.positions.insert(symbol.clone(), (x, y));
+    }
+
+    pub fn add_market(&mut self, market: &schema::Market) {
+        self.prices
+            .extend(PriceRecord::from_market(market, Utc::now()));
+    }
+
+    pub fn add_prices(&mut self, prices: impl IntoIterator<Item = PriceRecord>) {
+        //! meant for PriceHistory::latest_in_system
+        self.prices.extend(prices);
+    }
+
+    pub fn routes(&self, ship: &TradeShip, ranking: RouteRanking) -> Vec<TradeRoute> {
+        //! every profitable route, best first. prices at waypoints without
+        //! a known position are skipped
+        let mut routes = Vec::new();
+        for buy in &self.prices {
+            for sell in &self.prices {
+                if buy.trade_symbol != sell.trade_symbol
+                    || buy.waypoint_symbol == sell.waypoint_symbol
+                {
+                    continue;
+                }
+                if let Some(route) = self.route(ship, buy, sell) {
+                    routes.push(route);
+                }
+            }
+        }
+        routes.sort_by(|a, b| match ranking {
+            RouteRanking::ProfitPerUnit => b.profit_per_unit.cmp(&a.profit_per_unit),
+            RouteRanking::ProfitPerTrip => b.profit_per_trip.cmp(&a.profit_per_trip),
+            RouteRanking::ProfitPerSecond => b.profit_per_second.total_cmp(&a.profit_per_second),
+        });
+        routes
+    }
+
+    fn route(&self, ship: &TradeShip, buy: &PriceRecord, sell: &PriceRecord) -> Option<TradeRoute> {
+        let profit_per_unit = sell.sell_price as i64 - buy.purchase_price as i64;
+        if profit_per_unit <= 0 {
+            return None;
+        }
+        let from = *self.positions.get(&buy.waypoint_symbol)?;
+        let to = *self.positions.get(&sell.waypoint_symbol)?;
+
+        let volume = buy.trade_volume.min(sell.trade_volume).max(1);
+        let units_per_trip = match ship.max_transactions {
+            Some(max) => ship.cargo_capacity.min(volume * max),
+            None => ship.cargo_capacity,
+        };
+        if units_per_trip == 0 {
+            return None;
+        }
+        let transactions_per_trip = units_per_trip.div_ceil(volume);
+
+        let distance = navigation::distance(from, to);
+        let one_way = navigation::travel_time(distance, ship.engine_speed, ship.flight_mode);
+        let round_trip = one_way * 2;
+        let crew_cost =
+            crew::wages_over(ship.wages_per_hour, round_trip.to_std().unwrap_or_default());
+        let profit_per_trip = profit_per_unit * units_per_trip as i64 - crew_cost;
+        if profit_per_trip <= 0 {
+            return None;
+        }
+        let profit_per_second = profit_per_trip as f64 / round_trip.num_seconds().max(1) as f64;
+
+        Some(TradeRoute {
+            trade_symbol: buy.trade_symbol.clone(),
+            buy_at: buy.waypoint_symbol.clone(),
+            sell_at: sell.waypoint_symbol.clone(),
+            purchase_price: buy.purchase_price,
+            sell_price: sell.sell_price,
+            profit_per_unit,
+            units_per_trip,
+            transactions_per_trip,
+            crew_cost,
+            profit_per_trip,
+            round_trip,
+            profit_per_second,
+        })
+    }
+}
@@ -0,0 +1,304 @@
+/*
+Writes src/tests/conformance.rs: one test per component schema and per
+operation response in the spec (and in schema::MISSING), each decoding
+an example payload into the matching Rust type, encoding it again, and
+comparing the two.
+
+Anything that gets lost or changed on the way is drift. Drift we
+already know about lives in spec/known-drift.txt, so only new drift
+(or drift that got fixed without updating the list) fails a test.
+
+Each of those also gets a property test that varies the example's
+dates, numbers, text and list lengths, and checks every variation
+survives decode -> encode -> decode unchanged, unless the example
+is known not to decode at all.
+
+The tests are only as good as the spec they come from, so the file
+says which one that was: the api-docs commit in spec/UPSTREAM, or
+that it's the hand-made copy, which can't show real server drift.
+*/
+
+use crate::schema::{MISSING, OPEN, RESPONSES};
+use crate::spec::{pascal_case, snake_case, Schema, Spec};
+use crate::vendor::{upstream, REPO};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+
+const PRELUDE: &str = r#"// generated by `cargo xtask conformance` from spec/SpaceTraders.json.
+// don't edit it by hand; change the spec or xtask/src/conformance.rs instead
+
+use super::fidelity::{config, perturbed, survives_round_trip};
+use crate::api::{responses, schema};
+use proptest::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+const KNOWN_DRIFT: &str = include_str!("../../spec/known-drift.txt");
+
+fn drift(path: &str, expected: &Value, actual: &Value, found: &mut Vec<String>) {
+    // a null and a missing field are the same thing as far as we care
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, value) in expected {
+                let path = format!("{path}.{key}");
+                match actual.get(key) {
+                    Some(actual) => drift(&path, value, actual, found),
+                    None if !value.is_null() => found.push(format!("missing {path}")),
+                    None => {}
+                }
+            }
+            for (key, value) in actual {
+                if !expected.contains_key(key) && !value.is_null() {
+                    found.push(format!("extra {path}.{key}"));
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (expected, actual) in expected.iter().zip(actual) {
+                drift(&format!("{path}[]"), expected, actual, found);
+            }
+        }
+        (Value::Number(e), Value::Number(a)) if e.as_f64() == a.as_f64() => {}
+        (expected, actual) if expected == actual => {}
+        _ => found.push(format!("changed {path}")),
+    }
+}
+
+fn round_trip<T: Serialize + DeserializeOwned>(name: &str, example: &str) {
+    let expected: Value = serde_json::from_str(example).unwrap();
+    let mut found = Vec::new();
+    match serde_json::from_value::<T>(expected.clone()) {
+        Ok(decoded) => drift("", &expected, &serde_json::to_value(&decoded).unwrap(), &mut found),
+        // without the list of every variant serde would have taken
+        Err(e) => found.push(format!("doesn't decode: {}", e.to_string().split(", expected one of").next().unwrap())),
+    }
+    found.dedup();
+    let known: Vec<&str> = KNOWN_DRIFT
+        .lines()
+        .filter_map(|line| line.strip_prefix(name)?.strip_prefix(": "))
+        .collect();
+    let report: Vec<String> = found.iter().map(|drift| format!("{name}: {drift}")).collect();
+    assert_eq!(
+        found,
+        known,
+        "{name} doesn't match the spec. fix the type, or list what's expected in spec/known-drift.txt:\n{}",
+        report.join("\n")
+    );
+}
+
+fn names<T>(values: &[&str], open: bool)
+where T: Serialize + DeserializeOwned + Display + FromStr<Err = schema::ParseEnumError> + PartialEq + Debug {
+    // Display and FromStr have to agree with serde on every name
+    for value in values {
+        let decoded: T = serde_json::from_value(Value::from(*value)).unwrap();
+        assert_eq!(decoded.to_string(), *value);
+        assert_eq!(value.parse::<T>().unwrap(), decoded);
+    }
+    // the open ones keep a name they don't know, the rest refuse it
+    let unknown = "NOT_A_VARIANT".parse::<T>();
+    match open {
+        true => {
+            let unknown = unknown.unwrap();
+            assert_eq!(unknown.to_string(), "NOT_A_VARIANT");
+            assert_eq!(serde_json::to_value(&unknown).unwrap(), Value::from("NOT_A_VARIANT"));
+        }
+        false => assert!(unknown.is_err()),
+    }
+}
+"#;
+
+fn rust_types(schema_rs: &str) -> BTreeSet<String> {
+    //! every type api::schema declares, struct, enum, alias or field_holder!
+    let mut names = BTreeSet::new();
+    for line in schema_rs.lines() {
+        let line = line.trim();
+        let rest = ["pub struct ", "pub enum ", "pub type ", "field_holder!("]
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix));
+        if let Some(rest) = rest {
+            let name: String = rest
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            names.insert(name);
+        }
+    }
+    names
+}
+
+fn property_test(
+    tests: &mut String,
+    known_drift: &str,
+    schema: &str,
+    name: &str,
+    rust_type: &str,
+    path: &str,
+) -> std::fmt::Result {
+    //! decode -> encode -> decode on variations of the example, see src/tests/fidelity.rs.
+    //! there's nothing to vary if the example doesn't decode in the first place
+    writeln!(tests)?;
+    if known_drift
+        .lines()
+        .any(|line| line.starts_with(&format!("{schema}: doesn't decode")))
+    {
+        writeln!(
+            tests,
+            "// no round trip test for {schema}, see spec/known-drift.txt"
+        )?;
+        return Ok(());
+    }
+    writeln!(tests, "proptest! {{")?;
+    writeln!(tests, "    #![proptest_config(config())]")?;
+    writeln!(tests, "    #[test]")?;
+    writeln!(
+        tests,
+        "    fn test_{name}_survives_round_trip(json in perturbed(include_str!({path:?}))) {{"
+    )?;
+    writeln!(tests, "        survives_round_trip::<{rust_type}>(&json)?;")?;
+    writeln!(tests, "    }}")?;
+    writeln!(tests, "}}")
+}
+
+pub struct Generated {
+    /// path relative to the repo root, and what should be in it
+    pub files: Vec<(String, String)>,
+    /// component schemas with no type in api::schema
+    pub missing: Vec<String>,
+}
+
+pub fn generate(root: &Path, spec: &Spec) -> Result<Generated, Box<dyn std::error::Error>> {
+    let mut declared = BTreeSet::new();
+    for path in ["src/api/schema.rs", "src/api/schema/generated.rs"] {
+        declared.extend(rust_types(&std::fs::read_to_string(root.join(path))?));
+    }
+    let known_drift = std::fs::read_to_string(root.join("spec/known-drift.txt"))?;
+    let mut files = Vec::new();
+    let mut missing = Vec::new();
+    let mut tests = String::new();
+    let mut example = |path: String, value: serde_json::Value| -> String {
+        files.push((
+            path.clone(),
+            serde_json::to_string_pretty(&value).unwrap() + "\n",
+        ));
+        format!("../../{path}")
+    };
+
+    for (name, schema) in &spec.components.schemas.0 {
+        if !declared.contains(name) {
+            missing.push(name.clone());
+            continue;
+        }
+        let path = example(format!("spec/examples/{name}.json"), spec.example(schema));
+        writeln!(tests)?;
+        writeln!(tests, "#[test]")?;
+        writeln!(tests, "fn test_{}_conforms() {{", snake_case(name))?;
+        writeln!(
+            tests,
+            "    round_trip::<schema::{name}>({name:?}, include_str!({path:?}));"
+        )?;
+        writeln!(tests, "}}")?;
+        property_test(
+            &mut tests,
+            &known_drift,
+            name,
+            &snake_case(name),
+            &format!("schema::{name}"),
+            &path,
+        )?;
+        if !schema.values.is_empty() {
+            writeln!(tests)?;
+            writeln!(tests, "#[test]")?;
+            writeln!(tests, "fn test_{}_names() {{", snake_case(name))?;
+            writeln!(
+                tests,
+                "    names::<schema::{name}>(&{:?}, {});",
+                schema.values,
+                OPEN.contains(&name.as_str())
+            )?;
+            writeln!(tests, "}}")?;
+        }
+    }
+
+    // and the ones only xtask/src/schema.rs knows about, after the rest
+    let extra: Vec<(&str, Schema)> = MISSING
+        .iter()
+        .map(|(id, data)| (*id, serde_json::from_str(data).unwrap()))
+        .collect();
+    let mut responses: Vec<(&str, &Schema)> = Vec::new();
+    for operation in spec.operations() {
+        let id = operation.operation_id.as_str();
+        if !RESPONSES.iter().any(|(op, _)| *op == id) {
+            missing.push(format!("{id} (response)"));
+            continue;
+        }
+        let schema = match operation.json_response() {
+            Some(schema) => spec.resolve(schema),
+            None => continue,
+        };
+        // everything but the status endpoint wraps its result in "data"
+        responses.push((id, schema.properties.get("data").unwrap_or(schema)));
+    }
+    responses.extend(extra.iter().map(|(id, data)| (*id, data)));
+
+    for (id, schema) in responses {
+        let rust_type = RESPONSES.iter().find(|(op, _)| *op == id).unwrap().1;
+        let name = pascal_case(id);
+        let path = example(
+            format!("spec/examples/responses/{name}.json"),
+            spec.example(schema),
+        );
+        writeln!(tests)?;
+        writeln!(tests, "#[test]")?;
+        writeln!(
+            tests,
+            "fn test_{}_response_conforms() {{",
+            snake_case(&name)
+        )?;
+        writeln!(
+            tests,
+            "    round_trip::<{rust_type}>({name:?}, include_str!({path:?}));"
+        )?;
+        writeln!(tests, "}}")?;
+        let test_name = format!("{}_response", snake_case(&name));
+        property_test(
+            &mut tests,
+            &known_drift,
+            &name,
+            &test_name,
+            rust_type,
+            &path,
+        )?;
+    }
+
+    // which spec this is goes right under the "generated by" lines
+    let (header, prelude) = PRELUDE.split_once("\n\n").unwrap();
+    let mut conformance = format!("{header}\n");
+    match upstream(root) {
+        Some(commit) => writeln!(conformance, "// the spec is {REPO} at {commit}")?,
+        None => {
+            writeln!(
+                conformance,
+                "// the spec is a hand-made copy, not the one in {REPO}, so these only check"
+            )?;
+            writeln!(
+                conformance,
+                "// our types against our own reading of the docs. spec/README.md says how to vendor it"
+            )?;
+        }
+    }
+    writeln!(conformance)?;
+    conformance.push_str(prelude);
+    if !missing.is_empty() {
+        writeln!(conformance)?;
+        writeln!(conformance, "// in the spec, but not in the crate:")?;
+        for name in &missing {
+            writeln!(conformance, "// - {name}")?;
+        }
+    }
+    conformance.push_str(&tests);
+    files.push(("src/tests/conformance.rs".to_owned(), conformance));
+    Ok(Generated { files, missing })
+}
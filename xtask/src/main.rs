@@ -0,0 +1,157 @@
+/*
+cargo xtask <task> [--check]
+
+schema       regenerate src/api/schema/generated.rs and
+             src/api/responses from spec/SpaceTraders.json
+conformance  regenerate src/tests/conformance.rs and spec/examples/
+             from spec/SpaceTraders.json and api::schema, so run it
+             after schema
+vendor <commit>
+             download the published spec at that api-docs commit
+             over spec/SpaceTraders.json, and note it in spec/UPSTREAM
+
+with --check nothing is written; it fails if anything it would
+write differs from what's checked in, which is what CI wants.
+schema and conformance warn when spec/UPSTREAM doesn't say which
+published spec they're working from.
+*/
+
+mod conformance;
+mod schema;
+mod spec;
+mod vendor;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+fn root() -> PathBuf {
+    // xtask/ lives right under the repo root
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_owned()
+}
+
+fn stale(root: &Path, files: &[(String, String)]) -> Vec<String> {
+    files
+        .iter()
+        .filter(|(path, contents)| {
+            std::fs::read_to_string(root.join(path)).ok().as_deref() != Some(contents)
+        })
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+fn write(root: &Path, files: &[(String, String)]) -> std::io::Result<()> {
+    for (path, contents) in files {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, contents)?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let check = args.iter().any(|arg| arg == "--check");
+    let root = root();
+    if args.first().map(String::as_str) == Some("vendor") {
+        let Some(commit) = args.get(1) else {
+            eprintln!("usage: cargo xtask vendor <api-docs commit>");
+            return ExitCode::FAILURE;
+        };
+        return match vendor::vendor(&root, commit) {
+            Ok(()) => {
+                eprintln!("now run `cargo xtask schema` and `cargo xtask conformance`");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    if vendor::upstream(&root).is_none() {
+        eprintln!(
+            "warning: spec/SpaceTraders.json isn't from {}, so this only checks against \
+             a hand-made copy. see spec/README.md",
+            vendor::REPO
+        );
+    }
+    let spec = match spec::Spec::load(&root.join("spec/SpaceTraders.json")) {
+        Ok(spec) => spec,
+        Err(e) => {
+            eprintln!("couldn't read spec/SpaceTraders.json: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let files = match args.first().map(String::as_str) {
+        Some("schema") => schema::generate(&spec),
+        Some("conformance") => match conformance::generate(&root, &spec) {
+            Ok(generated) => {
+                for name in &generated.missing {
+                    eprintln!("warning: {name} is in the spec but has no rust type");
+                }
+                generated.files
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => {
+            eprintln!("usage: cargo xtask schema|conformance [--check] | vendor <commit>");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if check {
+        let stale = stale(&root, &files);
+        for path in &stale {
+            eprintln!("{path} is out of date, run `cargo xtask {}`", args[0]);
+        }
+        return if stale.is_empty() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    }
+    match write(&root, &files) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_is_up_to_date() {
+        //! the same as `cargo xtask schema --check`
+        let root = root();
+        let spec = spec::Spec::load(&root.join("spec/SpaceTraders.json")).unwrap();
+        assert_eq!(
+            stale(&root, &schema::generate(&spec)),
+            Vec::<String>::new(),
+            "run `cargo xtask schema`"
+        );
+    }
+
+    #[test]
+    fn test_conformance_tests_are_up_to_date() {
+        //! the same as `cargo xtask conformance --check`, so `cargo test --workspace` catches it
+        let root = root();
+        let spec = spec::Spec::load(&root.join("spec/SpaceTraders.json")).unwrap();
+        let generated = conformance::generate(&root, &spec).unwrap();
+        assert_eq!(
+            stale(&root, &generated.files),
+            Vec::<String>::new(),
+            "run `cargo xtask conformance`"
+        );
+    }
+}
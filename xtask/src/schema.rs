@@ -0,0 +1,662 @@
+/*
+Writes api::schema's types and api::responses from spec/SpaceTraders.json.
+
+Component schemas become structs, enums and aliases in
+src/api/schema/generated.rs, in the order the spec lists them, with any
+type the spec only describes inline written right after the one that
+uses it. Each operation's "data" becomes the response type named in
+RESPONSES, in the module that name says. The methods that call them,
+in src/api/endpoints, are written by hand: paging, caching and which
+requests are safe to retry aren't things the spec says, so RESPONSES
+is the only link between the two.
+
+The spec is taken as it comes. Where the server is stricter or says
+more than the spec does, that goes in TYPES, EMPTY and MISSING rather
+than into the spec, so the conformance tests still see the drift.
+Until spec/UPSTREAM names a published spec (see vendor.rs) those
+tables correct a hand-made copy, so after vendoring drop whichever
+ones the real spec doesn't need.
+
+The serde conventions are the ones the hand-written types always had:
+structs rename_all = "camelCase", enums "SCREAMING_SNAKE_CASE", a
+"type" property is called kind, dates go through date_time, and
+an optional list is an empty Vec rather than an Option. Whatever the
+spec can't say (SupplyLevel's ordering, Symbolic) lives in
+src/api/schema.rs, which is never overwritten. That includes the symbol
+newtypes in schema/symbols.rs, which strings naming a ship, waypoint
+and so on become according to SYMBOLS.
+
+Structs derive Clone and PartialEq; enums are Eq, Hash and Ord (in
+the spec's order) too, and get Display and FromStr using their wire
+names. Most are fieldless and Copy. The ones in OPEN keep a name they
+don't know as Unknown(String), which sorts after the rest, so they
+aren't.
+*/
+
+use crate::spec::{pascal_case, snake_case, Schema, Spec};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+/// which type in api::responses each operation's "data" decodes into
+pub const RESPONSES: &[(&str, &str)] = &[
+    ("get-status", "responses::GetStatus"),
+    ("register", "responses::Register"),
+    ("get-my-agent", "responses::agents::MyAgentDetails"),
+    ("get-systems", "responses::systems::ListSystems"),
+    ("get-system", "responses::systems::GetSystem"),
+    ("get-system-waypoints", "responses::systems::ListWaypoints"),
+    ("get-waypoint", "responses::systems::GetWaypoint"),
+    ("get-market", "responses::systems::GetMarket"),
+    ("get-shipyard", "responses::systems::GetShipyard"),
+    ("get-jump-gate", "responses::systems::GetJumpGate"),
+    ("get-contracts", "responses::contracts::ListContracts"),
+    ("get-contract", "responses::contracts::GetContract"),
+    ("accept-contract", "responses::contracts::AcceptContract"),
+    ("deliver-contract", "responses::contracts::DeliverContract"),
+    ("fulfill-contract", "responses::contracts::FulfillContract"),
+    ("get-factions", "responses::factions::ListFactions"),
+    ("get-faction", "responses::factions::GetFaction"),
+    ("get-my-ships", "responses::fleet::ListShips"),
+    ("purchase-ship", "responses::fleet::PurchaseShip"),
+    ("get-my-ship", "responses::fleet::GetShip"),
+    ("get-my-ship-cargo", "responses::fleet::GetShipCargo"),
+    ("orbit-ship", "responses::fleet::OrbitShip"),
+    ("ship-refine", "responses::fleet::ShipRefine"),
+    ("create-chart", "responses::fleet::CreateChart"),
+    ("get-ship-cooldown", "responses::fleet::GetShipCooldown"),
+    ("dock-ship", "responses::fleet::DockShip"),
+    ("create-survey", "responses::fleet::CreateSurvey"),
+    ("extract-resources", "responses::fleet::ExtractResources"),
+    ("jettison", "responses::fleet::JettisonCargo"),
+    ("jump-ship", "responses::fleet::JumpShip"),
+    ("navigate-ship", "responses::fleet::NavigateShip"),
+    ("patch-ship-nav", "responses::fleet::PatchShipNav"),
+    ("get-ship-nav", "responses::fleet::GetShipNav"),
+    ("warp-ship", "responses::fleet::WarpShip"),
+    ("sell-cargo", "responses::fleet::SellCargo"),
+    ("create-ship-system-scan", "responses::fleet::ScanSystems"),
+    (
+        "create-ship-waypoint-scan",
+        "responses::fleet::ScanWaypoints",
+    ),
+    ("create-ship-ship-scan", "responses::fleet::ScanShips"),
+    ("refuel-ship", "responses::fleet::RefuelShip"),
+    ("purchase-cargo", "responses::fleet::PurchaseCargo"),
+    ("transfer-cargo", "responses::fleet::TransferCargo"),
+    ("negotiate-contract", "responses::fleet::NegotiateContract"),
+];
+
+/// names for inline schemas, keyed by "Owner.property". anything not
+/// listed is called Owner + Property
+const NAMES: &[(&str, &str)] = &[
+    ("FactionTrait.symbol", "FactionTraitSymbols"),
+    ("MarketTradeGood.supply", "SupplyLevel"),
+    ("MarketTransaction.type", "MarketTransactionTypes"),
+    ("ShipEngine.symbol", "ShipEngineSymbols"),
+    ("ShipFrame.symbol", "ShipFrameSymbols"),
+    ("ShipFuel.consumed", "ShipFuelConsumption"),
+    ("ShipModule.symbol", "ShipModuleSymbols"),
+    ("ShipMount.deposits", "Deposits"),
+    ("ShipMount.symbol", "ShipMountSymbols"),
+    ("ShipReactor.symbol", "ShipReactorSymbols"),
+    ("Shipyard.shipTypes", "ShipTypeObject"),
+    ("get-status.stats", "Stats"),
+    ("ship-refine.consumed", "Produce"),
+    ("ship-refine.produced", "Produce"),
+];
+
+/// strings that are symbols, and which kind, keyed by "Owner.property"
+/// or just the property. "String" is for a name that looks like one but
+/// isn't (a shipyard transaction's shipSymbol is the ship's type)
+const SYMBOLS: &[(&str, &str)] = &[
+    ("sectorSymbol", "SectorSymbol"),
+    ("systemSymbol", "SystemSymbol"),
+    ("waypointSymbol", "WaypointSymbol"),
+    ("destinationSymbol", "WaypointSymbol"),
+    ("headquarters", "WaypointSymbol"),
+    ("shipSymbol", "ShipSymbol"),
+    ("factionSymbol", "FactionSymbol"),
+    ("ShipyardTransaction.shipSymbol", "String"),
+    ("ConnectedSystem.symbol", "SystemSymbol"),
+    ("Faction.symbol", "FactionSymbol"),
+    ("Market.symbol", "WaypointSymbol"),
+    ("ScannedShip.symbol", "ShipSymbol"),
+    ("ScannedSystem.symbol", "SystemSymbol"),
+    ("ScannedWaypoint.symbol", "WaypointSymbol"),
+    ("Ship.symbol", "ShipSymbol"),
+    ("ShipNavRouteWaypoint.symbol", "WaypointSymbol"),
+    ("Shipyard.symbol", "WaypointSymbol"),
+    ("Survey.symbol", "WaypointSymbol"),
+    ("System.symbol", "SystemSymbol"),
+    ("SystemFaction.symbol", "FactionSymbol"),
+    ("SystemWaypoint.symbol", "WaypointSymbol"),
+    ("Waypoint.symbol", "WaypointSymbol"),
+    ("WaypointFaction.symbol", "FactionSymbol"),
+    ("WaypointOrbital.symbol", "WaypointSymbol"),
+];
+
+/// properties the server is stricter about than the spec, keyed by
+/// "Owner.property" and written as they'd be inside api::schema
+const TYPES: &[(&str, &str)] = &[
+    ("Produce.tradeSymbol", "TradeSymbol"),
+    ("ShipCargo.capacity", "u32"),
+    ("ShipCargo.units", "u32"),
+];
+
+/// operations that sometimes answer 204 No Content without the spec
+/// saying so, like a ship's cooldown when it hasn't got one
+const EMPTY: &[&str] = &["get-ship-cooldown"];
+
+/// operations the server has and the spec doesn't, with the schema of
+/// their "data". they're generated after everything in the spec
+pub const MISSING: &[(&str, &str)] = &[(
+    "negotiate-contract",
+    r##"{
+        "type": "object",
+        "properties": { "contract": { "$ref": "#/components/schemas/Contract" } },
+        "required": ["contract"]
+    }"##,
+)];
+
+/// enums listing things the game keeps adding to: goods, traits, ship
+/// types and parts. one the spec doesn't know yet is Unknown, like
+/// SupplyLevel's, rather than failing the whole response
+pub const OPEN: &[&str] = &[
+    "FactionTraitSymbols",
+    "ShipEngineSymbols",
+    "ShipFrameSymbols",
+    "ShipModuleSymbols",
+    "ShipMountSymbols",
+    "Deposits",
+    "ShipReactorSymbols",
+    "ShipType",
+    "TradeSymbol",
+    "WaypointTraitSymbol",
+    "WaypointType",
+];
+
+/// types src/api/schema.rs writes by hand, which only get referred to here
+const HANDWRITTEN: &[&str] = &["SupplyLevel", "Symbolic", "TypedSymbolic"];
+const SYMBOL_TYPES: &str = "FactionSymbol, SectorSymbol, ShipSymbol, SystemSymbol, WaypointSymbol";
+
+const STRUCT_DERIVES: &str = "Serialize, Deserialize, Debug, Clone, PartialEq";
+const ENUM_DERIVES: &str =
+    "Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord";
+const OPEN_ENUM_DERIVES: &str =
+    "Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord";
+
+const HEADER: &str = "// generated by `cargo xtask schema` from spec/SpaceTraders.json.
+// don't edit it by hand; change the spec or xtask/src/schema.rs instead
+";
+
+fn lookup<'a>(table: &[(&str, &'a str)], key: &str) -> Option<&'a str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+fn is_symbolic(schema: &Schema) -> bool {
+    //! { symbol: string } and nothing else, which is what Symbolic is
+    let keys: Vec<&str> = schema
+        .properties
+        .0
+        .iter()
+        .map(|(k, _)| k.as_str())
+        .collect();
+    keys == ["symbol"]
+        && schema.properties.get("symbol").unwrap().kind.as_deref() == Some("string")
+        && schema.properties.get("symbol").unwrap().values.is_empty()
+}
+
+fn is_typed_symbolic(spec: &Spec, schema: &Schema) -> bool {
+    //! { symbol: SomeEnum, name, description }, which is TypedSymbolic<SomeEnum>
+    let keys: Vec<&str> = schema
+        .properties
+        .0
+        .iter()
+        .map(|(k, _)| k.as_str())
+        .collect();
+    keys == ["symbol", "name", "description"]
+        && schema.required.len() == 3
+        && !spec
+            .resolve(schema.properties.get("symbol").unwrap())
+            .values
+            .is_empty()
+}
+
+fn integer(schema: &Schema) -> &'static str {
+    match (schema.format.as_deref(), schema.minimum, schema.maximum) {
+        (Some("int64"), _, _) => "i64",
+        (_, Some(min), Some(max)) if min >= 0.0 && max <= 255.0 => "u8",
+        (_, Some(min), _) if min >= 0.0 => "u32",
+        _ => "i32",
+    }
+}
+
+fn field_name(key: &str) -> String {
+    match key {
+        "type" => "kind".to_owned(),
+        "yield" => "yield_amount".to_owned(),
+        key => snake_case(key),
+    }
+}
+
+fn variant_name(value: &str) -> String {
+    //! "ENGINE_ION_DRIVE_II" -> "EngineIonDriveIi"
+    value
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| word[..1].to_uppercase() + &word[1..].to_lowercase())
+        .collect()
+}
+
+fn screaming_snake(variant: &str) -> String {
+    //! what serde's rename_all = "SCREAMING_SNAKE_CASE" turns a variant into
+    let mut name = String::new();
+    for (i, c) in variant.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_uppercase());
+    }
+    name
+}
+
+fn docs(out: &mut String, indent: &str, description: &Option<String>) {
+    for line in description.iter().flat_map(|d| d.lines()) {
+        writeln!(out, "{indent}/// {line}").unwrap();
+    }
+}
+
+/// writes one module's worth of types
+struct Module<'a> {
+    spec: &'a Spec,
+    /// how to get at api::schema from here: "" inside it, "schema::" outside
+    prefix: &'a str,
+    out: String,
+    /// inline schemas named along the way, still to be written
+    pending: Vec<(String, &'a Schema)>,
+    named: BTreeSet<String>,
+}
+
+impl<'a> Module<'a> {
+    fn new(spec: &'a Spec, prefix: &'a str) -> Self {
+        Self {
+            spec,
+            prefix,
+            out: String::new(),
+            pending: Vec::new(),
+            named: BTreeSet::new(),
+        }
+    }
+
+    fn inline(&mut self, owner: &str, key: &str, schema: &'a Schema) -> String {
+        //! the name of a type the spec only describes inline, queueing it to be written
+        let name = lookup(NAMES, &format!("{owner}.{key}"))
+            .map(str::to_owned)
+            .unwrap_or_else(|| pascal_case(owner) + &pascal_case(key));
+        if HANDWRITTEN.contains(&name.as_str()) {
+            return format!("{}{name}", self.prefix);
+        }
+        if self.named.insert(name.clone()) {
+            self.pending.push((name.clone(), schema));
+        }
+        name
+    }
+
+    fn symbol(&self, owner: &str, key: &str) -> String {
+        //! a string property, which may be one of the symbol newtypes
+        match lookup(SYMBOLS, &format!("{owner}.{key}")).or_else(|| lookup(SYMBOLS, key)) {
+            None | Some("String") => "String".to_owned(),
+            Some(symbol) => format!("{}{symbol}", self.prefix),
+        }
+    }
+
+    fn rust_type(&mut self, owner: &str, key: &str, schema: &'a Schema) -> String {
+        if let Some(rust_type) = lookup(TYPES, &format!("{owner}.{key}")) {
+            return match rust_type.starts_with(char::is_uppercase) {
+                true => format!("{}{rust_type}", self.prefix),
+                false => rust_type.to_owned(),
+            };
+        }
+        if let Some(name) = schema.ref_name() {
+            return format!("{}{name}", self.prefix);
+        }
+        match schema.kind.as_deref() {
+            Some("array") => {
+                let items = schema.items.as_deref().expect("an array without items");
+                format!("Vec<{}>", self.rust_type(owner, key, items))
+            }
+            Some("integer") => integer(schema).to_owned(),
+            Some("number") => "f64".to_owned(),
+            Some("boolean") => "bool".to_owned(),
+            Some("object") if is_symbolic(schema) => format!("{}Symbolic", self.prefix),
+            Some("object") => self.inline(owner, key, schema),
+            _ if !schema.values.is_empty() => self.inline(owner, key, schema),
+            _ if schema.format.as_deref() == Some("date-time") => "DateTime<Utc>".to_owned(),
+            _ => self.symbol(owner, key),
+        }
+    }
+
+    fn item(&mut self, name: &str, owner: &str, schema: &'a Schema) {
+        //! writes name, then anything inline it needed a name for
+        self.write_item(name, owner, schema);
+        while !self.pending.is_empty() {
+            let (name, schema) = self.pending.remove(0);
+            self.write_item(&name, &name, schema);
+        }
+    }
+
+    fn write_item(&mut self, name: &str, owner: &str, schema: &'a Schema) {
+        self.named.insert(name.to_owned());
+        writeln!(self.out).unwrap();
+        if !schema.values.is_empty() {
+            return self.write_enum(name, schema);
+        }
+        match schema.kind.as_deref() {
+            Some("object") if is_symbolic(schema) => {
+                let symbolic = match self.symbol(owner, "symbol").as_str() {
+                    "String" => "Symbolic".to_owned(),
+                    symbol => format!("Symbolic<{symbol}>"),
+                };
+                docs(&mut self.out, "", &schema.description);
+                writeln!(self.out, "pub type {name} = {}{symbolic};", self.prefix).unwrap();
+            }
+            Some("object") if is_typed_symbolic(self.spec, schema) => {
+                let symbol = schema.properties.get("symbol").unwrap();
+                let symbol = self.rust_type(owner, "symbol", symbol);
+                docs(&mut self.out, "", &schema.description);
+                writeln!(
+                    self.out,
+                    "pub type {name} = {}TypedSymbolic<{symbol}>;",
+                    self.prefix
+                )
+                .unwrap();
+            }
+            Some("object") => self.write_struct(name, owner, schema),
+            _ => {
+                let rust_type = self.rust_type(owner, "", schema);
+                docs(&mut self.out, "", &schema.description);
+                writeln!(self.out, "pub type {name} = {rust_type};").unwrap();
+            }
+        }
+    }
+
+    fn write_struct(&mut self, name: &str, owner: &str, schema: &'a Schema) {
+        let mut fields = String::new();
+        for (key, property) in &schema.properties.0 {
+            let required = schema.required.contains(key);
+            let resolved = self.spec.resolve(property);
+            let rust_type = self.rust_type(owner, key, property);
+            let field = field_name(key);
+            docs(&mut fields, "    ", &property.description);
+
+            let mut attributes = Vec::new();
+            if field != snake_case(key) {
+                attributes.push(format!("rename = {key:?}"));
+            }
+            let date = resolved.format.as_deref() == Some("date-time");
+            let rust_type = match (required, resolved.kind.as_deref()) {
+                (true, _) => rust_type,
+                (false, Some("array")) => {
+                    attributes.push("default".to_owned());
+                    rust_type
+                }
+                (false, _) => format!("Option<{rust_type}>"),
+            };
+            match (date, required) {
+                (true, true) => attributes.push("with = \"date_time\"".to_owned()),
+                (true, false) => {
+                    attributes.push("default".to_owned());
+                    attributes.push("with = \"optional_date_time\"".to_owned());
+                }
+                _ => {}
+            }
+            if !attributes.is_empty() {
+                writeln!(fields, "    #[serde({})]", attributes.join(", ")).unwrap();
+            }
+            writeln!(fields, "    pub {field}: {rust_type},").unwrap();
+        }
+
+        docs(&mut self.out, "", &schema.description);
+        writeln!(self.out, "#[derive({STRUCT_DERIVES})]").unwrap();
+        if schema
+            .properties
+            .0
+            .iter()
+            .any(|(key, _)| key.chars().any(char::is_uppercase))
+        {
+            writeln!(self.out, "#[serde(rename_all = \"camelCase\")]").unwrap();
+        }
+        writeln!(self.out, "pub struct {name} {{").unwrap();
+        self.out.push_str(&fields);
+        writeln!(self.out, "}}").unwrap();
+    }
+
+    fn write_enum(&mut self, name: &str, schema: &Schema) {
+        //! the enum, plus Display and FromStr using the same names as serde.
+        //! an OPEN one also gets Unknown, and goes through String for serde
+        let open = OPEN.contains(&name);
+        let variants: Vec<(String, &String)> = schema
+            .values
+            .iter()
+            .map(|value| (variant_name(value), value))
+            .collect();
+        let out = &mut self.out;
+        docs(out, "", &schema.description);
+        match open {
+            true => {
+                writeln!(out, "#[derive({OPEN_ENUM_DERIVES})]").unwrap();
+                writeln!(out, "#[serde(from = \"String\", into = \"String\")]").unwrap();
+            }
+            false => {
+                writeln!(out, "#[derive({ENUM_DERIVES})]").unwrap();
+                writeln!(out, "#[serde(rename_all = \"SCREAMING_SNAKE_CASE\")]").unwrap();
+            }
+        }
+        writeln!(out, "pub enum {name} {{").unwrap();
+        for (variant, value) in &variants {
+            if !open && screaming_snake(variant) != **value {
+                writeln!(out, "    #[serde(rename = {value:?})]").unwrap();
+            }
+            writeln!(out, "    {variant},").unwrap();
+        }
+        if open {
+            writeln!(out, "    /// one the spec doesn't list (yet), by its name").unwrap();
+            writeln!(out, "    Unknown(String),").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out).unwrap();
+        writeln!(out, "impl {name} {{").unwrap();
+        match open {
+            true => writeln!(out, "    pub fn as_str(&self) -> &str {{").unwrap(),
+            false => writeln!(out, "    pub fn as_str(&self) -> &'static str {{").unwrap(),
+        }
+        writeln!(out, "        match self {{").unwrap();
+        for (variant, value) in &variants {
+            writeln!(out, "            {name}::{variant} => {value:?},").unwrap();
+        }
+        if open {
+            writeln!(out, "            {name}::Unknown(name) => name,").unwrap();
+        }
+        writeln!(out, "        }}").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out).unwrap();
+        writeln!(out, "impl fmt::Display for {name} {{").unwrap();
+        writeln!(
+            out,
+            "    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {{ f.write_str(self.as_str()) }}"
+        )
+        .unwrap();
+        writeln!(out, "}}").unwrap();
+
+        if open {
+            writeln!(out).unwrap();
+            writeln!(out, "impl From<String> for {name} {{").unwrap();
+            writeln!(out, "    fn from(name: String) -> Self {{").unwrap();
+            writeln!(out, "        match name.as_str() {{").unwrap();
+            for (variant, value) in &variants {
+                writeln!(out, "            {value:?} => {name}::{variant},").unwrap();
+            }
+            writeln!(out, "            _ => {name}::Unknown(name),").unwrap();
+            writeln!(out, "        }}").unwrap();
+            writeln!(out, "    }}").unwrap();
+            writeln!(out, "}}").unwrap();
+
+            writeln!(out).unwrap();
+            writeln!(out, "impl From<{name}> for String {{").unwrap();
+            writeln!(out, "    fn from(value: {name}) -> Self {{").unwrap();
+            writeln!(out, "        match value {{").unwrap();
+            writeln!(out, "            {name}::Unknown(name) => name,").unwrap();
+            writeln!(out, "            value => value.as_str().to_owned(),").unwrap();
+            writeln!(out, "        }}").unwrap();
+            writeln!(out, "    }}").unwrap();
+            writeln!(out, "}}").unwrap();
+        }
+
+        writeln!(out).unwrap();
+        writeln!(out, "impl FromStr for {name} {{").unwrap();
+        writeln!(out, "    type Err = {}ParseEnumError;", self.prefix).unwrap();
+        writeln!(out).unwrap();
+        if open {
+            writeln!(
+                out,
+                "    fn from_str(s: &str) -> Result<Self, Self::Err> {{ Ok(s.to_owned().into()) }}"
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                out,
+                "    fn from_str(s: &str) -> Result<Self, Self::Err> {{"
+            )
+            .unwrap();
+            writeln!(out, "        match s {{").unwrap();
+            for (variant, value) in &variants {
+                writeln!(out, "            {value:?} => Ok({name}::{variant}),").unwrap();
+            }
+            writeln!(
+                out,
+                "            _ => Err({}ParseEnumError::new({name:?}, s)),",
+                self.prefix
+            )
+            .unwrap();
+            writeln!(out, "        }}").unwrap();
+            writeln!(out, "    }}").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+}
+
+fn schema_rs(spec: &Spec) -> String {
+    let mut module = Module::new(spec, "");
+    for (name, schema) in &spec.components.schemas.0 {
+        module.item(name, name, schema);
+    }
+    format!(
+        "{HEADER}
+use super::{{date_time, optional_date_time, ParseEnumError, SupplyLevel, Symbolic, TypedSymbolic}};
+use super::{{{SYMBOL_TYPES}}};
+use chrono::{{DateTime, Utc}};
+use serde::{{Deserialize, Serialize}};
+use std::fmt;
+use std::str::FromStr;
+{}",
+        module.out
+    )
+}
+
+fn responses_rs(module: &str, body: &str) -> String {
+    let mut out = HEADER.to_owned();
+    if module.is_empty() {
+        out.push_str(
+            "
+/*
+The responses module contains information about what the different
+endpoints return. It does not contain any information about how to
+connect to those endpoints.
+*/
+",
+        );
+    }
+    out.push('\n');
+    if body.contains("schema::") {
+        out.push_str("use crate::api::schema;\n");
+    }
+    if body.contains("#[derive(") {
+        out.push_str("use serde::{Deserialize, Serialize};\n");
+    }
+    if body.contains("impl fmt::Display") {
+        out.push_str("use std::fmt;\nuse std::str::FromStr;\n");
+    }
+    if module.is_empty() {
+        out.push('\n');
+        let mut modules: Vec<&str> = RESPONSES
+            .iter()
+            .filter_map(|(_, path)| path.strip_prefix("responses::")?.split_once("::"))
+            .map(|(module, _)| module)
+            .collect();
+        modules.sort();
+        modules.dedup();
+        for module in modules {
+            writeln!(out, "pub mod {module};").unwrap();
+        }
+    }
+    out.push_str(body);
+    out
+}
+
+pub fn generate(spec: &Spec) -> Vec<(String, String)> {
+    //! every file this writes, relative to the repo root, and what goes in it
+    let missing: Vec<(&str, Schema)> = MISSING
+        .iter()
+        .map(|(id, data)| (*id, serde_json::from_str(data).unwrap()))
+        .collect();
+    let mut responses: Vec<(&str, &Schema, bool)> = Vec::new();
+    for operation in spec.operations() {
+        let schema = match operation.json_response() {
+            Some(schema) => spec.resolve(schema),
+            None => continue,
+        };
+        // everything but the status endpoint wraps its result in "data"
+        let data = schema.properties.get("data").unwrap_or(schema);
+        responses.push((&operation.operation_id, data, operation.may_be_empty()));
+    }
+    responses.extend(missing.iter().map(|(id, data)| (*id, data, false)));
+
+    let mut modules: BTreeMap<&str, Module> = BTreeMap::new();
+    for (id, data, may_be_empty) in responses {
+        let path = match lookup(RESPONSES, id) {
+            Some(path) => path.strip_prefix("responses::").unwrap(),
+            None => continue,
+        };
+        let (module_name, name) = path.rsplit_once("::").unwrap_or(("", path));
+        let module = modules
+            .entry(module_name)
+            .or_insert_with(|| Module::new(spec, "schema::"));
+        if data.ref_name().is_some() || data.kind.as_deref() == Some("array") {
+            let rust_type = module.rust_type(id, "", data);
+            let rust_type = match may_be_empty || EMPTY.contains(&id) {
+                true => format!("Option<{rust_type}>"),
+                false => rust_type,
+            };
+            writeln!(module.out).unwrap();
+            writeln!(module.out, "pub type {name} = {rust_type};").unwrap();
+        } else {
+            module.item(name, id, data);
+        }
+    }
+    modules
+        .entry("")
+        .or_insert_with(|| Module::new(spec, "schema::"));
+
+    let mut files = vec![("src/api/schema/generated.rs".to_owned(), schema_rs(spec))];
+    for (name, module) in &modules {
+        let path = match *name {
+            "" => "src/api/responses.rs".to_owned(),
+            name => format!("src/api/responses/{name}.rs"),
+        };
+        files.push((path, responses_rs(name, &module.out)));
+    }
+    files
+}
@@ -0,0 +1,200 @@
+/*
+Just enough of OpenAPI 3 to read spec/SpaceTraders.json: schemas,
+and the responses of each operation. Objects keep the order
+their properties were written in, which serde_json's own Map doesn't.
+*/
+
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde_json::{json, Value};
+use std::fmt;
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// a JSON object with its keys in the order they were written
+#[derive(Debug)]
+pub struct Ordered<T>(pub Vec<(String, T)>);
+
+impl<T> Default for Ordered<T> {
+    fn default() -> Self { Self(Vec::new()) }
+}
+
+impl<T> Ordered<T> {
+    pub fn get(&self, key: &str) -> Option<&T> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Ordered<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OrderedVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for OrderedVisitor<T> {
+            type Value = Ordered<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str("an object") }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Ordered(entries))
+            }
+        }
+
+        deserializer.deserialize_map(OrderedVisitor(PhantomData))
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Schema {
+    #[serde(rename = "$ref")]
+    pub reference: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub format: Option<String>,
+    pub description: Option<String>,
+    pub properties: Ordered<Schema>,
+    pub required: Vec<String>,
+    pub items: Option<Box<Schema>>,
+    #[serde(rename = "enum")]
+    pub values: Vec<String>,
+    pub example: Option<Value>,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+}
+
+impl Schema {
+    pub fn ref_name(&self) -> Option<&str> { self.reference.as_deref()?.rsplit('/').next() }
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct MediaType {
+    pub schema: Schema,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Response {
+    #[serde(default)]
+    pub content: Ordered<MediaType>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    pub operation_id: String,
+    pub responses: Ordered<Response>,
+}
+
+impl Operation {
+    pub fn json_response(&self) -> Option<&Schema> {
+        //! the schema of the first successful JSON response
+        self.responses
+            .0
+            .iter()
+            .filter(|(status, _)| status.starts_with('2'))
+            .find_map(|(_, response)| response.content.get("application/json"))
+            .map(|media| &media.schema)
+    }
+
+    pub fn may_be_empty(&self) -> bool {
+        //! whether it can answer 204 No Content instead
+        self.responses.get("204").is_some()
+    }
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Components {
+    pub schemas: Ordered<Schema>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Spec {
+    pub paths: Ordered<Ordered<Operation>>,
+    pub components: Components,
+}
+
+impl Spec {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    pub fn resolve<'a>(&'a self, schema: &'a Schema) -> &'a Schema {
+        match schema.ref_name() {
+            Some(name) => self
+                .components
+                .schemas
+                .get(name)
+                .unwrap_or_else(|| panic!("nothing in the spec called {}", name)),
+            None => schema,
+        }
+    }
+
+    pub fn operations(&self) -> impl Iterator<Item = &Operation> {
+        self.paths
+            .0
+            .iter()
+            .flat_map(|(_, methods)| methods.0.iter().map(|(_, op)| op))
+    }
+
+    pub fn example(&self, schema: &Schema) -> Value {
+        //! a payload for schema, filled in the way the docs' "example value"
+        //! tab does it: "string", 0, the first enum value, one of everything
+        if let Some(example) = &schema.example {
+            return example.clone();
+        }
+        let schema = self.resolve(schema);
+        if let Some(example) = &schema.example {
+            return example.clone();
+        }
+        if let Some(first) = schema.values.first() {
+            return json!(first);
+        }
+        match schema.kind.as_deref() {
+            Some("object") => {
+                let fields = schema
+                    .properties
+                    .0
+                    .iter()
+                    .map(|(name, property)| (name.clone(), self.example(property)))
+                    .collect();
+                Value::Object(fields)
+            }
+            Some("array") => match &schema.items {
+                Some(items) => json!([self.example(items)]),
+                None => json!([]),
+            },
+            Some("integer") | Some("number") => json!(schema.minimum.unwrap_or(0.0) as i64),
+            Some("boolean") => json!(true),
+            _ => match schema.format.as_deref() {
+                Some("date-time") => json!("2019-08-24T14:15:22Z"),
+                _ => json!("string"),
+            },
+        }
+    }
+}
+
+pub fn pascal_case(name: &str) -> String {
+    //! "get-my-ships" -> "GetMyShips"
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word[..1].to_uppercase() + &word[1..])
+        .collect()
+}
+
+pub fn snake_case(name: &str) -> String {
+    //! "ShipNavRoute" -> "ship_nav_route", "purchasePrice" -> "purchase_price"
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake.replace('-', "_")
+}
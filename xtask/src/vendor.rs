@@ -0,0 +1,59 @@
+/*
+Replaces spec/SpaceTraders.json with the published one, the
+reference/SpaceTraders.json in SpaceTraders' api-docs repo, at a
+commit you name. That commit goes in spec/UPSTREAM, so there's a
+record of which version everything was generated from.
+
+It only downloads (with curl, so xtask doesn't need an HTTP client)
+and checks the result reads as a spec. Regenerating, and sorting out
+whatever drift the new spec turns up, is still up to you: see
+spec/README.md.
+*/
+
+use crate::spec::Spec;
+use std::path::Path;
+use std::process::Command;
+
+pub const REPO: &str = "SpaceTradersAPI/api-docs";
+const FILE: &str = "reference/SpaceTraders.json";
+
+pub fn upstream(root: &Path) -> Option<String> {
+    //! the api-docs commit spec/SpaceTraders.json came from, if it did
+    let text = std::fs::read_to_string(root.join("spec/UPSTREAM")).ok()?;
+    let commit = text.split_whitespace().nth(1)?;
+    Some(commit.to_owned())
+}
+
+pub fn vendor(root: &Path, commit: &str) -> Result<(), Box<dyn std::error::Error>> {
+    // a branch name would move under us, so only take a commit hash
+    if commit.len() < 7 || !commit.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("{commit:?} isn't a commit hash").into());
+    }
+    let url = format!("https://raw.githubusercontent.com/{REPO}/{commit}/{FILE}");
+    let download = root.join("spec/SpaceTraders.json.download");
+    let status = Command::new("curl")
+        .args([
+            "--fail",
+            "--silent",
+            "--show-error",
+            "--location",
+            "--output",
+        ])
+        .arg(&download)
+        .arg(&url)
+        .status()?;
+    if !status.success() {
+        std::fs::remove_file(&download).ok();
+        return Err(format!("couldn't download {url}").into());
+    }
+    if let Err(e) = Spec::load(&download) {
+        std::fs::remove_file(&download).ok();
+        return Err(format!("{url} doesn't read as a spec: {e}").into());
+    }
+    std::fs::rename(&download, root.join("spec/SpaceTraders.json"))?;
+    std::fs::write(
+        root.join("spec/UPSTREAM"),
+        format!("{REPO} {commit} {FILE}\n"),
+    )?;
+    Ok(())
+}